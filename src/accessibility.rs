@@ -0,0 +1,106 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Accessibility options complementing the colors of the validation indicators with text.
+
+use eframe::egui::{self, Id, Response, RichText, Ui};
+
+/// Color of the markers of invalid options.
+const INVALID_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(150, 0, 0, 100);
+
+fn text_cues_id() -> Id {
+    Id::new("guiorder_text_cues")
+}
+
+/// Check whether the validation indicators should be accompanied by text.
+/// The setting is persisted across application restarts.
+pub(crate) fn text_cues(ctx: &egui::Context) -> bool {
+    ctx.data_mut(|d| d.get_persisted::<bool>(text_cues_id()))
+        .unwrap_or(false)
+}
+
+/// Toggle for accompanying the validation indicators with text.
+pub(crate) fn text_cues_toggle(ui: &mut Ui) {
+    let mut enabled = text_cues(ui.ctx());
+    if ui
+        .toggle_value(&mut enabled, "Aa")
+        .on_hover_ui(|ui| {
+            ui.label("Describe invalid and missing options with text in addition to colors.");
+        })
+        .changed()
+    {
+        ui.ctx()
+            .data_mut(|d| d.insert_persisted(text_cues_id(), enabled));
+    }
+}
+
+/// Get the text of the marker of an invalid option.
+fn marker_text(cue: &str, text_cues: bool) -> String {
+    if text_cues {
+        format!("❗ {}", cue)
+    } else {
+        String::from("❗")
+    }
+}
+
+/// Red marker of an invalid option. If text cues are enabled, the marker is followed by the `cue`.
+pub(crate) fn invalid_marker(ui: &mut Ui, cue: &str) -> Response {
+    let text = marker_text(cue, text_cues(ui.ctx()));
+    ui.label(RichText::new(text).color(INVALID_COLOR))
+}
+
+/// Red message describing an invalid option, preceded by the marker.
+/// The message is always written, whether the text cues are enabled or not.
+pub(crate) fn invalid_message(message: &str) -> RichText {
+    RichText::new(format!("❗ {}", message))
+        .font(egui::FontId::monospace(12.0))
+        .color(INVALID_COLOR)
+}
+
+/// Heading of a section containing invalid options.
+pub(crate) fn invalid_heading(ctx: &egui::Context, heading: &str) -> RichText {
+    let heading = if text_cues(ctx) {
+        format!("{} (invalid)", heading.trim_end())
+    } else {
+        heading.to_owned()
+    };
+
+    RichText::new(heading)
+        .font(egui::FontId::monospace(12.0))
+        .color(INVALID_COLOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marker_with_cues() {
+        assert_eq!(marker_text("not found", false), "❗");
+        assert_eq!(marker_text("not found", true), "❗ not found");
+    }
+
+    #[test]
+    fn messages_independent_of_cues() {
+        assert_eq!(
+            invalid_message("The same path is used for YAML, CSV.").text(),
+            "❗ The same path is used for YAML, CSV."
+        );
+    }
+
+    #[test]
+    fn cues_disabled_by_default() {
+        let ctx = egui::Context::default();
+        assert!(!text_cues(&ctx));
+        assert_eq!(
+            invalid_heading(&ctx, "Leaflet classification").text(),
+            "Leaflet classification"
+        );
+
+        ctx.data_mut(|d| d.insert_persisted(text_cues_id(), true));
+        assert_eq!(
+            invalid_heading(&ctx, "Leaflet classification").text(),
+            "Leaflet classification (invalid)"
+        );
+    }
+}
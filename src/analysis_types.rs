@@ -3,9 +3,13 @@
 
 //! Analysis types and their parameters.
 
-use eframe::egui::Ui;
+use eframe::egui::{self, RichText, Ui};
 
-use crate::GuiAnalysis;
+use crate::{
+    selection::is_blank,
+    structure::{self, SelectionCount},
+    GuiAnalysis,
+};
 
 /// Type of analysis to perform.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -17,6 +21,26 @@ pub(crate) enum AnalysisType {
     CGOrder,
 }
 
+impl AnalysisType {
+    /// Get the short name of the analysis type shown in the badge next to the selected type.
+    fn badge(self) -> &'static str {
+        match self {
+            AnalysisType::AAOrder => "AA",
+            AnalysisType::UAOrder => "UA",
+            AnalysisType::CGOrder => "CG",
+        }
+    }
+
+    /// Get the description of the order parameters calculated by the analysis type.
+    fn description(self) -> &'static str {
+        match self {
+            AnalysisType::AAOrder => "Atomistic order parameters will be calculated.",
+            AnalysisType::UAOrder => "United-atom order parameters will be calculated.",
+            AnalysisType::CGOrder => "Coarse-grained order parameters will be calculated.",
+        }
+    }
+}
+
 impl From<gorder::input::AnalysisType> for AnalysisType {
     fn from(value: gorder::input::AnalysisType) -> Self {
         match value {
@@ -28,20 +52,20 @@ impl From<gorder::input::AnalysisType> for AnalysisType {
 }
 
 /// Parameters for calculating AA order.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct AAParams {
     heavy_atoms: String,
     hydrogens: String,
 }
 
 /// Parameters for calculating CG order.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct CGParams {
     beads: String,
 }
 
 /// Parameters for calculating UA order.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct UAParams {
     saturated: String,
     unsaturated: String,
@@ -49,13 +73,54 @@ struct UAParams {
 }
 
 /// Parameters for all analysis types.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub(crate) struct AnalysisTypeParams {
     aa_params: AAParams,
     ua_params: UAParams,
     cg_params: CGParams,
 }
 
+impl AnalysisTypeParams {
+    /// Check whether any selection is specified for the analysis type.
+    fn is_specified(&self, analysis_type: AnalysisType) -> bool {
+        match analysis_type {
+            AnalysisType::AAOrder => {
+                !self.aa_params.heavy_atoms.is_empty() || !self.aa_params.hydrogens.is_empty()
+            }
+            AnalysisType::UAOrder => {
+                !self.ua_params.saturated.is_empty()
+                    || !self.ua_params.unsaturated.is_empty()
+                    || !self.ua_params.ignore.is_empty()
+            }
+            AnalysisType::CGOrder => !self.cg_params.beads.is_empty(),
+        }
+    }
+
+    /// Check whether any selection is specified for an analysis type other than `active`.
+    fn has_inactive(&self, active: AnalysisType) -> bool {
+        [
+            AnalysisType::AAOrder,
+            AnalysisType::UAOrder,
+            AnalysisType::CGOrder,
+        ]
+        .into_iter()
+        .any(|analysis_type| analysis_type != active && self.is_specified(analysis_type))
+    }
+
+    /// Remove the selections of all analysis types other than `active`.
+    fn clear_inactive(&mut self, active: AnalysisType) {
+        if active != AnalysisType::AAOrder {
+            self.aa_params = AAParams::default();
+        }
+        if active != AnalysisType::UAOrder {
+            self.ua_params = UAParams::default();
+        }
+        if active != AnalysisType::CGOrder {
+            self.cg_params = CGParams::default();
+        }
+    }
+}
+
 impl From<gorder::input::AnalysisType> for AnalysisTypeParams {
     fn from(value: gorder::input::AnalysisType) -> Self {
         match value {
@@ -101,17 +166,17 @@ impl From<&GuiAnalysis> for gorder::input::AnalysisType {
             }
             AnalysisType::UAOrder => {
                 let uaparams = &value.analysis_type_params.ua_params;
-                let saturated = match uaparams.saturated.is_empty() {
+                let saturated = match is_blank(&uaparams.saturated) {
                     true => None,
                     false => Some(&uaparams.saturated),
                 };
 
-                let unsaturated = match uaparams.unsaturated.is_empty() {
+                let unsaturated = match is_blank(&uaparams.unsaturated) {
                     true => None,
                     false => Some(&uaparams.unsaturated),
                 };
 
-                let ignore = match uaparams.ignore.is_empty() {
+                let ignore = match is_blank(&uaparams.ignore) {
                     true => None,
                     false => Some(&uaparams.ignore),
                 };
@@ -127,6 +192,41 @@ impl From<&GuiAnalysis> for gorder::input::AnalysisType {
 }
 
 impl GuiAnalysis {
+    /// Describe the analysis type and its selections as rows of the summary of the analysis.
+    pub(crate) fn analysis_type_summary(&self) -> Vec<(&'static str, String)> {
+        let params = &self.analysis_type_params;
+        match self.analysis_type {
+            AnalysisType::AAOrder => vec![
+                ("Analysis type", String::from("atomistic")),
+                ("Heavy atoms", params.aa_params.heavy_atoms.clone()),
+                ("Hydrogens", params.aa_params.hydrogens.clone()),
+            ],
+            AnalysisType::CGOrder => vec![
+                ("Analysis type", String::from("coarse-grained")),
+                ("Beads", params.cg_params.beads.clone()),
+            ],
+            AnalysisType::UAOrder => {
+                let mut rows = vec![("Analysis type", String::from("united-atom"))];
+                for (name, query) in [
+                    ("Saturated", &params.ua_params.saturated),
+                    ("Unsaturated", &params.ua_params.unsaturated),
+                    ("Ignored", &params.ua_params.ignore),
+                ] {
+                    if !is_blank(query) {
+                        rows.push((name, query.clone()));
+                    }
+                }
+                rows
+            }
+        }
+    }
+
+    /// Check whether any saturated or unsaturated carbons are selected for the united-atom analysis.
+    fn has_ua_carbons(&self) -> bool {
+        let params = &self.analysis_type_params.ua_params;
+        !is_blank(&params.saturated) || !is_blank(&params.unsaturated)
+    }
+
     /// Specify the type of analysis to perform and parameters for it.
     pub(super) fn specify_analysis_type(&mut self, ui: &mut Ui) {
         ui.horizontal(|ui| {
@@ -147,18 +247,27 @@ impl GuiAnalysis {
                 AnalysisType::UAOrder,
                 "united-atom",
             );
+
+            ui.label(
+                RichText::new(format!(" {} ", self.analysis_type.badge()))
+                    .font(egui::FontId::monospace(10.0))
+                    .background_color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 40)),
+            )
+            .on_hover_ui(|ui| {
+                ui.label(self.analysis_type.description());
+            });
         });
 
         ui.vertical(|ui| match self.analysis_type {
             AnalysisType::AAOrder => {
-                Self::specify_string(
+                Self::specify_selection(
                     &mut self.analysis_type_params.aa_params.heavy_atoms,
                     ui,
                     "Heavy atoms: ",
                     "Selection of heavy atoms to be used in the analysis.",
                     true,
                 );
-                Self::specify_string(
+                Self::specify_selection(
                     &mut self.analysis_type_params.aa_params.hydrogens,
                     ui,
                     "Hydrogens:   ",
@@ -168,30 +277,44 @@ impl GuiAnalysis {
             }
 
             AnalysisType::UAOrder => {
-                Self::specify_string(
+                Self::specify_selection(
                     &mut self.analysis_type_params.ua_params.saturated,
                     ui,
                     "Saturated carbons:   ",
                     "Selection of saturated carbons to be used in the analysis.",
                     false,
                 );
-                Self::specify_string(
+                Self::specify_selection(
                     &mut self.analysis_type_params.ua_params.unsaturated,
                     ui,
                     "Unsaturated carbons: ",
                     "Selection of unsaturated carbons to be used in the analysis.",
                     false,
                 );
-                Self::specify_string(
+                Self::specify_selection(
                     &mut self.analysis_type_params.ua_params.ignore,
                     ui,
                     "Ignore:              ",
                     "Selection of atoms to be ignored. (Optional)",
                     false,
                 );
+
+                if !self.has_ua_carbons() {
+                    ui.horizontal(|ui| {
+                        ui.add_space(104.0);
+                        ui.label(
+                            RichText::new("no saturated or unsaturated carbons selected")
+                                .font(egui::FontId::proportional(10.0))
+                                .color(egui::Color32::from_rgb(200, 150, 0)),
+                        )
+                        .on_hover_ui(|ui| {
+                            ui.label("United-atom order parameters are only calculated for the selected saturated and unsaturated carbons.");
+                        });
+                    });
+                }
             }
             AnalysisType::CGOrder => {
-                Self::specify_string(
+                Self::specify_selection(
                     &mut self.analysis_type_params.cg_params.beads,
                     ui,
                     "Beads: ",
@@ -200,16 +323,76 @@ impl GuiAnalysis {
                 );
             }
         });
+
+        if self.analysis_type_params.has_inactive(self.analysis_type) {
+            ui.horizontal(|ui| {
+                ui.add_space(104.0);
+                ui.label(
+                    RichText::new("selections of other analysis types are kept but not used")
+                        .font(egui::FontId::proportional(10.0))
+                        .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                )
+                .on_hover_ui(|ui| {
+                    ui.label("Selections specified for the other analysis types are kept so that you can switch back to them. Only the selected analysis type is exported and run.");
+                });
+
+                if ui
+                    .small_button("clear other types")
+                    .on_hover_ui(|ui| {
+                        ui.label("Remove the selections specified for the other analysis types.");
+                    })
+                    .clicked()
+                {
+                    self.analysis_type_params
+                        .clear_inactive(self.analysis_type);
+                }
+            });
+        }
+    }
+
+    /// Estimate the number of ordermaps written for the whole membrane (or for a single leaflet)
+    /// from the selections evaluated in the loaded structure. A map is written for each bond type,
+    /// each heavy atom type (except for coarse-grained analysis), and each molecule type.
+    /// Returns `None` if no structure is loaded or a selection can not be evaluated.
+    pub(crate) fn ordermap_count(&self, ctx: &egui::Context) -> Option<usize> {
+        let count = |query: &str| {
+            if query.trim().is_empty() {
+                Some(SelectionCount::default())
+            } else {
+                structure::count_selection(ctx, query)?.ok()
+            }
+        };
+
+        let params = &self.analysis_type_params;
+        Some(match self.analysis_type {
+            AnalysisType::AAOrder => {
+                let heavy = count(&params.aa_params.heavy_atoms)?;
+                // each hydrogen type forms exactly one bond type
+                let hydrogens = count(&params.aa_params.hydrogens)?;
+                heavy.atom_types + hydrogens.atom_types + heavy.residue_types
+            }
+            AnalysisType::UAOrder => {
+                let saturated = count(&params.ua_params.saturated)?;
+                let unsaturated = count(&params.ua_params.unsaturated)?;
+                let carbons = saturated.atom_types + unsaturated.atom_types;
+                let bonds = 2 * saturated.atom_types + unsaturated.atom_types;
+                carbons + bonds + saturated.residue_types.max(unsaturated.residue_types)
+            }
+            AnalysisType::CGOrder => {
+                // each molecule type has one bond type fewer than bead types and one average map
+                count(&params.cg_params.beads)?.atom_types
+            }
+        })
     }
 
     /// Check that all required options for analysis type have been provided.
     pub(super) fn check_analysis_params_sanity(&self) -> bool {
         match self.analysis_type {
             AnalysisType::AAOrder => {
-                !self.analysis_type_params.aa_params.heavy_atoms.is_empty()
-                    && !self.analysis_type_params.aa_params.hydrogens.is_empty()
+                !is_blank(&self.analysis_type_params.aa_params.heavy_atoms)
+                    && !is_blank(&self.analysis_type_params.aa_params.hydrogens)
             }
-            AnalysisType::CGOrder => !self.analysis_type_params.cg_params.beads.is_empty(),
+            AnalysisType::CGOrder => !is_blank(&self.analysis_type_params.cg_params.beads),
             AnalysisType::UAOrder => true, // no compulsory parameters
         }
     }
@@ -219,6 +402,20 @@ impl GuiAnalysis {
 mod tests {
     use super::*;
 
+    #[test]
+    fn summary_skips_blank_ua_selections() {
+        let mut analysis = GuiAnalysis {
+            analysis_type: AnalysisType::UAOrder,
+            ..Default::default()
+        };
+        analysis.analysis_type_params.ua_params.saturated = String::from("name C2");
+        analysis.analysis_type_params.ua_params.ignore = String::from("   ");
+
+        let rows = analysis.analysis_type_summary();
+        assert!(rows.contains(&("Saturated", String::from("name C2"))));
+        assert!(!rows.iter().any(|(name, _)| *name == "Ignored"));
+    }
+
     #[test]
     fn gorder_to_guiorder_aa_type() {
         assert_eq!(
@@ -394,4 +591,64 @@ mod tests {
             _ => panic!("Invalid analysis type returned."),
         }
     }
+
+    #[test]
+    fn ua_carbons_selected() {
+        let mut analysis =
+            GuiAnalysis {
+                analysis_type: AnalysisType::UAOrder,
+                analysis_type_params: AnalysisTypeParams::from(
+                    gorder::input::AnalysisType::uaorder(None, None, Some("name H*")),
+                ),
+                ..Default::default()
+            };
+        assert!(!analysis.has_ua_carbons());
+        assert!(analysis.check_analysis_params_sanity());
+
+        analysis.analysis_type_params.ua_params.unsaturated = String::from("name C29 C210");
+        assert!(analysis.has_ua_carbons());
+    }
+
+    #[test]
+    fn clear_inactive_types() {
+        let mut params = AnalysisTypeParams::from(gorder::input::AnalysisType::aaorder(
+            "element name carbon",
+            "element name hydrogen",
+        ));
+        params.cg_params.beads = String::from("@membrane");
+
+        assert!(params.has_inactive(AnalysisType::AAOrder));
+        assert!(params.has_inactive(AnalysisType::UAOrder));
+
+        params.clear_inactive(AnalysisType::CGOrder);
+        assert!(!params.has_inactive(AnalysisType::CGOrder));
+        assert!(params.is_specified(AnalysisType::CGOrder));
+        assert!(!params.is_specified(AnalysisType::AAOrder));
+    }
+
+    #[test]
+    fn whitespace_selections_are_missing() {
+        let mut analysis = GuiAnalysis {
+            analysis_type: AnalysisType::AAOrder,
+            analysis_type_params: AnalysisTypeParams::from(gorder::input::AnalysisType::aaorder(
+                "element name carbon",
+                "  ",
+            )),
+            ..Default::default()
+        };
+        assert!(!analysis.check_analysis_params_sanity());
+
+        analysis.analysis_type = AnalysisType::CGOrder;
+        analysis.analysis_type_params.cg_params.beads = String::from(" \t");
+        assert!(!analysis.check_analysis_params_sanity());
+
+        // blank optional selections are not exported
+        analysis.analysis_type = AnalysisType::UAOrder;
+        analysis.analysis_type_params.ua_params.saturated = String::from("name C211 C212");
+        analysis.analysis_type_params.ua_params.ignore = String::from("  ");
+        match gorder::input::AnalysisType::from(&analysis) {
+            gorder::input::AnalysisType::UAOrder { ignore, .. } => assert_eq!(ignore, None),
+            _ => panic!("Invalid analysis type."),
+        }
+    }
 }
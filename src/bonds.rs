@@ -0,0 +1,158 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Validation of the bonds file.
+
+use std::{path::Path, time::SystemTime};
+
+use eframe::egui::{self, Id, Ui};
+
+use crate::{accessibility, paths, GuiAnalysis};
+
+/// Number of seconds after which the modification time of a bonds file is checked again.
+const BONDS_CHECK_INTERVAL: f64 = 2.0;
+
+/// Result of validating a bonds file.
+#[derive(Debug, Clone)]
+struct CachedCheck {
+    modified: Option<SystemTime>,
+    checked_at: f64,
+    error: Option<String>,
+}
+
+/// Check that the content looks like a gorder bonds file.
+/// Each line must contain the number of an atom followed by the numbers of the atoms it is bonded to.
+/// Empty lines and lines starting with `#` are ignored.
+fn validate_bonds(content: &str) -> Result<(), String> {
+    let mut n_bonds = 0;
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let numbers = line
+            .split_whitespace()
+            .map(|item| item.parse::<usize>().ok().filter(|&n| n > 0))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                format!(
+                    "line {} does not consist of atom numbers: '{}'",
+                    i + 1,
+                    line
+                )
+            })?;
+
+        if numbers.len() < 2 {
+            return Err(format!(
+                "line {} does not specify any bonded atoms: '{}'",
+                i + 1,
+                line
+            ));
+        }
+
+        n_bonds += numbers.len() - 1;
+    }
+
+    if n_bonds == 0 {
+        return Err(String::from("the file does not specify any bonds"));
+    }
+
+    Ok(())
+}
+
+/// Read the bonds file and check that it looks like a gorder bonds file.
+pub(crate) fn check_bonds_file(path: impl AsRef<Path>) -> Result<(), String> {
+    let content = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+        format!(
+            "could not read the bonds file '{}': {}",
+            path.as_ref().display(),
+            e
+        )
+    })?;
+    validate_bonds(&content)
+        .map_err(|e| format!("invalid bonds file '{}': {}", path.as_ref().display(), e))
+}
+
+/// Get the problem with the bonds file, if any. Missing files are not reported.
+/// The result is cached for each path and only checked again if the file is modified.
+fn bonds_error(ctx: &egui::Context, path: &str) -> Option<String> {
+    if path.is_empty() {
+        return None;
+    }
+
+    let id = Id::new(("guiorder_bonds_check", path));
+    let now = ctx.input(|i| i.time);
+    let cached = ctx.data_mut(|d| d.get_temp::<CachedCheck>(id));
+
+    if let Some(cached) = &cached {
+        if now - cached.checked_at < BONDS_CHECK_INTERVAL {
+            return cached.error.clone();
+        }
+    }
+
+    let expanded = paths::expand_path(path);
+    let modified = std::fs::metadata(&expanded).and_then(|m| m.modified()).ok();
+
+    let error = match (cached, modified) {
+        (_, None) => None,
+        (Some(cached), Some(_)) if cached.modified == modified => cached.error,
+        _ => check_bonds_file(&expanded).err(),
+    };
+
+    ctx.data_mut(|d| {
+        d.insert_temp(
+            id,
+            CachedCheck {
+                modified,
+                checked_at: now,
+                error: error.clone(),
+            },
+        )
+    });
+
+    error
+}
+
+impl GuiAnalysis {
+    /// Check whether the bonds file, if provided, looks like a gorder bonds file.
+    pub(crate) fn check_bonds_sanity(ctx: &egui::Context, bonds: &str) -> bool {
+        bonds_error(ctx, bonds).is_none()
+    }
+
+    /// Red marker shown below the bonds file if it does not look like a gorder bonds file.
+    pub(crate) fn invalid_bonds_marker(ui: &mut Ui, bonds: &str) {
+        let Some(error) = bonds_error(ui.ctx(), bonds) else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.add_space(104.0);
+            accessibility::invalid_marker(ui, "invalid bonds file").on_hover_ui(|ui| {
+                ui.label(&error);
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_bonds() {
+        assert!(validate_bonds("1 2 3\n2 1\n\n# comment\n3 1\n").is_ok());
+        assert!(validate_bonds("  10   11\n").is_ok());
+    }
+
+    #[test]
+    fn invalid_bonds() {
+        assert!(validate_bonds("").is_err());
+        assert!(validate_bonds("# only a comment\n").is_err());
+        assert!(validate_bonds("1\n").is_err());
+        assert!(validate_bonds("1 2\n0 1\n").is_err());
+        assert!(validate_bonds("[ system ]\n1 2\n").is_err());
+        assert!(check_bonds_file("tests/index.ndx").is_err());
+        assert!(check_bonds_file("tests/nonexistent.bnd").is_err());
+    }
+}
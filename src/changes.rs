@@ -0,0 +1,249 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Comparison of the current analysis options with the last imported configuration.
+
+use eframe::egui::{self, RichText, Ui};
+use gorder::input::Analysis;
+use serde_yaml::Value;
+
+use crate::{GuiAnalysis, GuiOrderApp};
+
+/// Option that differs between the imported and the current configuration.
+#[derive(Debug, Clone, PartialEq)]
+struct Change {
+    /// Path to the option, e.g. `leaflets.heads`.
+    key: String,
+    /// Imported value, if the option was set.
+    old: Option<String>,
+    /// Current value, if the option is set.
+    new: Option<String>,
+}
+
+/// Convert the analysis options into a YAML value of the corresponding gorder analysis.
+fn to_value(analysis: &GuiAnalysis) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(serde_yaml::to_value(Analysis::try_from(analysis)?)?)
+}
+
+/// Write the value on a single line.
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Null => String::from("~"),
+        Value::Sequence(items) => format!(
+            "[{}]",
+            items.iter().map(inline).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Mapping(mapping) => format!(
+            "{{{}}}",
+            mapping
+                .iter()
+                .map(|(key, value)| format!("{}: {}", inline(key), inline(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Tagged(tagged) => format!("{} {}", tagged.tag, inline(&tagged.value)),
+        scalar => serde_yaml::to_string(scalar)
+            .map(|s| s.trim_end().to_owned())
+            .unwrap_or_default(),
+    }
+}
+
+/// Get the value of an option. Options set to null are treated as unset.
+fn option(value: Option<&Value>) -> Option<&Value> {
+    value.filter(|value| !value.is_null())
+}
+
+/// Collect the differences between the two values.
+fn diff(key: &str, old: Option<&Value>, new: Option<&Value>, changes: &mut Vec<Change>) {
+    let (old, new) = (option(old), option(new));
+    if old == new {
+        return;
+    }
+
+    let nested = |name: &Value| match name.as_str() {
+        Some(name) if key.is_empty() => name.to_owned(),
+        Some(name) => format!("{}.{}", key, name),
+        None => format!("{}.{}", key, inline(name)),
+    };
+
+    match (old, new) {
+        (Some(Value::Mapping(old)), Some(Value::Mapping(new))) => {
+            for (name, value) in old {
+                diff(&nested(name), Some(value), new.get(name), changes);
+            }
+            for (name, value) in new.iter().filter(|(name, _)| !old.contains_key(*name)) {
+                diff(&nested(name), None, Some(value), changes);
+            }
+        }
+        (Some(Value::Tagged(old)), Some(Value::Tagged(new))) if old.tag == new.tag => {
+            diff(key, Some(&old.value), Some(&new.value), changes)
+        }
+        _ => changes.push(Change {
+            key: key.to_owned(),
+            old: old.map(inline),
+            new: new.map(inline),
+        }),
+    }
+}
+
+/// Get all options that differ between the imported and the current analysis.
+fn changes(
+    imported: &GuiAnalysis,
+    current: &GuiAnalysis,
+) -> Result<Vec<Change>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut changes = Vec::new();
+    diff(
+        "",
+        Some(&to_value(imported)?),
+        Some(&to_value(current)?),
+        &mut changes,
+    );
+    Ok(changes)
+}
+
+/// Label for a value of an option.
+fn value_label(value: &Option<String>) -> RichText {
+    match value {
+        Some(value) => RichText::new(value).font(egui::FontId::monospace(12.0)),
+        None => RichText::new("(unset)")
+            .font(egui::FontId::monospace(12.0))
+            .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+    }
+}
+
+impl GuiOrderApp {
+    /// Button opening the window listing the changes made since the last import.
+    pub(super) fn show_changes_button(&mut self, ui: &mut Ui) {
+        if ui
+            .add_enabled(
+                self.imported.is_some(),
+                egui::Button::new("🔍 Show changes"),
+            )
+            .on_hover_ui(|ui| {
+                ui.label(
+                    "List the options that were changed since the configuration was imported.",
+                );
+            })
+            .on_disabled_hover_ui(|ui| {
+                ui.label("No configuration has been imported.");
+            })
+            .clicked()
+        {
+            ui.close_menu();
+            self.changes_open = true;
+        }
+    }
+
+    /// Render the window listing the changes made since the last import, if open.
+    pub(super) fn render_changes_window(&mut self, ctx: &egui::Context) {
+        let Some(imported) = self.imported.as_ref().filter(|_| self.changes_open) else {
+            return;
+        };
+
+        let changes = changes(imported, &self.analysis);
+        egui::Window::new("Changes since import")
+            .open(&mut self.changes_open)
+            .collapsible(false)
+            .default_width(500.0)
+            .show(ctx, |ui| match changes {
+                Err(e) => {
+                    ui.label(
+                        RichText::new(format!("Cannot compare the options: {}", e))
+                            .color(egui::Color32::from_rgb(200, 150, 0)),
+                    );
+                }
+                Ok(changes) if changes.is_empty() => {
+                    ui.label(
+                        RichText::new("No changes.")
+                            .font(egui::FontId::monospace(12.0))
+                            .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                    );
+                }
+                Ok(changes) => {
+                    egui::ScrollArea::vertical()
+                        .max_height(400.0)
+                        .show(ui, |ui| {
+                            egui::Grid::new("guiorder_changes_grid")
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for change in changes {
+                                        ui.label(
+                                            RichText::new(&change.key)
+                                                .font(egui::FontId::monospace(12.0))
+                                                .strong(),
+                                        );
+                                        ui.label(value_label(&change.old));
+                                        ui.label("→");
+                                        ui.label(value_label(&change.new));
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_changes_after_import() {
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+
+        let imported = app.imported.clone().unwrap();
+        assert!(changes(&imported, &app.analysis).unwrap().is_empty());
+    }
+
+    #[test]
+    fn list_changed_options() {
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+        let imported = app.imported.clone().unwrap();
+
+        app.analysis.structure = String::from("system.gro");
+        app.analysis.output.output_csv.clear();
+        app.analysis.other_params.n_threads = 8;
+
+        let changes = changes(&imported, &app.analysis).unwrap();
+        assert!(changes.contains(&Change {
+            key: String::from("structure"),
+            old: Some(String::from("tests/pcpepg.tpr")),
+            new: Some(String::from("system.gro")),
+        }));
+        assert!(changes.contains(&Change {
+            key: String::from("n_threads"),
+            old: Some(String::from("4")),
+            new: Some(String::from("8")),
+        }));
+        assert!(changes
+            .iter()
+            .any(|change| change.key == "output_csv" && change.new.is_none()));
+    }
+
+    #[test]
+    fn nested_changes() {
+        let old: Value = serde_yaml::from_str("a: 1\nb: {c: 2, d: [1, 2]}\n").unwrap();
+        let new: Value = serde_yaml::from_str("a: 1\nb: {c: 3, d: [1, 2], e: x}\n").unwrap();
+
+        let mut changes = Vec::new();
+        diff("", Some(&old), Some(&new), &mut changes);
+        assert_eq!(
+            changes,
+            vec![
+                Change {
+                    key: String::from("b.c"),
+                    old: Some(String::from("2")),
+                    new: Some(String::from("3")),
+                },
+                Change {
+                    key: String::from("b.e"),
+                    old: None,
+                    new: Some(String::from("x")),
+                },
+            ]
+        );
+    }
+}
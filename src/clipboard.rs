@@ -0,0 +1,174 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Copying the configuration into the clipboard and importing pasted configurations.
+
+use eframe::egui::{self, Id, RichText, Ui};
+
+use crate::{yaml_header::YamlHeader, GuiOrderApp};
+
+/// Number of seconds for which the confirmation of copying is displayed.
+const CONFIRMATION_DURATION: f64 = 2.0;
+
+fn copied_id() -> Id {
+    Id::new("guiorder_yaml_copied")
+}
+
+/// Check whether the configuration has been copied recently.
+/// Requests a repaint so that the confirmation disappears on time.
+pub(crate) fn recently_copied(ctx: &egui::Context) -> bool {
+    let Some(copied_at) = ctx.data_mut(|d| d.get_temp::<f64>(copied_id())) else {
+        return false;
+    };
+
+    let remaining = CONFIRMATION_DURATION - (ctx.input(|i| i.time) - copied_at);
+    if remaining > 0.0 {
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(remaining));
+        true
+    } else {
+        false
+    }
+}
+
+impl GuiOrderApp {
+    /// Copy the analysis options into the clipboard as the content of a configuration YAML file.
+    fn copy_yaml(&mut self, ctx: &egui::Context) {
+        match Self::config_yaml_with_header(&self.analysis, YamlHeader::get(ctx)) {
+            Ok(yaml) => {
+                ctx.copy_text(yaml);
+                let now = ctx.input(|i| i.time);
+                ctx.data_mut(|d| d.insert_temp(copied_id(), now));
+            }
+            Err(e) => self.open_error_window(e),
+        }
+    }
+
+    /// Button for copying the analysis options into the clipboard.
+    pub(super) fn copy_yaml_button(&mut self, ui: &mut Ui, sane: bool) {
+        if ui
+            .add_enabled(sane, egui::Button::new("📋 Copy YAML"))
+            .on_hover_ui(|ui| {
+                ui.label("Copy the options into the clipboard in the YAML format.");
+            })
+            .on_disabled_hover_ui(|ui| {
+                ui.label("Cannot copy the options: some options are invalid.");
+            })
+            .clicked()
+        {
+            ui.close_menu();
+            self.copy_yaml(ui.ctx());
+        }
+    }
+}
+
+impl GuiOrderApp {
+    /// Import the analysis options from the content of a configuration YAML file.
+    /// Returns `true` if the options were successfully imported.
+    fn import_yaml_str(&mut self, yaml: &str) -> bool {
+        match serde_yaml::from_str::<gorder::input::Analysis>(yaml) {
+            Ok(analysis) => self.import_analysis(analysis),
+            Err(e) => {
+                self.open_error_window(Box::from(e));
+                false
+            }
+        }
+    }
+
+    /// Button opening the window for importing a pasted configuration.
+    pub(super) fn paste_yaml_button(&mut self, ui: &mut Ui) {
+        if ui
+            .button("📋 Paste YAML")
+            .on_hover_ui(|ui| {
+                ui.label("Load the options from a pasted YAML configuration.");
+            })
+            .clicked()
+        {
+            self.pasted_yaml.get_or_insert_with(String::new);
+        }
+    }
+
+    /// Render the window for importing a pasted configuration, if open.
+    pub(super) fn render_paste_yaml_window(&mut self, ctx: &egui::Context) {
+        let Some(input) = &mut self.pasted_yaml else {
+            return;
+        };
+
+        let mut open = true;
+        let mut import = false;
+        let mut cancel = false;
+        egui::Window::new("Paste YAML")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(400.0)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new("Paste the content of a configuration YAML file.")
+                        .font(egui::FontId::monospace(12.0))
+                        .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                );
+
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        let response = ui.add(
+                            egui::TextEdit::multiline(input)
+                                .font(egui::FontId::monospace(12.0))
+                                .desired_rows(4)
+                                .desired_width(f32::INFINITY),
+                        );
+                        if input.is_empty() && !response.has_focus() {
+                            response.request_focus();
+                        }
+                    });
+
+                ui.horizontal(|ui| {
+                    import = ui
+                        .add_enabled(!input.trim().is_empty(), egui::Button::new("📥 Import"))
+                        .clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+
+        if import {
+            let yaml = input.clone();
+            self.pasted_yaml = None;
+            self.import_yaml_str(&yaml);
+        } else if cancel || !open {
+            self.pasted_yaml = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copied_yaml_matches_export() {
+        let output = tempfile::NamedTempFile::new().unwrap();
+
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+        app.export_to_yaml(output.path().to_path_buf(), YamlHeader::Comment);
+
+        let yaml = GuiOrderApp::config_yaml(&app.analysis).unwrap();
+        assert_eq!(yaml, std::fs::read_to_string(output.path()).unwrap());
+        assert!(yaml.starts_with("# Analysis options generated by 'guiorder v"));
+    }
+
+    #[test]
+    fn paste_copied_yaml() {
+        let mut original = GuiOrderApp::default();
+        original.import_yaml("tests/parameters.yaml");
+        let yaml = GuiOrderApp::config_yaml(&original.analysis).unwrap();
+
+        let mut app = GuiOrderApp::default();
+        assert!(app.import_yaml_str(&yaml));
+        assert_eq!(app.analysis.structure, "tests/pcpepg.tpr");
+        assert_eq!(app.analysis.other_params.n_threads, 4);
+        assert_eq!(GuiOrderApp::config_yaml(&app.analysis).unwrap(), yaml);
+        // pasted configurations are not watched
+        assert!(app.watched_config.is_none());
+    }
+}
@@ -0,0 +1,92 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Confirmation of closing the application while an analysis is running.
+
+use eframe::egui::{self, RichText};
+
+use crate::{trajectory, GuiOrderApp};
+
+/// State of closing the application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum CloseState {
+    /// No close has been requested.
+    #[default]
+    Idle,
+    /// The user is being asked whether to close the application.
+    Confirming,
+    /// The user confirmed closing the application.
+    Confirmed,
+}
+
+/// Check whether a request to close the application must be cancelled.
+fn cancel_close(running: bool, state: CloseState) -> bool {
+    running && state != CloseState::Confirmed
+}
+
+impl GuiOrderApp {
+    /// Ask for confirmation if the application is closed while an analysis is running.
+    pub(super) fn handle_close_request(&mut self, ctx: &egui::Context) {
+        let running = *self.running.lock().unwrap();
+
+        if ctx.input(|i| i.viewport().close_requested()) {
+            if cancel_close(running, self.close_state) {
+                ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+                self.close_state = CloseState::Confirming;
+            } else {
+                trajectory::cancel_scans(ctx);
+            }
+        }
+
+        // the analysis finished while the user was deciding
+        if !running && self.close_state == CloseState::Confirming {
+            self.close_state = CloseState::Idle;
+        }
+
+        if self.close_state != CloseState::Confirming {
+            return;
+        }
+
+        egui::Window::new("Quit guiorder?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new("Analysis is still running. Quit anyway?")
+                        .font(egui::FontId::monospace(12.0)),
+                );
+                ui.label(
+                    RichText::new(
+                        "The analysis will be aborted and its results will not be written.",
+                    )
+                    .font(egui::FontId::monospace(12.0))
+                    .color(egui::Color32::from_rgb(200, 150, 0)),
+                );
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Quit").clicked() {
+                        self.close_state = CloseState::Confirmed;
+                        ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
+                    }
+                    if ui.button("Keep running").clicked() {
+                        self.close_state = CloseState::Idle;
+                    }
+                });
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_close_only_while_running() {
+        assert!(!cancel_close(false, CloseState::Idle));
+        assert!(cancel_close(true, CloseState::Idle));
+        assert!(cancel_close(true, CloseState::Confirming));
+        assert!(!cancel_close(true, CloseState::Confirmed));
+    }
+}
@@ -0,0 +1,371 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Export and import of the analysis as a command for the gorder command line tool.
+
+use std::path::{Path, PathBuf};
+
+use eframe::egui::{self, RichText, Ui};
+
+use crate::{
+    clipboard, dialogs,
+    yaml_header::{self, YamlHeader},
+    GuiAnalysis, GuiOrderApp,
+};
+
+/// Quote the string so that it is interpreted literally by a POSIX shell.
+/// Strings consisting only of safe characters are returned unchanged.
+pub(crate) fn shell_quote(string: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "_-./:=+@%,".contains(c);
+    if !string.is_empty() && string.chars().all(safe) {
+        return string.to_owned();
+    }
+
+    format!("'{}'", string.replace('\'', r"'\''"))
+}
+
+/// Split a command line into arguments, respecting shell quoting and escaping.
+fn split_command(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => arg.push(c),
+                        None => return Err(String::from("unterminated single quote")),
+                    }
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => arg.push(c),
+                            Some('\n') => (),
+                            Some(c) => {
+                                arg.push('\\');
+                                arg.push(c);
+                            }
+                            None => return Err(String::from("unterminated double quote")),
+                        },
+                        Some(c) => arg.push(c),
+                        None => return Err(String::from("unterminated double quote")),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                // line continuation
+                Some('\n') => (),
+                Some(c) => current.get_or_insert_with(String::new).push(c),
+                None => (),
+            },
+            c if c.is_whitespace() => args.extend(current.take()),
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    args.extend(current.take());
+    Ok(args)
+}
+
+/// Options parsed from a gorder command.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ParsedCommand {
+    /// Path to the configuration YAML file.
+    pub config: Option<String>,
+    pub silent: bool,
+    pub overwrite: bool,
+    /// Arguments that are not recognized.
+    pub unrecognized: Vec<String>,
+}
+
+impl ParsedCommand {
+    /// Apply the flags of the command to the analysis options.
+    /// Returns the names of the applied flags.
+    pub(crate) fn apply(&self, analysis: &mut GuiAnalysis) -> Vec<&'static str> {
+        analysis.other_params.silent |= self.silent;
+        analysis.other_params.overwrite |= self.overwrite;
+
+        [(self.silent, "silent"), (self.overwrite, "overwrite")]
+            .into_iter()
+            .filter_map(|(set, name)| set.then_some(name))
+            .collect()
+    }
+}
+
+/// Parse a gorder command line. The leading `gorder` is optional.
+pub(crate) fn parse_command(line: &str) -> Result<ParsedCommand, String> {
+    let mut args = split_command(line)?.into_iter().peekable();
+    if args
+        .peek()
+        .is_some_and(|program| program == "gorder" || program.ends_with("/gorder"))
+    {
+        args.next();
+    }
+
+    let mut parsed = ParsedCommand::default();
+    for arg in args {
+        match arg.as_str() {
+            "-s" | "--silent" => parsed.silent = true,
+            "-o" | "--overwrite" => parsed.overwrite = true,
+            _ if arg.starts_with('-') || parsed.config.is_some() => parsed.unrecognized.push(arg),
+            _ => parsed.config = Some(arg),
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Construct the gorder commands running the analyses defined in the provided configuration files.
+/// The number of threads and all other options are part of the configuration files.
+pub(crate) fn gorder_command(configs: &[PathBuf]) -> String {
+    configs
+        .iter()
+        .map(|path| format!("gorder {}", shell_quote(&path.to_string_lossy())))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl GuiOrderApp {
+    /// Import the analysis options from a gorder command.
+    pub(super) fn import_command(&mut self, line: &str) {
+        let parsed = match parse_command(line) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.open_error_window(Box::from(format!("Could not parse the command: {}.", e)));
+                return;
+            }
+        };
+
+        let Some(config) = &parsed.config else {
+            self.open_error_window(Box::from(
+                "The command does not specify any configuration YAML file.",
+            ));
+            return;
+        };
+
+        if !self.import_yaml(config) {
+            return;
+        }
+
+        let applied = parsed.apply(&mut self.analysis);
+        if let Some(watched) = &mut self.watched_config {
+            watched.set_command(parsed.clone());
+        }
+        if !applied.is_empty() {
+            self.open_info_window(&format!(
+                "The following options of the command have been applied: {}.",
+                applied.join(", ")
+            ));
+        }
+
+        if !parsed.unrecognized.is_empty() {
+            self.open_warning_window(&format!(
+                "The following arguments were not recognized and have been ignored: {}.",
+                parsed
+                    .unrecognized
+                    .iter()
+                    .map(|arg| format!("'{}'", arg))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+
+    /// Button opening the window for importing the analysis options from a gorder command.
+    pub(super) fn import_command_button(&mut self, ui: &mut Ui) {
+        if ui
+            .button("⌨ Import from command")
+            .on_hover_ui(|ui| {
+                ui.label("Load the options from a gorder command line.");
+            })
+            .clicked()
+        {
+            self.command_input.get_or_insert_with(String::new);
+        }
+    }
+
+    /// Render the window for importing the analysis options from a gorder command, if open.
+    pub(super) fn render_import_command_window(&mut self, ctx: &egui::Context) {
+        let Some(input) = &mut self.command_input else {
+            return;
+        };
+
+        let mut open = true;
+        let mut import = false;
+        let mut cancel = false;
+        egui::Window::new("Import from command")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(400.0)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new("Paste a gorder command, e.g. 'gorder analysis.yaml'.")
+                        .font(egui::FontId::monospace(12.0))
+                        .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                );
+                ui.add(
+                    egui::TextEdit::multiline(input)
+                        .font(egui::FontId::monospace(12.0))
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY),
+                );
+
+                ui.horizontal(|ui| {
+                    import = ui
+                        .add_enabled(!input.trim().is_empty(), egui::Button::new("📥 Import"))
+                        .clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+
+        if import {
+            let line = input.clone();
+            self.command_input = None;
+            self.import_command(&line);
+        } else if cancel || !open {
+            self.command_input = None;
+        }
+    }
+
+    /// Export the configuration YAML files and show the gorder command running the analysis.
+    fn export_command(&mut self, output: &Path, header: YamlHeader) {
+        match self.export_configs(output, header) {
+            Ok(files) => self.open_command_window(
+                "Configuration exported. Run the analysis using:",
+                gorder_command(&files),
+            ),
+            Err(e) => self.open_error_window(e),
+        }
+    }
+
+    /// Menu with additional options for exporting the analysis.
+    pub(super) fn export_menu(&mut self, ui: &mut Ui) {
+        let sane = self.analysis.check_sanity(ui.ctx());
+
+        let label = if clipboard::recently_copied(ui.ctx()) {
+            "✔ Copied!"
+        } else {
+            "⋯"
+        };
+
+        ui.menu_button(label, |ui| {
+            if ui
+                .add_enabled(sane, egui::Button::new("⌨ Export command"))
+                .on_hover_ui(|ui| {
+                    ui.label("Export the options into a YAML file and show the equivalent gorder command.");
+                })
+                .on_disabled_hover_ui(|ui| {
+                    ui.label("Cannot export the command: some options are invalid.");
+                })
+                .clicked()
+            {
+                ui.close_menu();
+                dialogs::open_save_file(ui.ctx(), dialogs::id("export_command"));
+            }
+
+            self.export_python_button(ui, sane);
+            self.copy_yaml_button(ui, sane);
+            self.export_minimal_button(ui, sane);
+            self.export_json_button(ui, sane);
+            ui.separator();
+            yaml_header::header_selector(ui);
+            ui.separator();
+            self.show_changes_button(ui);
+        })
+        .response
+        .on_hover_ui(|ui| {
+            ui.label("More export options.");
+        });
+
+        // the menu is closed while the dialogs are open
+        if let Some(path) = dialogs::take_file(ui.ctx(), dialogs::id("export_command")) {
+            self.export_command(&path, YamlHeader::get(ui.ctx()));
+        }
+        self.finish_python_export(ui.ctx());
+        self.finish_minimal_export(ui.ctx());
+        self.finish_json_export(ui.ctx());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_for_shell() {
+        assert_eq!(shell_quote("analysis.yaml"), "analysis.yaml");
+        assert_eq!(
+            shell_quote("/home/user/my config.yaml"),
+            "'/home/user/my config.yaml'"
+        );
+        assert_eq!(shell_quote("it's.yaml"), r"'it'\''s.yaml'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn split_quoted_command() {
+        assert_eq!(
+            split_command(r#"gorder 'my dir/a.yaml' "x \"y\"" a\ b"#).unwrap(),
+            vec!["gorder", "my dir/a.yaml", "x \"y\"", "a b"]
+        );
+        assert_eq!(
+            split_command("gorder \\\n  analysis.yaml").unwrap(),
+            vec!["gorder", "analysis.yaml"]
+        );
+        assert_eq!(split_command("''").unwrap(), vec![""]);
+        assert!(split_command("gorder 'analysis.yaml").is_err());
+    }
+
+    #[test]
+    fn parse_gorder_command() {
+        assert_eq!(
+            parse_command("gorder analysis.yaml --silent --threads 4").unwrap(),
+            ParsedCommand {
+                config: Some(String::from("analysis.yaml")),
+                silent: true,
+                overwrite: false,
+                unrecognized: vec![String::from("--threads"), String::from("4")],
+            }
+        );
+
+        let parsed = parse_command("/usr/bin/gorder -o 'it'\\''s.yaml'").unwrap();
+        assert_eq!(parsed.config.as_deref(), Some("it's.yaml"));
+        assert!(parsed.overwrite);
+        assert!(parsed.unrecognized.is_empty());
+
+        assert_eq!(parse_command("gorder").unwrap().config, None);
+    }
+
+    #[test]
+    fn command_round_trip() {
+        let configs = vec![PathBuf::from("/home/user/my analysis.yaml")];
+        let parsed = parse_command(&gorder_command(&configs)).unwrap();
+        assert_eq!(
+            parsed.config.as_deref(),
+            Some("/home/user/my analysis.yaml")
+        );
+    }
+
+    #[test]
+    fn command_for_regions() {
+        let configs = vec![
+            PathBuf::from("analysis.yaml"),
+            PathBuf::from("analysis_region2.yaml"),
+        ];
+
+        assert_eq!(
+            gorder_command(&configs),
+            "gorder analysis.yaml\ngorder analysis_region2.yaml"
+        );
+    }
+}
@@ -3,23 +3,39 @@
 
 //! Common structures and methods.
 
-use eframe::egui::{self, CollapsingResponse, CursorIcon, Response, RichText, Ui};
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use eframe::egui::{self, CollapsingResponse, CursorIcon, Id, Rect, Response, RichText, Ui};
 use gorder::input::Axis;
 
 use crate::{
+    accessibility,
     analysis_types::{AnalysisType, AnalysisTypeParams},
+    dialogs,
     error::ConversionError,
     estimate_error::EstimateErrorParams,
+    file_drop, focus,
     frame_selection::FrameSelectionParams,
-    geometry::{GeomSelection, GeomSelectionParams},
+    geometry::{GeomRegion, GeomSelection, GeomSelectionParams},
     membrane_normal::DynamicNormalParams,
     ordermaps::OrderMapsParams,
     other_options::OtherParams,
-    LeafletClassification, LeafletClassificationParams, OutputFiles,
+    paths,
+    pinned::{self, PinnableSection},
+    sections,
+    selection::is_blank,
+    window, LeafletClassification, LeafletClassificationParams, OutputFiles,
 };
 
+/// Number of seconds after which the existence of an input file is checked again.
+const FILE_CHECK_INTERVAL: f64 = 2.0;
+
 /// Main structure handling the drawing of the GUI and the collection of input.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub(crate) struct GuiAnalysis {
     pub structure: String,
     pub trajectory: Vec<String>,
@@ -33,15 +49,21 @@ pub(crate) struct GuiAnalysis {
     pub membrane_normal: MembraneNormal,
     pub dynamic_normal_params: DynamicNormalParams,
     pub from_file_normals: String,
+    /// Inline membrane normals from an imported configuration.
+    /// Kept unchanged so that they can be exported again.
+    pub from_map_normals: Option<gorder::input::MembraneNormal>,
     pub ordermaps_params: OrderMapsParams,
     pub estimate_error_params: EstimateErrorParams,
     pub frame_selection_params: FrameSelectionParams,
     pub other_params: OtherParams,
     pub geom_selection: GeomSelection,
     pub geom_selection_params: GeomSelectionParams,
+    /// Regions analyzed in addition to the primary region, each in a separate analysis.
+    pub additional_regions: Vec<GeomRegion>,
 }
 
 /// Direction of the membrane nornal.
+/// Static normals are limited to the box axes, as gorder does not support arbitrary static vectors.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub(crate) enum MembraneNormal {
     X,
@@ -50,6 +72,7 @@ pub(crate) enum MembraneNormal {
     Z,
     Dynamic,
     FromFile,
+    FromMap,
 }
 
 impl From<Axis> for MembraneNormal {
@@ -83,23 +106,26 @@ impl TryFrom<gorder::input::MembraneNormal> for MembraneNormal {
             gorder::input::MembraneNormal::Static(axis) => Ok(axis.into()),
             gorder::input::MembraneNormal::Dynamic(_) => Ok(Self::Dynamic),
             gorder::input::MembraneNormal::FromFile(_) => Ok(Self::FromFile),
-            gorder::input::MembraneNormal::FromMap(_) => Err(ConversionError::FromMapNormals),
+            gorder::input::MembraneNormal::FromMap(_) => Ok(Self::FromMap),
         }
     }
 }
 
 impl GuiAnalysis {
     /// Specify input file either using a text input or by interactive selection.
+    /// Returns the area occupied by the row.
     pub(crate) fn specify_input_file(
         target: &mut String,
         ui: &mut Ui,
         label: &str,
         hint: &str,
         required: bool,
-    ) {
+    ) -> Rect {
+        let dialog = dialogs::field_id(ui, label);
         ui.horizontal(|ui| {
             Self::label_with_hint(ui, label, hint);
-            Self::text_field(target, ui, required);
+            Self::text_field(target, ui, required)
+                .on_hover_ui(|ui| Self::resolved_path_hint(ui, target));
 
             if ui
                 .button("📁")
@@ -108,21 +134,37 @@ impl GuiAnalysis {
                 })
                 .clicked()
             {
-                if let Some(path) = rfd::FileDialog::new().set_directory(".").pick_file() {
-                    *target = path.display().to_string();
-                }
+                dialogs::open_pick_file(ui.ctx(), dialog);
             }
-        });
+            if let Some(path) = dialogs::take_file(ui.ctx(), dialog) {
+                *target = path.display().to_string();
+            }
+
+            Self::missing_file_marker(ui, target);
+        })
+        .response
+        .rect
     }
 
     /// Specify multiple input files either using a text input or by interactive selection.
+    /// Files with one of the `extensions` can also be added from a folder.
+    /// Returns the area occupied by the row.
     pub(crate) fn specify_multiple_input_files(
         target: &mut Vec<String>,
         ui: &mut Ui,
         label: &str,
         hint: &str,
+        extensions: &[&str],
         required: bool,
-    ) {
+    ) -> Rect {
+        let dialog = dialogs::field_id(ui, label);
+        if let Some(paths) = dialogs::take_files(ui.ctx(), dialog) {
+            target.clear();
+            for file in paths {
+                target.push(file.display().to_string())
+            }
+        }
+
         if target.is_empty() {
             target.push(String::new());
         }
@@ -137,6 +179,7 @@ impl GuiAnalysis {
 
                 Self::text_field(&mut target[0], ui, required);
                 Self::add_file_button(target, ui, !target.iter().any(|x| x.is_empty()));
+                Self::duplicate_file_button(target, ui, label);
 
                 if ui
                     .button("📁")
@@ -145,14 +188,17 @@ impl GuiAnalysis {
                     })
                     .clicked()
                 {
-                    if let Some(paths) = rfd::FileDialog::new().set_directory(".").pick_files() {
-                        target.clear();
-                        for file in paths {
-                            target.push(file.display().to_string())
-                        }
-                    }
+                    dialogs::open_pick_files(ui.ctx(), dialog);
                 }
-            });
+                Self::add_folder_button(target, ui, dialog.with("folder"), label, extensions);
+
+                if let Some(file) = target.first() {
+                    Self::missing_file_marker(ui, file);
+                    Self::unexpected_extension_marker(ui, file, extensions);
+                }
+            })
+            .response
+            .rect
         } else {
             ui.horizontal(|ui| {
                 Self::collapsing_with_warning(ui, label, true, !target.iter().any(|file| file.is_empty()), |ui| {
@@ -161,9 +207,13 @@ impl GuiAnalysis {
                     let mut move_down = None;
                     let last_index = target.len() - 1;
                     let no_empty = !target.iter().any(|x| x.is_empty());
+                    let focus_last = Self::take_focus_duplicate(ui.ctx(), label);
                     for (i, item) in target.iter_mut().enumerate() {
                         ui.horizontal(|ui| {
-                            Self::text_field(item, ui, required);
+                            let response = Self::text_field(item, ui, required);
+                            if focus_last && i == last_index {
+                                response.request_focus();
+                            }
 
                             if ui.add_enabled(i != 0, egui::Button::new("🔼"))
                                 .on_hover_ui(|ui| {ui.label("Move up in the list.");})
@@ -190,6 +240,9 @@ impl GuiAnalysis {
                             {
                                 index_to_remove = Some(i);
                             }
+
+                            Self::missing_file_marker(ui, item);
+                            Self::unexpected_extension_marker(ui, item, extensions);
                         });
                     }
 
@@ -209,6 +262,29 @@ impl GuiAnalysis {
                     ui.horizontal(|ui| {
                         ui.add_space(140.0);
                         Self::add_file_button(target, ui, no_empty);
+                        Self::duplicate_file_button(target, ui, label);
+
+                        if ui
+                            .button("Sort")
+                            .on_hover_ui(|ui| {
+                                ui.label("Sort the files in natural order (part2 before part10).");
+                            })
+                            .clicked()
+                        {
+                            Self::sort_files(target);
+                        }
+
+                        if ui
+                            .button("Remove duplicates")
+                            .on_hover_ui(|ui| {
+                                ui.label("Remove files that are selected multiple times.");
+                            })
+                            .clicked()
+                        {
+                            Self::remove_duplicate_files(ui.ctx(), target);
+                        }
+
+                        Self::add_folder_button(target, ui, dialog.with("folder"), label, extensions);
                     });
                 });
 
@@ -219,17 +295,103 @@ impl GuiAnalysis {
                     })
                     .clicked()
                 {
-                    if let Some(paths) = rfd::FileDialog::new().set_directory(".").pick_files() {
-                        target.clear();
-                        for file in paths {
-                            target.push(file.display().to_string())
-                        }
-                    }
+                    dialogs::open_pick_files(ui.ctx(), dialog);
                 }
-                });
+                })
+                .response
+                .rect
+        }
+    }
+
+    /// Append files to the list of selected files, replacing any empty entries.
+    pub(crate) fn append_files(target: &mut Vec<String>, files: impl IntoIterator<Item = String>) {
+        target.retain(|file| !file.is_empty());
+        target.extend(files);
+
+        if target.is_empty() {
+            target.push(String::new());
         }
     }
 
+    /// Sort the files in natural order. Empty entries are moved to the end of the list.
+    pub(crate) fn sort_files(target: &mut [String]) {
+        target.sort_by(|a, b| match (a.is_empty(), b.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => paths::natural_cmp(a, b),
+        });
+    }
+
+    /// Get the path used for comparing files. Paths to existing files are canonicalized,
+    /// so that `./md.xtc` and `md.xtc` are the same file.
+    /// The path is cached for each file and only resolved again after `FILE_CHECK_INTERVAL` seconds.
+    fn file_key(ctx: &egui::Context, file: &str) -> PathBuf {
+        let id = Id::new(("guiorder_file_key", file));
+        let now = ctx.input(|i| i.time);
+
+        if let Some((key, checked_at)) = ctx.data_mut(|d| d.get_temp::<(PathBuf, f64)>(id)) {
+            if now - checked_at < FILE_CHECK_INTERVAL {
+                return key;
+            }
+        }
+
+        let expanded = paths::expand_path(file);
+        let key = std::fs::canonicalize(&expanded).unwrap_or_else(|_| PathBuf::from(expanded));
+        ctx.data_mut(|d| d.insert_temp(id, (key.clone(), now)));
+        key
+    }
+
+    /// Remove repeated files from the list, keeping the first occurrence of each file.
+    pub(crate) fn remove_duplicate_files(ctx: &egui::Context, target: &mut Vec<String>) {
+        let mut seen = HashSet::new();
+        target.retain(|file| seen.insert(Self::file_key(ctx, file)));
+    }
+
+    /// Get the files that are repeated in the list, each listed once. Empty entries are ignored.
+    pub(crate) fn duplicate_files<'a>(ctx: &egui::Context, files: &'a [String]) -> Vec<&'a str> {
+        let mut seen = HashSet::new();
+        let mut duplicates = Vec::new();
+        for file in files.iter().filter(|file| !file.is_empty()) {
+            if !seen.insert(Self::file_key(ctx, file)) && !duplicates.contains(&file.as_str()) {
+                duplicates.push(file.as_str());
+            }
+        }
+        duplicates
+    }
+
+    /// Show a red marker if the path is not empty but the file does not exist.
+    /// For glob patterns, the marker is shown if the pattern matches no file.
+    fn missing_file_marker(ui: &mut Ui, path: &str) {
+        if !path.is_empty() && !Self::file_exists(ui.ctx(), path) {
+            accessibility::invalid_marker(ui, "not found").on_hover_ui(|ui| {
+                ui.label("File not found.");
+            });
+        }
+    }
+
+    /// Check whether the file exists.
+    /// The result is cached for each path and only rechecked after `FILE_CHECK_INTERVAL` seconds.
+    fn file_exists(ctx: &egui::Context, path: &str) -> bool {
+        let id = Id::new(("guiorder_file_exists", path));
+        let now = ctx.input(|i| i.time);
+
+        if let Some((exists, checked_at)) = ctx.data_mut(|d| d.get_temp::<(bool, f64)>(id)) {
+            if now - checked_at < FILE_CHECK_INTERVAL {
+                return exists;
+            }
+        }
+
+        let expanded = paths::expand_path(path);
+        let exists = if paths::is_glob_pattern(&expanded) {
+            paths::expand_glob(&expanded).is_ok()
+        } else {
+            Path::new(&expanded).exists()
+        };
+        ctx.data_mut(|d| d.insert_temp(id, (exists, now)));
+        exists
+    }
+
     /// Create a button that adds additional file to the list of selected files.
     fn add_file_button(target: &mut Vec<String>, ui: &mut Ui, not_empty: bool) {
         if ui
@@ -246,6 +408,145 @@ impl GuiAnalysis {
         }
     }
 
+    /// Append a copy of the last file to the list of selected files.
+    /// Nothing is appended if any of the selected files is missing, same as for the '+' button.
+    /// Returns `true` if the file was duplicated.
+    pub(crate) fn duplicate_last_file(target: &mut Vec<String>) -> bool {
+        if target.iter().any(|x| x.is_empty()) {
+            return false;
+        }
+
+        match target.last().cloned() {
+            Some(last) => {
+                target.push(last);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn focus_duplicate_id(label: &str) -> Id {
+        Id::new(("guiorder_focus_duplicate", label.trim()))
+    }
+
+    /// Check whether the duplicated file should be focused. The request is consumed.
+    fn take_focus_duplicate(ctx: &egui::Context, label: &str) -> bool {
+        ctx.data_mut(|d| d.remove_temp::<bool>(Self::focus_duplicate_id(label)))
+            .unwrap_or(false)
+    }
+
+    /// Create a button that duplicates the last selected file so that it can be edited immediately.
+    fn duplicate_file_button(target: &mut Vec<String>, ui: &mut Ui, label: &str) {
+        let enabled = !target.is_empty() && !target.iter().any(|x| x.is_empty());
+        if ui
+            .add_enabled(enabled, egui::Button::new("⧉"))
+            .on_hover_ui(|ui| {
+                ui.label("Duplicate the last file, e.g., to only change the number of the part.");
+            })
+            .on_disabled_hover_ui(|ui| {
+                ui.label("Cannot duplicate the last file because a filename is missing.");
+            })
+            .clicked()
+            && Self::duplicate_last_file(target)
+        {
+            ui.ctx().data_mut(|d| {
+                d.insert_persisted(sections::section_id(label), true);
+                d.insert_temp(Self::focus_duplicate_id(label), true);
+            });
+        }
+    }
+
+    /// Button for appending all files with one of the `extensions` from an interactively selected folder.
+    /// Right-clicking the button restricts the added files to a single extension.
+    fn add_folder_button(
+        target: &mut Vec<String>,
+        ui: &mut Ui,
+        dialog: Id,
+        label: &str,
+        extensions: &[&str],
+    ) {
+        let filter_id = Id::new(("guiorder_folder_filter", label.trim()));
+        let mut filter = ui
+            .ctx()
+            .data_mut(|d| d.get_persisted::<String>(filter_id))
+            .unwrap_or_default();
+
+        let response = ui.button("📂 Add folder").on_hover_ui(|ui| {
+            let allowed = if filter.is_empty() {
+                extensions
+                    .iter()
+                    .map(|ext| format!(".{}", ext))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            } else {
+                format!(".{}", filter)
+            };
+            ui.label(format!(
+                "Add all files with the extension {} from a folder, sorted in natural order. Right-click to choose the extensions.",
+                allowed
+            ));
+        });
+        if response.clicked() {
+            dialogs::open_pick_folder(ui.ctx(), dialog);
+        }
+
+        response.context_menu(|ui| {
+            let previous = filter.clone();
+            ui.selectable_value(&mut filter, String::new(), "All extensions");
+            for ext in extensions {
+                ui.selectable_value(&mut filter, ext.to_string(), format!(".{} only", ext));
+            }
+
+            if filter != previous {
+                ui.ctx()
+                    .data_mut(|d| d.insert_persisted(filter_id, filter.clone()));
+                ui.close_menu();
+            }
+        });
+
+        let Some(folder) = dialogs::take_file(ui.ctx(), dialog) else {
+            return;
+        };
+
+        let allowed = if filter.is_empty() {
+            extensions.to_vec()
+        } else {
+            vec![filter.as_str()]
+        };
+        match Self::files_in_folder(&folder, &allowed) {
+            Ok(files) if files.is_empty() => window::report_warning(
+                ui.ctx(),
+                format!(
+                    "No files with the extension(s) {} found in '{}'.",
+                    allowed.join(", "),
+                    folder.display()
+                ),
+            ),
+            Ok(files) => Self::append_files(target, files),
+            Err(e) => window::report_error(
+                ui.ctx(),
+                format!("Could not read the folder '{}': {}", folder.display(), e),
+            ),
+        }
+    }
+
+    /// Get all files in the folder with one of the `extensions`, sorted in natural order.
+    /// Subfolders are not searched.
+    pub(crate) fn files_in_folder(
+        folder: &Path,
+        extensions: &[&str],
+    ) -> std::io::Result<Vec<String>> {
+        let mut files = std::fs::read_dir(folder)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && file_drop::has_extension(path, extensions))
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>();
+
+        Self::sort_files(&mut files);
+        Ok(files)
+    }
+
     /// Specify an output file either by using a text or by interactive selection.
     pub(crate) fn specify_output_file(
         target: &mut String,
@@ -254,9 +555,11 @@ impl GuiAnalysis {
         hint: &str,
         required: bool,
     ) {
+        let dialog = dialogs::field_id(ui, label);
         ui.horizontal(|ui| {
             Self::label_with_hint(ui, label, hint);
-            Self::text_field(target, ui, required);
+            Self::text_field(target, ui, required)
+                .on_hover_ui(|ui| Self::resolved_path_hint(ui, target));
 
             if ui
                 .button("📁")
@@ -265,9 +568,10 @@ impl GuiAnalysis {
                 })
                 .clicked()
             {
-                if let Some(path) = rfd::FileDialog::new().set_directory(".").save_file() {
-                    *target = path.display().to_string();
-                }
+                dialogs::open_save_file(ui.ctx(), dialog);
+            }
+            if let Some(path) = dialogs::take_file(ui.ctx(), dialog) {
+                *target = path.display().to_string();
             }
         });
     }
@@ -295,19 +599,45 @@ impl GuiAnalysis {
         });
     }
 
-    /// Create a text field. 'Required' text fields will be colored red if empty.
-    fn text_field(target: &mut String, ui: &mut Ui, required: bool) {
-        if required && target.is_empty() {
-            ui.add(
-                egui::TextEdit::singleline(target)
-                    .background_color(egui::Color32::from_rgba_premultiplied(50, 0, 0, 50)),
-            );
+    /// Show the absolute path to which the path resolves. Only called on hover.
+    fn resolved_path_hint(ui: &mut Ui, path: &str) {
+        if path.is_empty() {
+            return;
+        }
+
+        let expanded = paths::expand_path(path);
+        match std::fs::canonicalize(&expanded) {
+            Ok(resolved) => ui.label(format!("Resolves to '{}'.", resolved.display())),
+            Err(_) => ui.label(format!(
+                "Resolves to '{}' (does not exist yet).",
+                paths::absolute(path).display()
+            )),
+        };
+    }
+
+    /// Create a text field reachable using Tab. 'Required' text fields will be colored red if empty.
+    pub(crate) fn text_field(target: &mut String, ui: &mut Ui, required: bool) -> Response {
+        if required && is_blank(target) {
+            let hint = if accessibility::text_cues(ui.ctx()) {
+                "(missing)"
+            } else {
+                ""
+            };
+
+            focus::chained(
+                ui.add(
+                    egui::TextEdit::singleline(target)
+                        .hint_text(hint)
+                        .background_color(egui::Color32::from_rgba_premultiplied(50, 0, 0, 50)),
+                ),
+            )
         } else {
-            ui.add(egui::TextEdit::singleline(target));
+            focus::chained(ui.add(egui::TextEdit::singleline(target)))
         }
     }
 
     /// Collapsing environment which heading gets colored red if there is an error inside the environment.
+    /// Whether the environment is open is remembered between launches; `open` is used the first time.
     pub(crate) fn collapsing_with_warning<R>(
         ui: &mut Ui,
         heading: &str,
@@ -315,17 +645,44 @@ impl GuiAnalysis {
         sanity_check: bool,
         contents: impl FnOnce(&mut Ui) -> R,
     ) -> CollapsingResponse<R> {
-        let heading = if sanity_check {
-            RichText::new(heading).font(egui::FontId::monospace(12.0))
+        let id = sections::section_id(heading);
+        let is_open = ui
+            .ctx()
+            .data_mut(|d| d.get_persisted::<bool>(id))
+            .unwrap_or(open);
+
+        let pinnable = PinnableSection::from_heading(heading);
+        let displayed = match pinnable {
+            Some(section) if pinned::is_pinned(ui.ctx(), section) => format!("📌 {}", heading),
+            _ => heading.to_owned(),
+        };
+
+        let text = if sanity_check {
+            RichText::new(&displayed).font(egui::FontId::monospace(12.0))
         } else {
-            RichText::new(heading)
-                .font(egui::FontId::monospace(12.0))
-                .color(egui::Color32::from_rgba_premultiplied(150, 0, 0, 100))
+            accessibility::invalid_heading(ui.ctx(), &displayed)
         };
 
-        egui::CollapsingHeader::new(heading)
-            .default_open(open)
-            .show(ui, contents)
+        let response = egui::CollapsingHeader::new(text)
+            .id_salt(id)
+            .open(Some(is_open))
+            .show(ui, contents);
+
+        if response.header_response.clicked() {
+            ui.ctx().data_mut(|d| d.insert_persisted(id, !is_open));
+        }
+
+        if let Some(section) = pinnable {
+            pinned::pin_menu(&response.header_response, section);
+        }
+
+        if sections::take_target(ui.ctx(), heading) {
+            response
+                .header_response
+                .scroll_to_me(Some(egui::Align::TOP));
+        }
+
+        response
     }
 
     /// Radio button that can be toggled off, deselecting all options.
@@ -379,6 +736,30 @@ mod tests {
 
     use super::*;
 
+    /// Render a section and return whether its contents were shown.
+    fn section_shown(ctx: &egui::Context, open: bool) -> bool {
+        let mut shown = false;
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                GuiAnalysis::collapsing_with_warning(ui, "Test section", open, true, |_| {
+                    shown = true;
+                });
+            });
+        });
+        shown
+    }
+
+    #[test]
+    fn remembered_section_state() {
+        assert!(!section_shown(&egui::Context::default(), false));
+        assert!(section_shown(&egui::Context::default(), true));
+
+        // the stored state takes precedence over the default
+        let ctx = egui::Context::default();
+        ctx.data_mut(|d| d.insert_persisted(Id::new(("guiorder_section", "Test section")), true));
+        assert!(section_shown(&ctx, false));
+    }
+
     #[test]
     fn convert_axis() {
         assert_eq!(
@@ -424,6 +805,168 @@ mod tests {
         );
     }
 
+    #[test]
+    fn append_files_replaces_empty() {
+        let mut files = vec![String::new()];
+        GuiAnalysis::append_files(
+            &mut files,
+            vec![String::from("md1.xtc"), String::from("md2.xtc")],
+        );
+        assert_eq!(
+            files,
+            vec![String::from("md1.xtc"), String::from("md2.xtc")]
+        );
+
+        GuiAnalysis::append_files(&mut files, vec![String::from("md3.xtc")]);
+        assert_eq!(
+            files,
+            vec![
+                String::from("md1.xtc"),
+                String::from("md2.xtc"),
+                String::from("md3.xtc")
+            ]
+        );
+    }
+
+    #[test]
+    fn sort_files_natural() {
+        let mut files = vec![
+            String::from("md_part10.xtc"),
+            String::new(),
+            String::from("md_part2.xtc"),
+            String::from("md_part1.xtc"),
+        ];
+        GuiAnalysis::sort_files(&mut files);
+        assert_eq!(
+            files,
+            vec![
+                String::from("md_part1.xtc"),
+                String::from("md_part2.xtc"),
+                String::from("md_part10.xtc"),
+                String::new(),
+            ]
+        );
+    }
+
+    #[test]
+    fn files_in_folder_natural_order() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "md_part10.xtc",
+            "md_part2.xtc",
+            "md.TRR",
+            "system.tpr",
+            "notes.txt",
+        ] {
+            std::fs::File::create(dir.path().join(name)).unwrap();
+        }
+        std::fs::create_dir(dir.path().join("backup.xtc")).unwrap();
+
+        let path = |name: &str| dir.path().join(name).display().to_string();
+        assert_eq!(
+            GuiAnalysis::files_in_folder(dir.path(), &crate::file_drop::TRAJECTORY_EXTENSIONS)
+                .unwrap(),
+            vec![path("md.TRR"), path("md_part2.xtc"), path("md_part10.xtc")]
+        );
+        assert_eq!(
+            GuiAnalysis::files_in_folder(dir.path(), &["xtc"]).unwrap(),
+            vec![path("md_part2.xtc"), path("md_part10.xtc")]
+        );
+        assert!(GuiAnalysis::files_in_folder(&dir.path().join("missing"), &["xtc"]).is_err());
+    }
+
+    #[test]
+    fn duplicate_last_nonempty_file() {
+        let mut files = vec![String::from("md_part1.xtc"), String::from("md_part2.xtc")];
+        assert!(GuiAnalysis::duplicate_last_file(&mut files));
+        assert_eq!(
+            files,
+            vec![
+                String::from("md_part1.xtc"),
+                String::from("md_part2.xtc"),
+                String::from("md_part2.xtc"),
+            ]
+        );
+
+        let mut files = vec![String::from("md_part1.xtc"), String::new()];
+        assert!(!GuiAnalysis::duplicate_last_file(&mut files));
+        assert_eq!(files.len(), 2);
+
+        let mut files = Vec::new();
+        assert!(!GuiAnalysis::duplicate_last_file(&mut files));
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn list_duplicate_files() {
+        let files = vec![
+            String::from("leaflets1.ndx"),
+            String::from("leaflets2.ndx"),
+            String::from("leaflets1.ndx"),
+            String::new(),
+            String::new(),
+            String::from("leaflets1.ndx"),
+        ];
+        let ctx = egui::Context::default();
+        assert_eq!(
+            GuiAnalysis::duplicate_files(&ctx, &files),
+            vec!["leaflets1.ndx"]
+        );
+        assert!(GuiAnalysis::duplicate_files(&ctx, &files[..2]).is_empty());
+    }
+
+    #[test]
+    fn file_keys_are_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("leaflets.ndx");
+        std::fs::File::create(&path).unwrap();
+        let dotted = dir
+            .path()
+            .join(".")
+            .join("leaflets.ndx")
+            .display()
+            .to_string();
+
+        let ctx = egui::Context::default();
+        let key = GuiAnalysis::file_key(&ctx, &dotted);
+        assert_eq!(key, std::fs::canonicalize(&path).unwrap());
+
+        // the file is not resolved again within the interval
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(GuiAnalysis::file_key(&ctx, &dotted), key);
+
+        // each context keeps its own paths
+        assert_eq!(
+            GuiAnalysis::file_key(&egui::Context::default(), &dotted),
+            PathBuf::from(&dotted)
+        );
+    }
+
+    #[test]
+    fn remove_duplicate_files_keeps_first() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::File::create(dir.path().join("md1.xtc")).unwrap();
+        let md1 = dir.path().join("md1.xtc").display().to_string();
+        let md1_dot = dir.path().join(".").join("md1.xtc").display().to_string();
+
+        let mut files = vec![
+            String::from("missing2.xtc"),
+            md1.clone(),
+            String::from("missing1.xtc"),
+            md1_dot,
+            String::from("missing2.xtc"),
+        ];
+        GuiAnalysis::remove_duplicate_files(&egui::Context::default(), &mut files);
+        assert_eq!(
+            files,
+            vec![
+                String::from("missing2.xtc"),
+                md1,
+                String::from("missing1.xtc"),
+            ]
+        );
+    }
+
     #[test]
     fn convert_to_axis() {
         assert_eq!(Axis::from(MembraneNormal::X), Axis::X);
@@ -0,0 +1,238 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Watching of the imported configuration file for changes on disk.
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use eframe::egui::{self, RichText};
+
+use crate::{command::ParsedCommand, GuiOrderApp};
+
+/// Number of seconds after which the imported configuration file is checked again.
+const CONFIG_CHECK_INTERVAL: f64 = 2.0;
+
+/// Get the time of the last modification of the file.
+fn modification_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Configuration file imported into the application.
+#[derive(Debug, Clone)]
+pub(crate) struct WatchedConfig {
+    path: PathBuf,
+    /// Modification time of the file when it was last imported or when the change was dismissed.
+    modified: Option<SystemTime>,
+    /// Time (in seconds of the egui clock) of the last check.
+    checked_at: Option<f64>,
+    /// Has the file changed since it was imported?
+    changed: bool,
+    /// Command the file was imported with. Its flags are applied again when the file is reloaded.
+    command: Option<ParsedCommand>,
+}
+
+impl WatchedConfig {
+    pub(crate) fn new(path: &str) -> Self {
+        let path = PathBuf::from(path);
+        Self {
+            modified: modification_time(&path),
+            path,
+            checked_at: None,
+            changed: false,
+            command: None,
+        }
+    }
+
+    /// Remember the command the file was imported with.
+    pub(crate) fn set_command(&mut self, command: ParsedCommand) {
+        self.command = Some(command);
+    }
+
+    /// Check whether this is the watched file.
+    fn is_file(&self, path: &Path) -> bool {
+        self.path == path
+            || std::fs::canonicalize(&self.path)
+                .is_ok_and(|watched| std::fs::canonicalize(path).is_ok_and(|path| path == watched))
+    }
+
+    /// Check whether the file has been modified. Returns `true` if it has.
+    /// Only the modification time of the file is compared so changes made in the GUI are irrelevant.
+    /// Removed files are not reported.
+    fn poll(&mut self) -> bool {
+        if !self.changed {
+            let current = modification_time(&self.path);
+            self.changed = current.is_some() && current != self.modified;
+        }
+
+        self.changed
+    }
+
+    /// Forget about the change of the file. It will only be reported again after another modification.
+    fn dismiss(&mut self) {
+        self.modified = modification_time(&self.path);
+        self.changed = false;
+    }
+}
+
+impl GuiOrderApp {
+    /// Register that the application has written the file.
+    /// If it is the watched configuration file, this change is not reported.
+    pub(super) fn config_written(&mut self, path: &Path) {
+        if let Some(watched) = &mut self.watched_config {
+            if watched.is_file(path) {
+                watched.dismiss();
+            }
+        }
+    }
+
+    /// Check whether the imported configuration file has changed and if it has,
+    /// render a banner offering to reload it.
+    pub(super) fn render_config_banner(&mut self, ctx: &egui::Context) {
+        let Some(watched) = &mut self.watched_config else {
+            return;
+        };
+
+        let now = ctx.input(|i| i.time);
+        if watched
+            .checked_at
+            .is_none_or(|checked_at| now - checked_at >= CONFIG_CHECK_INTERVAL)
+        {
+            watched.poll();
+            watched.checked_at = Some(now);
+        }
+        ctx.request_repaint_after(Duration::from_secs_f64(CONFIG_CHECK_INTERVAL));
+
+        if !watched.changed {
+            return;
+        }
+
+        let mut reload = false;
+        egui::TopBottomPanel::top("config_banner").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "Config '{}' changed on disk. Reload?",
+                        watched.path.display()
+                    ))
+                    .font(egui::FontId::monospace(12.0))
+                    .color(egui::Color32::from_rgb(200, 150, 0)),
+                );
+
+                if ui
+                    .button("🔄 Reload")
+                    .on_hover_ui(|ui| {
+                        ui.label(
+                            "Import the configuration file again, replacing all current options.",
+                        );
+                    })
+                    .clicked()
+                {
+                    reload = true;
+                }
+
+                if ui
+                    .button("Dismiss")
+                    .on_hover_ui(|ui| {
+                        ui.label("Keep the current options.");
+                    })
+                    .clicked()
+                {
+                    watched.dismiss();
+                }
+            });
+        });
+
+        if reload {
+            self.reload_config();
+        }
+    }
+
+    /// Import the watched configuration file again.
+    /// Flags of the command the file was imported with are applied again.
+    fn reload_config(&mut self) {
+        let Some(watched) = &mut self.watched_config else {
+            return;
+        };
+
+        watched.dismiss();
+        let path = watched.path.display().to_string();
+        let command = watched.command.clone();
+        if !self.import_yaml(&path) {
+            return;
+        }
+
+        if let Some(command) = command {
+            command.apply(&mut self.analysis);
+            if let Some(watched) = &mut self.watched_config {
+                watched.set_command(command);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_modification() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let path = file.path().display().to_string();
+        let mut watched = WatchedConfig::new(&path);
+        assert!(!watched.poll());
+
+        let later = SystemTime::now() + Duration::from_secs(60);
+        file.as_file().set_modified(later).unwrap();
+        assert!(watched.poll());
+
+        watched.dismiss();
+        assert!(!watched.poll());
+    }
+
+    #[test]
+    fn ignore_own_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("analysis.yaml");
+        std::fs::copy("tests/parameters.yaml", &path).unwrap();
+
+        let mut app = GuiOrderApp::default();
+        assert!(app.import_yaml(&path.display().to_string()));
+        app.watched_config.as_mut().unwrap().modified = Some(SystemTime::UNIX_EPOCH);
+
+        app.export_configs(&path, crate::yaml_header::YamlHeader::Comment)
+            .unwrap();
+        assert!(!app.watched_config.as_mut().unwrap().poll());
+    }
+
+    #[test]
+    fn reload_keeps_command_flags() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("analysis.yaml");
+        let content = std::fs::read_to_string("tests/parameters.yaml")
+            .unwrap()
+            .replace("overwrite: true\n", "");
+        std::fs::write(&path, content).unwrap();
+
+        let mut app = GuiOrderApp::default();
+        app.import_command(&format!("gorder {} --overwrite", path.display()));
+        assert!(app.analysis.other_params.overwrite);
+
+        app.reload_config();
+        assert!(app.analysis.other_params.overwrite);
+        assert!(app.watched_config.unwrap().command.is_some());
+    }
+
+    #[test]
+    fn ignore_removed_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("analysis.yaml");
+        std::fs::write(&path, "structure: system.tpr").unwrap();
+
+        let mut watched = WatchedConfig::new(&path.display().to_string());
+        std::fs::remove_file(&path).unwrap();
+        assert!(!watched.poll());
+    }
+}
@@ -3,9 +3,17 @@
 
 //! Conversion from and to gorder::Analysis.
 
+use std::{collections::HashMap, path::Path};
+
 use gorder::input::Analysis;
 
-use crate::{error::ConversionError, frame_selection::FrameSelectionParams, GuiAnalysis};
+use crate::{
+    error::ConversionError,
+    frame_selection::FrameSelectionParams,
+    geometry::region_suffix,
+    paths::{expand_glob, expand_path, insert_suffix_unexpanded, is_glob_pattern},
+    GuiAnalysis,
+};
 
 impl TryFrom<Analysis> for GuiAnalysis {
     type Error = ConversionError;
@@ -27,6 +35,10 @@ impl TryFrom<Analysis> for GuiAnalysis {
             } else {
                 String::new()
             },
+            from_map_normals: match value.membrane_normal() {
+                normal @ gorder::input::MembraneNormal::FromMap(_) => Some(normal.clone()),
+                _ => None,
+            },
             leaflet_classification_method: value.leaflets().clone().try_into()?,
             leaflet_classification_params: value.leaflets().clone().try_into()?,
             estimate_error_params: value.estimate_error().clone().into(),
@@ -37,7 +49,8 @@ impl TryFrom<Analysis> for GuiAnalysis {
             ),
             geom_selection: value.geometry().clone().into(),
             geom_selection_params: value.geometry().clone().into(),
-            ordermaps_params: value.map().clone().into(),
+            additional_regions: Vec::new(),
+            ordermaps_params: (&value).into(),
             other_params: (&value).into(),
         })
     }
@@ -104,7 +117,7 @@ impl TryFrom<&GuiAnalysis> for Analysis {
         }
 
         // ordermaps
-        if let Some(params) = (&value.ordermaps_params).try_into()? {
+        if let Some(params) = Option::<gorder::input::OrderMap>::try_from(value)? {
             analysis.ordermap(params);
         }
 
@@ -127,3 +140,382 @@ impl TryFrom<&GuiAnalysis> for Analysis {
             .map_err(|e| ConversionError::InvalidAnalysisParams(e.to_string()))
     }
 }
+
+impl GuiAnalysis {
+    /// Convert the GuiAnalysis to gorder analysis structure that can be directly run.
+    /// Unlike the plain conversion used for exporting, `~` and environment variables in the paths are expanded.
+    /// Glob patterns in the trajectory list are expanded into the matching files.
+    pub(crate) fn to_runnable(&self) -> Result<Analysis, ConversionError> {
+        let mut expanded = self.clone();
+        expanded.expand_paths()?;
+        Analysis::try_from(&expanded)
+    }
+
+    /// Convert the GuiAnalysis to gorder analysis structures that can be directly run, one for each geometry region.
+    pub(crate) fn to_runnables(&self) -> Result<Vec<Analysis>, ConversionError> {
+        self.region_analyses()
+            .iter()
+            .map(GuiAnalysis::to_runnable)
+            .collect()
+    }
+
+    /// Split the analysis into separate analyses, one for each geometry region.
+    /// The first analysis uses the primary region and writes into the specified output files.
+    /// Additional region number `n` (starting from 2) writes into output files with the suffix `_region{n}`,
+    /// e.g. `order.yaml` -> `order_region2.yaml` and `ordermaps` -> `ordermaps_region2`.
+    /// The output paths are kept unexpanded, so that they can be exported as written by the user.
+    pub(crate) fn region_analyses(&self) -> Vec<GuiAnalysis> {
+        let mut primary = self.clone();
+        primary.additional_regions.clear();
+
+        let mut analyses = vec![primary.clone()];
+        for (i, region) in self.additional_regions.iter().enumerate() {
+            let mut analysis = primary.clone();
+            analysis.geom_selection = region.selection;
+            analysis.geom_selection_params = region.params.clone();
+
+            let suffix = region_suffix(i + 2);
+            for path in analysis.output_paths_mut() {
+                *path = insert_suffix_unexpanded(path, &suffix);
+            }
+
+            analyses.push(analysis);
+        }
+
+        analyses
+    }
+
+    /// Get mutable access to all output paths.
+    fn output_paths_mut(&mut self) -> [&mut String; 6] {
+        [
+            &mut self.output.output_yaml,
+            &mut self.output.output_csv,
+            &mut self.output.output_tab,
+            &mut self.output.output_xvg,
+            self.ordermaps_params.output_directory_mut(),
+            self.estimate_error_params.output_convergence_mut(),
+        ]
+    }
+
+    /// Get a copy of the analysis options with all output files and directories placed directly in `directory`.
+    /// Only the names of the outputs are kept, their original directories are dropped.
+    /// Fails if two outputs have the same name.
+    pub(crate) fn in_run_directory(&self, directory: &str) -> Result<GuiAnalysis, ConversionError> {
+        let mut analysis = self.clone();
+        let mut names: HashMap<String, String> = HashMap::new();
+        for path in analysis.output_paths_mut() {
+            let trimmed = path.trim_end_matches(['/', '\\']);
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            let name = Path::new(trimmed)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| trimmed.to_owned());
+            if let Some(previous) = names.insert(name.clone(), path.clone()) {
+                return Err(ConversionError::CollidingOutputs(previous, path.clone()));
+            }
+            *path = format!("{}/{}", directory, name);
+        }
+
+        Ok(analysis)
+    }
+
+    /// Expand `~` and environment variables in all input and output paths
+    /// and glob patterns in the trajectory files.
+    fn expand_paths(&mut self) -> Result<(), ConversionError> {
+        for path in [
+            &mut self.structure,
+            &mut self.ndx,
+            &mut self.bonds,
+            &mut self.from_file_normals,
+        ]
+        .into_iter()
+        .chain(self.trajectory.iter_mut())
+        .chain(self.leaflet_classification_params.input_paths_mut())
+        {
+            *path = expand_path(path);
+        }
+
+        for path in self.output_paths_mut() {
+            *path = expand_path(path);
+        }
+
+        let mut trajectory = Vec::with_capacity(self.trajectory.len());
+        for file in &self.trajectory {
+            if is_glob_pattern(file) {
+                trajectory.extend(expand_glob(file)?);
+            } else {
+                trajectory.push(file.clone());
+            }
+        }
+        self.trajectory = trajectory;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runnable_expands_paths() {
+        std::env::set_var("GUIORDER_TEST_CONVERT_DIR", "/data/sims");
+
+        let analysis = GuiAnalysis {
+            structure: String::from("$GUIORDER_TEST_CONVERT_DIR/system.tpr"),
+            trajectory: vec![String::from("${GUIORDER_TEST_CONVERT_DIR}/md.xtc")],
+            output: crate::OutputFiles {
+                output_yaml: String::from("$GUIORDER_TEST_CONVERT_DIR/order.yaml"),
+                ..Default::default()
+            },
+            membrane_normal: crate::common::MembraneNormal::FromFile,
+            from_file_normals: String::from("$GUIORDER_TEST_CONVERT_DIR/normals.yaml"),
+            ..Default::default()
+        };
+
+        let runnable = analysis.to_runnable().unwrap();
+        match runnable.membrane_normal() {
+            gorder::input::MembraneNormal::FromFile(file) => {
+                assert_eq!(file, "/data/sims/normals.yaml")
+            }
+            _ => panic!("Invalid membrane normal."),
+        }
+        assert_eq!(runnable.structure(), "/data/sims/system.tpr");
+        assert_eq!(
+            runnable.trajectory(),
+            &vec![String::from("/data/sims/md.xtc")]
+        );
+        assert_eq!(
+            runnable.output_yaml().as_deref(),
+            Some("/data/sims/order.yaml")
+        );
+
+        // raw text is kept for export
+        let exported = Analysis::try_from(&analysis).unwrap();
+        assert_eq!(
+            exported.structure(),
+            "$GUIORDER_TEST_CONVERT_DIR/system.tpr"
+        );
+    }
+
+    #[test]
+    fn runnable_expands_globs() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["md_part2.xtc", "md_part1.xtc"] {
+            std::fs::File::create(dir.path().join(name)).unwrap();
+        }
+
+        let pattern = format!("{}/md_part*.xtc", dir.path().display());
+        let analysis = GuiAnalysis {
+            structure: String::from("system.tpr"),
+            trajectory: vec![pattern.clone(), String::from("extra.xtc")],
+            output: crate::OutputFiles {
+                output_yaml: String::from("order.yaml"),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let runnable = analysis.to_runnable().unwrap();
+        assert_eq!(
+            runnable.trajectory(),
+            &vec![
+                format!("{}/md_part1.xtc", dir.path().display()),
+                format!("{}/md_part2.xtc", dir.path().display()),
+                String::from("extra.xtc"),
+            ]
+        );
+
+        // pattern is kept for export
+        let exported = Analysis::try_from(&analysis).unwrap();
+        assert_eq!(
+            exported.trajectory(),
+            &vec![pattern, String::from("extra.xtc")]
+        );
+    }
+
+    #[test]
+    fn runnable_glob_no_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let analysis = GuiAnalysis {
+            structure: String::from("system.tpr"),
+            trajectory: vec![format!("{}/md_part*.xtc", dir.path().display())],
+            output: crate::OutputFiles {
+                output_yaml: String::from("order.yaml"),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            analysis.to_runnable(),
+            Err(ConversionError::NoGlobMatch(_))
+        ));
+    }
+
+    #[test]
+    fn from_map_normals_round_trip() {
+        let mut normals = std::collections::HashMap::new();
+        normals.insert(
+            String::from("POPC"),
+            vec![vec![
+                gorder::prelude::Vector3D::new(0.0, 0.0, 1.0),
+                gorder::prelude::Vector3D::new(0.0, 0.0, -1.0),
+            ]],
+        );
+        let normals = gorder::input::MembraneNormal::FromMap(normals);
+        let analysis = GuiAnalysis {
+            structure: String::from("system.tpr"),
+            trajectory: vec![String::from("md.xtc")],
+            output: crate::OutputFiles {
+                output_yaml: String::from("order.yaml"),
+                ..Default::default()
+            },
+            membrane_normal: crate::common::MembraneNormal::FromMap,
+            from_map_normals: Some(normals.clone()),
+            ..Default::default()
+        };
+
+        let exported = Analysis::try_from(&analysis).unwrap();
+        let imported = GuiAnalysis::try_from(exported).unwrap();
+        assert_eq!(
+            imported.membrane_normal,
+            crate::common::MembraneNormal::FromMap
+        );
+
+        let reexported = Analysis::try_from(&imported).unwrap();
+        assert_eq!(
+            serde_yaml::to_string(reexported.membrane_normal()).unwrap(),
+            serde_yaml::to_string(&normals).unwrap()
+        );
+    }
+
+    #[test]
+    fn region_analyses_suffix_outputs() {
+        let analysis = GuiAnalysis {
+            structure: String::from("system.tpr"),
+            trajectory: vec![String::from("md.xtc")],
+            output: crate::OutputFiles {
+                output_yaml: String::from("results/order.yaml"),
+                output_csv: String::from("order.csv"),
+                ..Default::default()
+            },
+            geom_selection: crate::geometry::GeomSelection::Cuboid,
+            additional_regions: vec![crate::geometry::GeomRegion::default()],
+            ..Default::default()
+        };
+
+        let analyses = analysis.region_analyses();
+        assert_eq!(analyses.len(), 2);
+
+        assert_eq!(analyses[0].output.output_yaml, "results/order.yaml");
+        assert_eq!(analyses[0].output.output_csv, "order.csv");
+        assert_eq!(
+            analyses[0].geom_selection,
+            crate::geometry::GeomSelection::Cuboid
+        );
+        assert!(analyses[0].additional_regions.is_empty());
+
+        assert_eq!(analyses[1].output.output_yaml, "results/order_region2.yaml");
+        assert_eq!(analyses[1].output.output_csv, "order_region2.csv");
+        assert!(analyses[1].output.output_tab.is_empty());
+        assert_eq!(
+            analyses[1].geom_selection,
+            crate::geometry::GeomSelection::None
+        );
+
+        let runnables = analysis.to_runnables().unwrap();
+        assert_eq!(
+            runnables[1].output_yaml().as_deref(),
+            Some("results/order_region2.yaml")
+        );
+    }
+
+    #[test]
+    fn region_suffix_before_expansion() {
+        std::env::set_var("GUIORDER_TEST_REGION_OUT", "/data/order");
+
+        let analysis = GuiAnalysis {
+            output: crate::OutputFiles {
+                output_yaml: String::from("$GUIORDER_TEST_REGION_OUT"),
+                output_csv: String::from("~/results/order.csv"),
+                ..Default::default()
+            },
+            additional_regions: vec![crate::geometry::GeomRegion::default()],
+            ..Default::default()
+        };
+
+        // the exported analyses keep the paths as written
+        let analyses = analysis.region_analyses();
+        assert_eq!(analyses[0].output.output_yaml, "$GUIORDER_TEST_REGION_OUT");
+        assert_eq!(
+            analyses[1].output.output_yaml,
+            "${GUIORDER_TEST_REGION_OUT}_region2"
+        );
+        assert_eq!(analyses[1].output.output_csv, "~/results/order_region2.csv");
+
+        // the paths are only expanded when the analyses are run
+        let mut expanded = analyses[1].clone();
+        expanded.expand_paths().unwrap();
+        assert_eq!(expanded.output.output_yaml, "/data/order_region2");
+    }
+
+    #[test]
+    fn outputs_in_run_directory() {
+        let mut analysis = GuiAnalysis {
+            output: crate::OutputFiles {
+                output_yaml: String::from("out/order.yaml"),
+                output_xvg: String::from("/data/order.xvg"),
+                timestamped_directory: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        *analysis.ordermaps_params.output_directory_mut() = String::from("maps/");
+
+        let mut moved = analysis
+            .in_run_directory("results/2025-01-01_00-00-00")
+            .unwrap();
+        assert_eq!(
+            moved.output.output_yaml,
+            "results/2025-01-01_00-00-00/order.yaml"
+        );
+        assert_eq!(
+            moved.output.output_xvg,
+            "results/2025-01-01_00-00-00/order.xvg"
+        );
+        assert!(moved.output.output_csv.is_empty());
+        assert_eq!(
+            moved.ordermaps_params.output_directory_mut(),
+            "results/2025-01-01_00-00-00/maps"
+        );
+
+        // the displayed paths are not changed
+        assert_eq!(analysis.output.output_yaml, "out/order.yaml");
+    }
+
+    #[test]
+    fn colliding_outputs_in_run_directory() {
+        let analysis = GuiAnalysis {
+            output: crate::OutputFiles {
+                output_yaml: String::from("first/order.yaml"),
+                output_csv: String::from("order.csv"),
+                output_tab: String::from("second/order.yaml"),
+                timestamped_directory: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        match analysis.in_run_directory("results/2025-01-01_00-00-00") {
+            Err(ConversionError::CollidingOutputs(first, second)) => {
+                assert_eq!(first, "first/order.yaml");
+                assert_eq!(second, "second/order.yaml");
+            }
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,279 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Interactive file dialogs remembering the last visited directories.
+//! Dialogs are run in the background so that the application stays responsive while they are open.
+//! The selected files are retrieved on a later frame using the id with which the dialog was opened.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake},
+};
+
+use eframe::egui::{self, Id, Ui};
+use rfd::{AsyncFileDialog, FileHandle};
+use serde::{Deserialize, Serialize};
+
+/// Directories in which the last input and output files were selected.
+/// Stored in the persisted egui memory so they survive application restarts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct LastDirs {
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+}
+
+/// Type of the file dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DialogKind {
+    Input,
+    Output,
+}
+
+impl LastDirs {
+    fn id() -> Id {
+        Id::new("guiorder_last_dirs")
+    }
+
+    /// Get the directory in which a dialog of the given kind should be opened.
+    /// Falls back to the directory of the other kind and then to the current working directory.
+    fn get(ctx: &egui::Context, kind: DialogKind) -> PathBuf {
+        let dirs = ctx
+            .data_mut(|d| d.get_persisted::<LastDirs>(Self::id()))
+            .unwrap_or_default();
+
+        let (primary, secondary) = match kind {
+            DialogKind::Input => (dirs.input, dirs.output),
+            DialogKind::Output => (dirs.output, dirs.input),
+        };
+
+        primary
+            .or(secondary)
+            .filter(|dir| dir.is_dir())
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// Remember the parent directory of the selected file.
+    fn update(ctx: &egui::Context, kind: DialogKind, selected: &Path) {
+        let Some(parent) = selected.parent() else {
+            return;
+        };
+
+        ctx.data_mut(|d| {
+            let dirs = d.get_persisted_mut_or_default::<LastDirs>(Self::id());
+            match kind {
+                DialogKind::Input => dirs.input = Some(parent.to_path_buf()),
+                DialogKind::Output => dirs.output = Some(parent.to_path_buf()),
+            }
+        });
+    }
+}
+
+/// State of a file dialog.
+#[derive(Debug, Clone)]
+enum DialogState {
+    /// The dialog is open.
+    Open,
+    /// The dialog has been closed. Contains the selected files, if any.
+    Closed(Option<Vec<PathBuf>>),
+}
+
+/// Dialog opened by a widget.
+#[derive(Debug, Clone)]
+struct PendingDialog {
+    kind: DialogKind,
+    state: Arc<Mutex<DialogState>>,
+}
+
+/// All dialogs whose results have not been retrieved yet.
+#[derive(Debug, Clone, Default)]
+struct PendingDialogs(HashMap<Id, PendingDialog>);
+
+impl PendingDialogs {
+    fn id() -> Id {
+        Id::new("guiorder_pending_dialogs")
+    }
+}
+
+/// Get the id identifying the dialog with the given name.
+pub(crate) fn id(name: &str) -> Id {
+    Id::new(("guiorder_dialog", name))
+}
+
+/// Get the id identifying the dialog of the field with the given label placed in `ui`.
+/// Fields with the same label in different parts of the window get different dialogs.
+pub(crate) fn field_id(ui: &Ui, label: &str) -> Id {
+    ui.id().with(("guiorder_dialog", label))
+}
+
+/// Run the future to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(std::thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut context = Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+/// Open the dialog in a background thread, unless a dialog with the same id is already open.
+/// The dialog itself is created on the calling thread as required by some platforms.
+fn open<F>(ctx: &egui::Context, id: Id, kind: DialogKind, dialog: impl FnOnce(AsyncFileDialog) -> F)
+where
+    F: Future<Output = Option<Vec<FileHandle>>> + Send + 'static,
+{
+    let already_open = ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<PendingDialogs>(PendingDialogs::id())
+            .0
+            .get(&id)
+            .is_some_and(|pending| matches!(*pending.state.lock().unwrap(), DialogState::Open))
+    });
+    if already_open {
+        return;
+    }
+
+    let state = Arc::new(Mutex::new(DialogState::Open));
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<PendingDialogs>(PendingDialogs::id())
+            .0
+            .insert(
+                id,
+                PendingDialog {
+                    kind,
+                    state: Arc::clone(&state),
+                },
+            )
+    });
+
+    let future = dialog(AsyncFileDialog::new().set_directory(LastDirs::get(ctx, kind)));
+    let ctx = ctx.clone();
+    std::thread::spawn(move || {
+        let selected = block_on(future).map(|files| {
+            files
+                .iter()
+                .map(|file| file.path().to_path_buf())
+                .collect::<Vec<_>>()
+        });
+
+        *state.lock().unwrap() = DialogState::Closed(selected);
+        ctx.request_repaint();
+    });
+}
+
+/// Open a dialog for selecting a single input file.
+pub(crate) fn open_pick_file(ctx: &egui::Context, id: Id) {
+    open(ctx, id, DialogKind::Input, |dialog| {
+        let file = dialog.pick_file();
+        async move { file.await.map(|file| vec![file]) }
+    });
+}
+
+/// Open a dialog for selecting multiple input files.
+pub(crate) fn open_pick_files(ctx: &egui::Context, id: Id) {
+    open(ctx, id, DialogKind::Input, |dialog| dialog.pick_files());
+}
+
+/// Open a dialog for selecting a folder.
+pub(crate) fn open_pick_folder(ctx: &egui::Context, id: Id) {
+    open(ctx, id, DialogKind::Input, |dialog| {
+        let folder = dialog.pick_folder();
+        async move { folder.await.map(|folder| vec![folder]) }
+    });
+}
+
+/// Open a dialog for specifying an output file.
+pub(crate) fn open_save_file(ctx: &egui::Context, id: Id) {
+    open(ctx, id, DialogKind::Output, |dialog| {
+        let file = dialog.save_file();
+        async move { file.await.map(|file| vec![file]) }
+    });
+}
+
+/// Get the files selected in the dialog with the given id, once the dialog is closed.
+/// Returns `None` if the dialog is still open, was cancelled, or was never opened.
+pub(crate) fn take_files(ctx: &egui::Context, id: Id) -> Option<Vec<PathBuf>> {
+    let pending = ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<PendingDialogs>(PendingDialogs::id())
+            .0
+            .get(&id)
+            .cloned()
+    })?;
+
+    let DialogState::Closed(selected) = pending.state.lock().unwrap().clone() else {
+        return None;
+    };
+
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<PendingDialogs>(PendingDialogs::id())
+            .0
+            .remove(&id)
+    });
+
+    let selected = selected.filter(|files| !files.is_empty())?;
+    LastDirs::update(ctx, pending.kind, &selected[0]);
+    Some(selected)
+}
+
+/// Get the file selected in the dialog with the given id, once the dialog is closed.
+pub(crate) fn take_file(ctx: &egui::Context, id: Id) -> Option<PathBuf> {
+    take_files(ctx, id).and_then(|files| files.into_iter().next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_future() {
+        assert_eq!(block_on(async { 21 * 2 }), 42);
+    }
+
+    #[test]
+    fn take_selected_files() {
+        let ctx = egui::Context::default();
+        let dialog = id("test");
+
+        let state = Arc::new(Mutex::new(DialogState::Open));
+        ctx.data_mut(|d| {
+            d.get_temp_mut_or_default::<PendingDialogs>(PendingDialogs::id())
+                .0
+                .insert(
+                    dialog,
+                    PendingDialog {
+                        kind: DialogKind::Input,
+                        state: Arc::clone(&state),
+                    },
+                )
+        });
+
+        // nothing is returned while the dialog is open
+        assert_eq!(take_files(&ctx, dialog), None);
+
+        *state.lock().unwrap() = DialogState::Closed(Some(vec![PathBuf::from("tests/pcpepg.xtc")]));
+        assert_eq!(
+            take_file(&ctx, dialog),
+            Some(PathBuf::from("tests/pcpepg.xtc"))
+        );
+
+        // the result can only be taken once
+        assert_eq!(take_files(&ctx, dialog), None);
+        assert_eq!(
+            LastDirs::get(&ctx, DialogKind::Input),
+            PathBuf::from("tests")
+        );
+    }
+}
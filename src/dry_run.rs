@@ -0,0 +1,184 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Validation of the analysis options without running the analysis.
+
+use eframe::egui::{self, Ui};
+use serde_yaml::Value;
+
+use crate::{selection, structure, GuiAnalysis, GuiOrderApp};
+
+/// Options of a gorder configuration that contain selection queries.
+const SELECTION_KEYS: [&str; 8] = [
+    "heavy_atoms",
+    "hydrogens",
+    "beads",
+    "saturated",
+    "unsaturated",
+    "ignore",
+    "membrane",
+    "heads",
+];
+
+/// Collect all selection queries in the value, together with the names of their options.
+fn collect_selections(value: &Value, key: Option<&str>, selections: &mut Vec<(String, String)>) {
+    match value {
+        Value::String(query) if key.is_some_and(|key| SELECTION_KEYS.contains(&key)) => {
+            selections.push((key.unwrap_or_default().to_owned(), query.clone()))
+        }
+        Value::Mapping(mapping) => {
+            for (name, value) in mapping {
+                collect_selections(value, name.as_str(), selections);
+            }
+        }
+        Value::Tagged(tagged) => collect_selections(&tagged.value, key, selections),
+        _ => (),
+    }
+}
+
+/// Get all selection queries used by the analysis, together with the names of their options.
+fn selections(
+    analysis: &gorder::input::Analysis,
+) -> Result<Vec<(String, String)>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut selections = Vec::new();
+    collect_selections(&serde_yaml::to_value(analysis)?, None, &mut selections);
+    Ok(selections)
+}
+
+/// Result of the validation.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Validation {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+    /// Number of selections checked against the loaded structure.
+    checked: usize,
+}
+
+impl GuiAnalysis {
+    /// Convert the analysis options into gorder analyses and check the selections,
+    /// against the loaded structure if available.
+    fn dry_run(&self, ctx: &egui::Context) -> Validation {
+        let mut validation = Validation::default();
+
+        let analyses = match self.to_runnables() {
+            Ok(analyses) => analyses,
+            Err(e) => {
+                validation.errors.push(e.to_string());
+                return validation;
+            }
+        };
+
+        let mut queries = Vec::new();
+        for analysis in &analyses {
+            match selections(analysis) {
+                Ok(found) => queries.extend(found),
+                Err(e) => validation.errors.push(e.to_string()),
+            }
+        }
+        queries.sort();
+        queries.dedup();
+
+        for (key, query) in queries {
+            if let Err(e) = selection::validate_selection(&query) {
+                validation
+                    .errors
+                    .push(format!("invalid selection '{}' ({}): {}.", query, key, e));
+                continue;
+            }
+
+            match structure::count_selection_now(ctx, &query) {
+                None => (),
+                Some(Err(e)) => validation
+                    .errors
+                    .push(format!("invalid selection '{}' ({}): {}", query, key, e)),
+                Some(Ok(count)) => {
+                    validation.checked += 1;
+                    if count.atoms == 0 {
+                        validation.warnings.push(format!(
+                            "selection '{}' ({}) does not match any atoms in the structure.",
+                            query, key
+                        ));
+                    }
+                }
+            }
+        }
+
+        validation
+    }
+}
+
+impl GuiOrderApp {
+    /// Button validating the analysis options without running the analysis.
+    pub(super) fn validate_button(&mut self, ui: &mut Ui) {
+        if !ui
+            .button("✔ Validate")
+            .on_hover_ui(|ui| {
+                ui.label("Check the analysis options without running the analysis.");
+            })
+            .clicked()
+        {
+            return;
+        }
+
+        let validation = self.analysis.dry_run(ui.ctx());
+        if !validation.errors.is_empty() || !validation.warnings.is_empty() {
+            self.open_validation_window(validation.errors, validation.warnings);
+        } else if validation.checked > 0 {
+            self.open_success_window(&format!(
+                "Everything checks out. All {} selection(s) were checked against the structure.",
+                validation.checked
+            ));
+        } else {
+            self.open_success_window(
+                "Everything checks out. Selections could not be checked against the structure because it is not loaded.",
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dry_run_imported() {
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+
+        let validation = app.analysis.dry_run(&egui::Context::default());
+        assert!(validation.errors.is_empty());
+        assert_eq!(validation.checked, 0);
+    }
+
+    #[test]
+    fn dry_run_invalid() {
+        let validation = GuiAnalysis::default().dry_run(&egui::Context::default());
+        assert_eq!(validation.errors.len(), 1);
+    }
+
+    #[test]
+    fn collect_nested_selections() {
+        let value: Value = serde_yaml::from_str(
+            "analysis_type: !AAOrder\n  heavy_atoms: '@membrane and element name carbon'\n  hydrogens: '@membrane and element name hydrogen'\nleaflets:\n  method: global\n  membrane: '@membrane'\n  heads: name P\nstructure: system.tpr\n",
+        )
+        .unwrap();
+
+        let mut found = Vec::new();
+        collect_selections(&value, None, &mut found);
+        assert_eq!(
+            found,
+            vec![
+                (
+                    String::from("heavy_atoms"),
+                    String::from("@membrane and element name carbon")
+                ),
+                (
+                    String::from("hydrogens"),
+                    String::from("@membrane and element name hydrogen")
+                ),
+                (String::from("membrane"), String::from("@membrane")),
+                (String::from("heads"), String::from("name P")),
+            ]
+        );
+    }
+}
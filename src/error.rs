@@ -9,9 +9,9 @@ use thiserror::Error;
 /// Errors returned when attempting conversion between gorder's Analysis and GuiAnalysis structures.
 #[derive(Debug, Clone, Error)]
 pub enum ConversionError {
-    #[error("{} guiorder does not support inline specification of membrane normals", "error:".red().bold())]
+    #[error("{} inline membrane normals are not available (they can only be imported from a configuration file)", "error:".red().bold())]
     FromMapNormals,
-    #[error("{} guiorder does not support inline specification of leaflet assignment", "error:".red().bold())]
+    #[error("{} inline leaflet assignment is not available (it can only be imported from a configuration file)", "error:".red().bold())]
     FromMapLeaflets,
     #[error("{} could not convert ordermap parameters into gorder structure (details: {})", "error:".red().bold(), .0.yellow())]
     InvalidOrderMapParams(String),
@@ -21,6 +21,14 @@ pub enum ConversionError {
     InvalidEstimateError(String),
     #[error("{} could not convert membrane normal parameters into gorder structure (details: {})", "error:".red().bold(), .0.yellow())]
     InvalidMembraneNormal(String),
+    #[error("{} could not convert leaflet assignment parameters into gorder structure (details: {})", "error:".red().bold(), .0.yellow())]
+    InvalidLeafletParams(String),
     #[error("{} could not convert geometry parameters into gorder structure (details: {})", "error:".red().bold(), .0.yellow())]
     InvalidGeometryParams(String),
+    #[error("{} no trajectory files match the pattern '{}'", "error:".red().bold(), .0.yellow())]
+    NoGlobMatch(String),
+    #[error("{} invalid trajectory pattern '{}' (details: {})", "error:".red().bold(), .0.yellow(), .1.yellow())]
+    InvalidGlobPattern(String, String),
+    #[error("{} outputs '{}' and '{}' have the same name and would overwrite each other in the timestamped directory", "error:".red().bold(), .0.yellow(), .1.yellow())]
+    CollidingOutputs(String, String),
 }
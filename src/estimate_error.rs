@@ -3,11 +3,19 @@
 
 //! Parameters for error estimation.
 
-use eframe::egui::{DragValue, Ui};
+use eframe::egui::{self, Color32, DragValue, RichText, Ui};
 
-use crate::{error::ConversionError, GuiAnalysis};
+use crate::{
+    error::ConversionError,
+    focus,
+    trajectory::{self, ScanState},
+    units, GuiAnalysis,
+};
 
-#[derive(Debug, Clone)]
+/// Blocks with fewer frames than this are considered too short.
+const SHORT_BLOCK_FRAMES: usize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
 /// Parameters for the error estimation.
 pub(crate) struct EstimateErrorParams {
     estimate_error: bool,
@@ -44,6 +52,22 @@ impl From<Option<gorder::input::EstimateError>> for EstimateErrorParams {
     }
 }
 
+impl EstimateErrorParams {
+    /// Get the output file for the convergence data, if the error is estimated.
+    pub(crate) fn output_convergence(&self) -> Option<&str> {
+        if self.estimate_error && !self.output_convergence.is_empty() {
+            Some(&self.output_convergence)
+        } else {
+            None
+        }
+    }
+
+    /// Get mutable access to the output file for the convergence data.
+    pub(crate) fn output_convergence_mut(&mut self) -> &mut String {
+        &mut self.output_convergence
+    }
+}
+
 impl TryFrom<&EstimateErrorParams> for Option<gorder::input::EstimateError> {
     type Error = ConversionError;
     fn try_from(value: &EstimateErrorParams) -> Result<Self, Self::Error> {
@@ -64,6 +88,15 @@ impl TryFrom<&EstimateErrorParams> for Option<gorder::input::EstimateError> {
 }
 
 impl GuiAnalysis {
+    /// Describe the error estimation for the summary of the analysis.
+    pub(crate) fn estimate_error_summary(&self) -> String {
+        if self.estimate_error_params.estimate_error {
+            format!("{} blocks", self.estimate_error_params.n_blocks)
+        } else {
+            String::from("off")
+        }
+    }
+
     pub(super) fn specify_estimate_error(&mut self, ui: &mut Ui) {
         Self::collapsing_with_warning(ui, "Error estimation", false, true, |ui| {
             ui.horizontal(|ui| {
@@ -86,11 +119,16 @@ impl GuiAnalysis {
                     "Number of blocks to use for block averaging.",
                 );
 
-                ui.add(
-                    DragValue::new(&mut self.estimate_error_params.n_blocks)
-                        .speed(0.1)
-                        .range(2..=usize::MAX),
+                focus::chained(
+                    ui.add(
+                        DragValue::new(&mut self.estimate_error_params.n_blocks)
+                            .speed(0.1)
+                            .range(2..=usize::MAX)
+                            .custom_parser(units::parse_integer),
+                    ),
                 );
+
+                self.block_size_label(ui);
             });
 
             Self::specify_output_file(
@@ -104,6 +142,32 @@ impl GuiAnalysis {
     }
 }
 
+impl GuiAnalysis {
+    /// Show the number of frames in each block, if the length of the trajectory is known.
+    fn block_size_label(&self, ui: &mut Ui) {
+        let Some(ScanState::Done(span)) =
+            trajectory::trajectory_span(ui.ctx(), &self.structure, &self.trajectory)
+        else {
+            return;
+        };
+
+        let params = &self.frame_selection_params;
+        let frames = span.analyzed_frames(params.begin, params.end, params.step)
+            / self.estimate_error_params.n_blocks.max(1);
+
+        let label = RichText::new(format!("≈ {} frames per block", frames))
+            .font(egui::FontId::monospace(12.0));
+        if frames < SHORT_BLOCK_FRAMES {
+            ui.label(label.color(Color32::from_rgb(200, 150, 0)))
+                .on_hover_ui(|ui| {
+                    ui.label("The blocks are very short. Consider using fewer blocks or analyzing more frames.");
+                });
+        } else {
+            ui.label(label.color(Color32::from_rgba_premultiplied(150, 150, 150, 100)));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,231 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Checking the extensions of the input and output files.
+
+use std::path::Path;
+
+use eframe::egui::{self, RichText, Ui};
+
+use crate::{accessibility, file_drop, paths, GuiAnalysis};
+
+/// Extensions expected for the output CSV file. The first one is suggested.
+pub(crate) const CSV_EXTENSIONS: [&str; 1] = ["csv"];
+/// Extensions expected for the output table file. The first one is suggested.
+pub(crate) const TAB_EXTENSIONS: [&str; 3] = ["dat", "tab", "txt"];
+
+/// Check whether the specified output file lacks all of the expected extensions.
+/// Empty paths and paths to directories are not checked.
+fn lacks_extension(path: &str, extensions: &[&str]) -> bool {
+    let path = path.trim();
+    !path.is_empty()
+        && !path.ends_with(['/', '\\'])
+        && !file_drop::has_extension(Path::new(path), extensions)
+}
+
+/// Check whether the input file does not have any of the expected extensions.
+/// Glob patterns are only checked if their extension is not itself a pattern, e.g. `md_*.xtc` but not `md.*`.
+fn unexpected_extension(path: &str, extensions: &[&str]) -> bool {
+    let path = path.trim();
+    if path.is_empty() {
+        return false;
+    }
+
+    let extension = Path::new(path).extension().and_then(|ext| ext.to_str());
+    if paths::is_glob_pattern(path) {
+        match extension {
+            None => return false,
+            Some(ext) if paths::is_glob_pattern(ext) => return false,
+            Some(_) => (),
+        }
+    }
+
+    !file_drop::has_extension(Path::new(path), extensions)
+}
+
+/// Append the extension to the path.
+fn with_extension(path: &str, extension: &str) -> String {
+    format!("{}.{}", path.trim().trim_end_matches('.'), extension)
+}
+
+impl GuiAnalysis {
+    /// Get warnings for all output files lacking the expected extension.
+    /// The XVG pattern is not checked here, its preview already notes a missing extension.
+    pub(crate) fn output_extension_warnings(&self) -> Vec<String> {
+        [
+            (
+                "Output YAML",
+                &self.output.output_yaml,
+                &file_drop::YAML_EXTENSIONS[..],
+            ),
+            ("Output CSV", &self.output.output_csv, &CSV_EXTENSIONS[..]),
+            ("Output table", &self.output.output_tab, &TAB_EXTENSIONS[..]),
+        ]
+        .into_iter()
+        .filter(|(_, path, extensions)| lacks_extension(path, extensions))
+        .map(|(name, path, extensions)| {
+            format!(
+                "{} file '{}' does not have the expected '.{}' extension.",
+                name, path, extensions[0]
+            )
+        })
+        .collect()
+    }
+
+    /// Get the trajectory files that do not have the extension of a supported trajectory format.
+    pub(crate) fn unexpected_trajectory_files(&self) -> Vec<&str> {
+        self.trajectory
+            .iter()
+            .filter(|file| unexpected_extension(file, &file_drop::TRAJECTORY_EXTENSIONS))
+            .map(|file| file.as_str())
+            .collect()
+    }
+
+    /// Show a red warning listing the trajectory files with unexpected extensions.
+    pub(crate) fn unexpected_trajectories_label(&self, ui: &mut Ui) {
+        let unexpected = self.unexpected_trajectory_files();
+        if unexpected.is_empty() {
+            return;
+        }
+
+        ui.label(accessibility::invalid_message(&format!(
+            "Not a trajectory file (expected {}): {}.",
+            file_drop::TRAJECTORY_EXTENSIONS
+                .iter()
+                .map(|ext| format!(".{}", ext))
+                .collect::<Vec<_>>()
+                .join(", "),
+            unexpected
+                .iter()
+                .map(|file| format!("'{}'", file))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+
+    /// Red marker shown next to an input file that does not have any of the expected extensions.
+    pub(crate) fn unexpected_extension_marker(ui: &mut Ui, path: &str, extensions: &[&str]) {
+        if !unexpected_extension(path, extensions) {
+            return;
+        }
+
+        accessibility::invalid_marker(ui, "unexpected extension").on_hover_ui(|ui| {
+            ui.label(format!(
+                "Expected a file with the extension {}.",
+                extensions
+                    .iter()
+                    .map(|ext| format!(".{}", ext))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        });
+    }
+
+    /// Orange note shown below an output file lacking the expected extension,
+    /// with a button for appending the first of the expected extensions.
+    pub(crate) fn missing_extension_note(ui: &mut Ui, target: &mut String, extensions: &[&str]) {
+        if !lacks_extension(target, extensions) {
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.add_space(104.0);
+            ui.label(
+                RichText::new(format!("missing .{} extension", extensions[0]))
+                    .font(egui::FontId::proportional(10.0))
+                    .color(egui::Color32::from_rgb(200, 150, 0)),
+            )
+            .on_hover_ui(|ui| {
+                ui.label(format!(
+                    "Expected one of the extensions: {}.",
+                    extensions
+                        .iter()
+                        .map(|ext| format!(".{}", ext))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            });
+
+            if ui.small_button(format!("add .{}", extensions[0])).clicked() {
+                *target = with_extension(target, extensions[0]);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_missing_extension() {
+        let yaml = file_drop::YAML_EXTENSIONS;
+        assert!(lacks_extension("order", &yaml));
+        assert!(lacks_extension("results/order.csv", &yaml));
+        assert!(!lacks_extension("order.yaml", &yaml));
+        assert!(!lacks_extension("order.YML", &yaml));
+        assert!(!lacks_extension("", &yaml));
+        assert!(!lacks_extension("results/", &CSV_EXTENSIONS));
+
+        assert_eq!(with_extension("order", "yaml"), "order.yaml");
+        assert_eq!(with_extension(" order. ", "csv"), "order.csv");
+    }
+
+    #[test]
+    fn detect_unexpected_extension() {
+        let extensions = file_drop::TRAJECTORY_EXTENSIONS;
+        assert!(!unexpected_extension("md.xtc", &extensions));
+        assert!(!unexpected_extension("md.TRR", &extensions));
+        assert!(!unexpected_extension("", &extensions));
+        assert!(!unexpected_extension("md_part*.xtc", &extensions));
+        assert!(!unexpected_extension("md_part*", &extensions));
+        assert!(!unexpected_extension("md.*", &extensions));
+        assert!(unexpected_extension("system.gro", &extensions));
+        assert!(unexpected_extension("md", &extensions));
+        assert!(unexpected_extension("md_part*.gro", &extensions));
+    }
+
+    #[test]
+    fn list_unexpected_trajectories() {
+        let analysis = GuiAnalysis {
+            trajectory: vec![
+                String::from("md.xtc"),
+                String::from("system.gro"),
+                String::from("md.trr"),
+                String::from("conf.pdb"),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            analysis.unexpected_trajectory_files(),
+            vec!["system.gro", "conf.pdb"]
+        );
+    }
+
+    #[test]
+    fn warn_about_output_extensions() {
+        let analysis = GuiAnalysis {
+            output: crate::OutputFiles {
+                output_yaml: String::from("order"),
+                output_csv: String::from("order.csv"),
+                output_tab: String::from("order.out"),
+                output_xvg: String::from("order"),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            analysis.output_extension_warnings(),
+            vec![
+                String::from(
+                    "Output YAML file 'order' does not have the expected '.yaml' extension."
+                ),
+                String::from(
+                    "Output table file 'order.out' does not have the expected '.dat' extension."
+                ),
+            ]
+        );
+    }
+}
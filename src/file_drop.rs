@@ -0,0 +1,207 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Handles files dropped onto the application window.
+
+use std::path::{Path, PathBuf};
+
+use eframe::egui::{self, Align2, Color32, Id, LayerId, Order, Pos2, Rect};
+
+use crate::{json, GuiAnalysis, GuiOrderApp};
+
+/// Extensions of YAML configuration files.
+pub(crate) const YAML_EXTENSIONS: [&str; 2] = ["yaml", "yml"];
+
+/// Check whether the file has one of the provided extensions (case-insensitive).
+pub(crate) fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.iter().any(|x| x.eq_ignore_ascii_case(ext)))
+}
+
+/// Extensions of structure files.
+const STRUCTURE_EXTENSIONS: [&str; 4] = ["tpr", "gro", "pdb", "pqr"];
+
+/// Extensions of trajectory files.
+pub(crate) const TRAJECTORY_EXTENSIONS: [&str; 2] = ["xtc", "trr"];
+
+/// Rows of the window onto which files can be dropped, as rendered in the previous frame.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DropTargets {
+    pub structure: Option<Rect>,
+    pub trajectory: Option<Rect>,
+}
+
+/// Where a dropped file should go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DropTarget {
+    Config,
+    Structure,
+    Trajectory,
+    Ignored,
+}
+
+impl DropTargets {
+    /// Get the row located under the specified position.
+    fn row_at(&self, pos: Pos2) -> Option<DropTarget> {
+        if self.structure.is_some_and(|rect| rect.contains(pos)) {
+            Some(DropTarget::Structure)
+        } else if self.trajectory.is_some_and(|rect| rect.contains(pos)) {
+            Some(DropTarget::Trajectory)
+        } else {
+            None
+        }
+    }
+}
+
+/// Decide where the dropped file should go based on its extension.
+fn classify(path: &Path) -> DropTarget {
+    if has_extension(path, &YAML_EXTENSIONS) || json::is_json(path) {
+        DropTarget::Config
+    } else if has_extension(path, &STRUCTURE_EXTENSIONS) {
+        DropTarget::Structure
+    } else if has_extension(path, &TRAJECTORY_EXTENSIONS) {
+        DropTarget::Trajectory
+    } else {
+        DropTarget::Ignored
+    }
+}
+
+impl GuiOrderApp {
+    /// Handle files dropped onto the window.
+    ///
+    /// YAML and JSON files are imported as configuration. Other files are routed to the row
+    /// they were dropped onto or, if dropped elsewhere, classified by their extension.
+    pub(super) fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+        if dropped.is_empty() {
+            return;
+        }
+
+        let hovered_row = ctx
+            .input(|i| i.pointer.latest_pos())
+            .and_then(|pos| self.drop_targets.row_at(pos));
+
+        let mut config = None;
+        let mut structure = None;
+        let mut trajectories = Vec::new();
+        let mut ignored = Vec::new();
+
+        for path in dropped.into_iter().filter_map(|file| file.path) {
+            let target = match (classify(&path), hovered_row) {
+                (DropTarget::Config, _) => DropTarget::Config,
+                (_, Some(row)) => row,
+                (target, None) => target,
+            };
+
+            match target {
+                DropTarget::Config if config.is_none() => config = Some(path),
+                DropTarget::Structure if structure.is_none() => structure = Some(path),
+                DropTarget::Trajectory => trajectories.push(path.display().to_string()),
+                _ => ignored.push(path),
+            }
+        }
+
+        if let Some(path) = config {
+            self.import_yaml(&path.display().to_string());
+        }
+
+        if let Some(path) = structure {
+            self.analysis.structure = path.display().to_string();
+        }
+
+        if !trajectories.is_empty() {
+            GuiAnalysis::append_files(&mut self.analysis.trajectory, trajectories);
+        }
+
+        if !ignored.is_empty() {
+            self.open_warning_window(&format!(
+                "The following dropped files were ignored:\n{}",
+                ignored
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            ));
+        }
+    }
+
+    /// Darken the window and display a hint while files are being dragged over it.
+    pub(super) fn render_drop_overlay(ctx: &egui::Context) {
+        if ctx.input(|i| i.raw.hovered_files.is_empty()) {
+            return;
+        }
+
+        let painter = ctx.layer_painter(LayerId::new(
+            Order::Foreground,
+            Id::new("file_drop_overlay"),
+        ));
+        let screen_rect = ctx.screen_rect();
+        painter.rect_filled(screen_rect, 0.0, Color32::from_black_alpha(192));
+        painter.text(
+            screen_rect.center(),
+            Align2::CENTER_BOTTOM,
+            "Drop YAML to import",
+            egui::FontId::monospace(15.0),
+            Color32::WHITE,
+        );
+        painter.text(
+            screen_rect.center(),
+            Align2::CENTER_TOP,
+            "or structure/trajectory files to add them",
+            egui::FontId::monospace(12.0),
+            Color32::WHITE,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yaml_extensions() {
+        assert!(has_extension(Path::new("config.yaml"), &YAML_EXTENSIONS));
+        assert!(has_extension(Path::new("dir/config.YML"), &YAML_EXTENSIONS));
+        assert!(!has_extension(
+            Path::new("config.yaml.bak"),
+            &YAML_EXTENSIONS
+        ));
+        assert!(!has_extension(Path::new("config"), &YAML_EXTENSIONS));
+    }
+
+    #[test]
+    fn classify_dropped_files() {
+        assert_eq!(classify(Path::new("config.yml")), DropTarget::Config);
+        assert_eq!(classify(Path::new("config.json")), DropTarget::Config);
+        assert_eq!(classify(Path::new("system.tpr")), DropTarget::Structure);
+        assert_eq!(classify(Path::new("system.gro")), DropTarget::Structure);
+        assert_eq!(classify(Path::new("md.xtc")), DropTarget::Trajectory);
+        assert_eq!(classify(Path::new("md.TRR")), DropTarget::Trajectory);
+        assert_eq!(classify(Path::new("index.ndx")), DropTarget::Ignored);
+    }
+
+    #[test]
+    fn row_under_pointer() {
+        let targets = DropTargets {
+            structure: Some(Rect::from_min_max(
+                Pos2::new(0.0, 0.0),
+                Pos2::new(100.0, 20.0),
+            )),
+            trajectory: Some(Rect::from_min_max(
+                Pos2::new(0.0, 20.0),
+                Pos2::new(100.0, 40.0),
+            )),
+        };
+
+        assert_eq!(
+            targets.row_at(Pos2::new(50.0, 10.0)),
+            Some(DropTarget::Structure)
+        );
+        assert_eq!(
+            targets.row_at(Pos2::new(50.0, 30.0)),
+            Some(DropTarget::Trajectory)
+        );
+        assert_eq!(targets.row_at(Pos2::new(50.0, 60.0)), None);
+    }
+}
@@ -0,0 +1,116 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Keyboard navigation between the input fields of the form.
+//!
+//! By default, egui moves the focus to the next widget of any kind when Tab is pressed,
+//! including all the buttons. Here, Tab and Shift+Tab move the focus only between
+//! the text fields and drag values, in the order in which they appear in the form.
+//! Fields in collapsed sections are not created and are therefore skipped.
+
+use eframe::egui::{self, Id, Key, Response};
+
+fn chain_id() -> Id {
+    Id::new("guiorder_focus_chain")
+}
+
+fn pending_id() -> Id {
+    Id::new("guiorder_focus_pending")
+}
+
+/// Tab press observed at the start of the frame while one of the fields was focused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TabPress {
+    /// Field that was focused when Tab was pressed.
+    from: Id,
+    /// Was Shift held?
+    backwards: bool,
+}
+
+/// Get the field following (or preceding) `current` in the chain. Wraps around at the ends.
+fn next_in_chain(chain: &[Id], current: Id, backwards: bool) -> Option<Id> {
+    let position = chain.iter().position(|&id| id == current)?;
+    let next = if backwards {
+        position.checked_sub(1).unwrap_or(chain.len() - 1)
+    } else {
+        (position + 1) % chain.len()
+    };
+    Some(chain[next])
+}
+
+/// Make the field reachable using Tab. Fields are ordered by their creation in the frame.
+pub(crate) fn chained(response: Response) -> Response {
+    response.ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<Vec<Id>>(chain_id())
+            .push(response.id)
+    });
+    response
+}
+
+/// Prepare the chain of fields for the new frame. Must be called before any field is created.
+/// Returns the Tab press that should move the focus to another field, if any.
+pub(crate) fn begin_frame(ctx: &egui::Context) -> Option<TabPress> {
+    let previous = ctx.data_mut(|d| d.remove_temp::<Vec<Id>>(chain_id()).unwrap_or_default());
+
+    // egui may assign the focus at the start of the frame following Shift+Tab, so it is requested again
+    if let Some(pending) = ctx.data_mut(|d| d.remove_temp::<Id>(pending_id())) {
+        ctx.memory_mut(|m| m.request_focus(pending));
+        return None;
+    }
+
+    let (tab, backwards) = ctx.input(|i| (i.key_pressed(Key::Tab), i.modifiers.shift));
+    let from = ctx.memory(|m| m.focused())?;
+    (tab && previous.contains(&from)).then_some(TabPress { from, backwards })
+}
+
+/// Move the focus to the field following the one focused when Tab was pressed.
+/// Must be called after all fields of the frame have been created.
+pub(crate) fn end_frame(ctx: &egui::Context, press: Option<TabPress>) {
+    let Some(press) = press else {
+        return;
+    };
+
+    let chain = ctx.data_mut(|d| d.get_temp::<Vec<Id>>(chain_id()).unwrap_or_default());
+    if let Some(next) = next_in_chain(&chain, press.from, press.backwards) {
+        ctx.memory_mut(|m| m.request_focus(next));
+        ctx.data_mut(|d| d.insert_temp(pending_id(), next));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_along_chain() {
+        let chain = [Id::new("a"), Id::new("b"), Id::new("c")];
+        assert_eq!(next_in_chain(&chain, chain[0], false), Some(chain[1]));
+        assert_eq!(next_in_chain(&chain, chain[2], false), Some(chain[0]));
+        assert_eq!(next_in_chain(&chain, chain[1], true), Some(chain[0]));
+        assert_eq!(next_in_chain(&chain, chain[0], true), Some(chain[2]));
+        assert_eq!(next_in_chain(&chain, Id::new("d"), false), None);
+        assert_eq!(next_in_chain(&[], chain[0], false), None);
+    }
+
+    #[test]
+    fn chain_in_creation_order() {
+        let ctx = egui::Context::default();
+        let mut first = String::new();
+        let mut second = String::new();
+        let mut ids = Vec::new();
+
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            assert_eq!(begin_frame(ctx), None);
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ids.push(chained(ui.text_edit_singleline(&mut first)).id);
+                let _ = ui.button("not chained");
+                ids.push(chained(ui.text_edit_singleline(&mut second)).id);
+            });
+        });
+
+        assert_eq!(
+            ctx.data_mut(|d| d.get_temp::<Vec<Id>>(chain_id())),
+            Some(ids)
+        );
+    }
+}
@@ -3,21 +3,50 @@
 
 //! Parameters for frame selection.
 
-use eframe::egui::{DragValue, Ui};
+use std::ops::RangeInclusive;
 
-use crate::GuiAnalysis;
+use eframe::egui::{self, DragValue, Response, RichText, Ui};
+
+use crate::{
+    accessibility, focus,
+    trajectory::{self, ScanState},
+    units::{self, TimeUnit},
+    GuiAnalysis,
+};
 
 /// Parameters for frame selection.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct FrameSelectionParams {
     pub begin: f32,
     pub end: f32,
     pub step: usize,
 }
 
+/// Times (in ps) at or above which the end of the frame selection is treated as the end of the trajectory.
+const INFINITE_END_THRESHOLD: f32 = 1e15;
+
 impl FrameSelectionParams {
+    /// Create new frame selection parameters, replacing nonsensical values.
+    /// Negative or undefined begin is replaced by 0, undefined end and end past
+    /// `INFINITE_END_THRESHOLD` mean the end of the trajectory, and step of 0 is replaced by 1.
     pub(crate) fn new(begin: f32, end: f32, step: usize) -> Self {
-        Self { begin, end, step }
+        let begin = if begin.is_finite() && begin > 0.0 {
+            begin
+        } else {
+            0.0
+        };
+
+        let end = if end.is_nan() || end >= INFINITE_END_THRESHOLD {
+            f32::INFINITY
+        } else {
+            end
+        };
+
+        Self {
+            begin,
+            end,
+            step: step.max(1),
+        }
     }
 }
 
@@ -33,6 +62,10 @@ impl Default for FrameSelectionParams {
 
 fn format_with_commas<T: std::fmt::Display>(num: T) -> String {
     let s = num.to_string();
+    if s == "inf" {
+        return String::from("∞");
+    }
+
     let parts: Vec<&str> = s.split('.').collect();
     let integer = parts[0];
     let decimal = if parts.len() > 1 { parts[1] } else { "" };
@@ -55,10 +88,51 @@ fn format_with_commas<T: std::fmt::Display>(num: T) -> String {
     }
 }
 
+/// Create a drag value for a time stored in picoseconds and displayed in the currently selected unit.
+/// `speed` and `range` are provided in picoseconds.
+fn time_drag_value(
+    ui: &mut Ui,
+    value: &mut f32,
+    speed: f64,
+    range: RangeInclusive<f32>,
+) -> Response {
+    let unit = TimeUnit::get(ui.ctx());
+    let range = unit.from_ps(*range.start())..=unit.from_ps(*range.end());
+
+    focus::chained(
+        ui.add(
+            DragValue::from_get_set(|new| {
+                if let Some(new) = new {
+                    *value = unit.to_ps(new);
+                }
+                unit.from_ps(*value)
+            })
+            .speed(speed / unit.ps_per_unit())
+            .range(range)
+            .suffix(unit.suffix())
+            .custom_formatter(|n, _| format_with_commas(n))
+            .custom_parser(move |text| units::parse_time(text, unit)),
+        ),
+    )
+}
+
 impl GuiAnalysis {
+    /// Describe the selected frames for the summary of the analysis.
+    pub(crate) fn frame_selection_summary(&self, unit: TimeUnit) -> String {
+        let params = &self.frame_selection_params;
+        format!(
+            "{} to {}{}, step {}",
+            format_with_commas(unit.from_ps(params.begin) as f32),
+            format_with_commas(unit.from_ps(params.end) as f32),
+            unit.suffix(),
+            params.step
+        )
+    }
+
     /// Specify the parameters for the frame selection.
     pub(super) fn specify_frame_selection(&mut self, ui: &mut Ui) {
-        Self::collapsing_with_warning(ui, "Frame selection", false, true, |ui| {
+        let sane = self.check_frame_selection_sanity();
+        Self::collapsing_with_warning(ui, "Frame selection", false, sane, |ui| {
             ui.horizontal(|ui| {
                 Self::label_with_hint(
                     ui,
@@ -66,13 +140,8 @@ impl GuiAnalysis {
                     "Start to read the trajectory from this time.",
                 );
 
-                ui.add(
-                    DragValue::new(&mut self.frame_selection_params.begin)
-                        .speed(200)
-                        .range(0.0..=self.frame_selection_params.end)
-                        .suffix(" ps")
-                        .custom_formatter(|n, _| format_with_commas(n)),
-                );
+                let end = self.frame_selection_params.end;
+                time_drag_value(ui, &mut self.frame_selection_params.begin, 200.0, 0.0..=end);
 
                 Self::label_with_hint(
                     ui,
@@ -80,12 +149,12 @@ impl GuiAnalysis {
                     "Finish reading the trajectory at this time.",
                 );
 
-                let response = ui.add(
-                    DragValue::new(&mut self.frame_selection_params.end)
-                        .speed(200)
-                        .range(self.frame_selection_params.begin..=f32::INFINITY)
-                        .suffix(" ps")
-                        .custom_formatter(|n, _| format_with_commas(n)),
+                let begin = self.frame_selection_params.begin;
+                let response = time_drag_value(
+                    ui,
+                    &mut self.frame_selection_params.end,
+                    200.0,
+                    begin..=f32::INFINITY,
                 );
 
                 // makes it possible to decrease from infinity
@@ -94,12 +163,168 @@ impl GuiAnalysis {
                 }
 
                 Self::label_with_hint(ui, "   Step: ", "Read every Nth frame.");
-                ui.add(
-                    DragValue::new(&mut self.frame_selection_params.step)
-                        .speed(0.1)
-                        .range(1..=usize::MAX),
+                focus::chained(
+                    ui.add(
+                        DragValue::new(&mut self.frame_selection_params.step)
+                            .speed(0.1)
+                            .range(1..=usize::MAX)
+                            .custom_parser(units::parse_integer),
+                    ),
                 );
+
+                if !sane {
+                    accessibility::invalid_marker(ui, "no frames").on_hover_ui(|ui| {
+                        ui.label(
+                            "Begin must be smaller than end, otherwise no frames are analyzed.",
+                        );
+                    });
+                }
+            });
+
+            self.trajectory_span_row(ui);
+
+            ui.horizontal(|ui| {
+                Self::label_with_hint(
+                    ui,
+                    "Time unit: ",
+                    "Unit in which the times are displayed. Times are always exported in ps.",
+                );
+                units::time_unit_selector(ui);
             });
         });
     }
+
+    /// Check that the frame selection can select at least one frame.
+    pub(super) fn check_frame_selection_sanity(&self) -> bool {
+        self.frame_selection_params.begin < self.frame_selection_params.end
+    }
+
+    /// Show the time span of the trajectory and a button for analyzing the entire trajectory.
+    fn trajectory_span_row(&mut self, ui: &mut Ui) {
+        let Some(state) = trajectory::trajectory_span(ui.ctx(), &self.structure, &self.trajectory)
+        else {
+            return;
+        };
+
+        let gray = egui::Color32::from_rgba_premultiplied(150, 150, 150, 100);
+        ui.horizontal(|ui| match state {
+            ScanState::Scanning(0) => {
+                ui.spinner();
+                ui.label(
+                    RichText::new("Scanning the trajectory…")
+                        .font(egui::FontId::monospace(12.0))
+                        .color(gray),
+                );
+            }
+            ScanState::Scanning(read) => {
+                ui.spinner();
+                ui.label(
+                    RichText::new(format!(
+                        "Scanning the trajectory… ({} frames read)",
+                        format_with_commas(read)
+                    ))
+                    .font(egui::FontId::monospace(12.0))
+                    .color(gray),
+                );
+            }
+            ScanState::Failed => {
+                ui.label(
+                    RichText::new("(could not read the trajectory)")
+                        .font(egui::FontId::monospace(12.0))
+                        .color(gray),
+                );
+            }
+            ScanState::Done(span) => {
+                let unit = TimeUnit::get(ui.ctx());
+                ui.label(
+                    RichText::new(format!(
+                        "(trajectory spans {}–{} {}, ~{} frames)",
+                        format_with_commas(unit.from_ps(span.start)),
+                        format_with_commas(unit.from_ps(span.end)),
+                        unit,
+                        format_with_commas(span.frames),
+                    ))
+                    .font(egui::FontId::monospace(12.0))
+                    .color(gray),
+                );
+
+                if self.frame_selection_params.step > span.frames {
+                    ui.label(
+                        RichText::new("step exceeds the number of frames!")
+                            .font(egui::FontId::proportional(10.0))
+                            .color(egui::Color32::from_rgb(200, 150, 0)),
+                    )
+                    .on_hover_ui(|ui| {
+                        ui.label("Only the first frame of the trajectory will be analyzed.");
+                    });
+                }
+
+                if ui
+                    .button("Use full range")
+                    .on_hover_ui(|ui| {
+                        ui.label("Analyze the trajectory from its first to its last frame.");
+                    })
+                    .clicked()
+                {
+                    self.frame_selection_params.begin = span.start;
+                    self.frame_selection_params.end = span.end;
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_nan() {
+        let params = FrameSelectionParams::new(f32::NAN, f32::NAN, 1);
+        assert_eq!(params.begin, 0.0);
+        assert!(params.end.is_infinite());
+    }
+
+    #[test]
+    fn sanitize_negative_and_sentinel() {
+        let params = FrameSelectionParams::new(-100.0, 5000.0, 0);
+        assert_eq!(params.begin, 0.0);
+        assert_eq!(params.end, 5000.0);
+        assert_eq!(params.step, 1);
+
+        let params = FrameSelectionParams::new(f32::INFINITY, f32::MAX, 2);
+        assert_eq!(params.begin, 0.0);
+        assert_eq!(params.end, f32::INFINITY);
+        assert_eq!(params.step, 2);
+        assert_eq!(format_with_commas(params.end), "∞");
+    }
+
+    #[test]
+    fn zero_frames_selected() {
+        let mut analysis = GuiAnalysis::default();
+        assert!(analysis.check_frame_selection_sanity());
+
+        analysis.frame_selection_params = FrameSelectionParams::new(1000.0, 1000.0, 1);
+        assert!(!analysis.check_frame_selection_sanity());
+        assert!(!analysis.check_sanity(&egui::Context::default()));
+
+        analysis.frame_selection_params.end = 2000.0;
+        assert!(analysis.check_frame_selection_sanity());
+    }
+
+    #[test]
+    fn imported_times_in_ns() {
+        let analysis = GuiAnalysis::try_from(
+            gorder::input::Analysis::from_file("tests/parameters.yaml").unwrap(),
+        )
+        .unwrap();
+
+        let params = &analysis.frame_selection_params;
+        let unit = TimeUnit::Nanosecond;
+        assert_eq!(unit.from_ps(params.begin), 450.5);
+        assert_eq!(unit.from_ps(params.end), 450.8);
+        assert_eq!(unit.to_ps(unit.from_ps(params.begin)), params.begin);
+        assert_eq!(unit.to_ps(unit.from_ps(params.end)), params.end);
+        assert_eq!(format_with_commas(unit.from_ps(params.begin)), "450.5");
+    }
 }
@@ -5,10 +5,18 @@
 
 use std::fmt::Display;
 
-use eframe::egui::{self, ComboBox, DragValue, Response, RichText, Ui};
+use eframe::egui::{self, ComboBox, Response, RichText, Ui};
 use gorder::{input::Axis, prelude::Vector3D};
 
-use crate::{error::ConversionError, GuiAnalysis};
+use crate::{
+    accessibility,
+    error::ConversionError,
+    json,
+    selection::is_blank,
+    structure,
+    units::{length_drag_value, LengthUnit},
+    GuiAnalysis,
+};
 
 /// Geometric selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -31,8 +39,21 @@ impl From<Option<gorder::input::Geometry>> for GeomSelection {
     }
 }
 
+/// Additional geometry region analyzed in a separate analysis with its own output files.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct GeomRegion {
+    pub selection: GeomSelection,
+    pub params: GeomSelectionParams,
+}
+
+/// Get the suffix added to the output files of the region with the given number.
+/// The primary region is number 1 and writes into the output files without a suffix.
+pub(crate) fn region_suffix(number: usize) -> String {
+    format!("_region{}", number)
+}
+
 /// Parameters for geometric selection.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub(crate) struct GeomSelectionParams {
     cuboid: CuboidParams,
     cylinder: CylinderParams,
@@ -42,18 +63,27 @@ pub(crate) struct GeomSelectionParams {
     ref_selection: String,
 }
 
+/// Replace the value written into JSON files in place of infinity by infinity.
+fn unbounded(value: f32) -> f32 {
+    if value.abs() >= json::JSON_INFINITY {
+        f32::INFINITY.copysign(value)
+    } else {
+        value
+    }
+}
+
 impl From<Option<gorder::input::Geometry>> for GeomSelectionParams {
     fn from(value: Option<gorder::input::Geometry>) -> Self {
         match value {
             None => Self::default(),
             Some(gorder::input::Geometry::Cuboid(params)) => Self {
                 cuboid: CuboidParams {
-                    minx: params.xdim()[0],
-                    maxx: params.xdim()[1],
-                    miny: params.ydim()[0],
-                    maxy: params.ydim()[1],
-                    minz: params.zdim()[0],
-                    maxz: params.zdim()[1],
+                    minx: unbounded(params.xdim()[0]),
+                    maxx: unbounded(params.xdim()[1]),
+                    miny: unbounded(params.ydim()[0]),
+                    maxy: unbounded(params.ydim()[1]),
+                    minz: unbounded(params.zdim()[0]),
+                    maxz: unbounded(params.zdim()[1]),
                 },
                 reference_type: params.reference().clone().into(),
                 ref_point: get_static_reference_point(params.reference()),
@@ -63,8 +93,8 @@ impl From<Option<gorder::input::Geometry>> for GeomSelectionParams {
             Some(gorder::input::Geometry::Cylinder(params)) => Self {
                 cylinder: CylinderParams {
                     radius: params.radius(),
-                    start: params.span()[0],
-                    end: params.span()[1],
+                    start: unbounded(params.span()[0]),
+                    end: unbounded(params.span()[1]),
                     orientation: params.orientation(),
                 },
                 reference_type: params.reference().clone().into(),
@@ -89,34 +119,41 @@ impl TryFrom<&GuiAnalysis> for Option<gorder::input::Geometry> {
     type Error = ConversionError;
 
     fn try_from(value: &GuiAnalysis) -> Result<Self, Self::Error> {
-        let params = &value.geom_selection_params;
-        let reference = gorder::input::GeomReference::from(params);
+        geometry_to_gorder(value.geom_selection, &value.geom_selection_params)
+    }
+}
 
-        match value.geom_selection {
-            GeomSelection::None => Ok(None),
-            GeomSelection::Cuboid => Ok(Some(
-                gorder::input::Geometry::cuboid(
-                    reference,
-                    [params.cuboid.minx, params.cuboid.maxx],
-                    [params.cuboid.miny, params.cuboid.maxy],
-                    [params.cuboid.minz, params.cuboid.maxz],
-                )
-                .map_err(|e| ConversionError::InvalidGeometryParams(e.to_string()))?,
-            )),
-            GeomSelection::Cylinder => Ok(Some(
-                gorder::input::Geometry::cylinder(
-                    reference,
-                    params.cylinder.radius,
-                    [params.cylinder.start, params.cylinder.end],
-                    params.cylinder.orientation,
-                )
+/// Convert the geometric selection and its parameters to gorder geometry.
+fn geometry_to_gorder(
+    selection: GeomSelection,
+    params: &GeomSelectionParams,
+) -> Result<Option<gorder::input::Geometry>, ConversionError> {
+    let reference = gorder::input::GeomReference::from(params);
+
+    match selection {
+        GeomSelection::None => Ok(None),
+        GeomSelection::Cuboid => Ok(Some(
+            gorder::input::Geometry::cuboid(
+                reference,
+                [params.cuboid.minx, params.cuboid.maxx],
+                [params.cuboid.miny, params.cuboid.maxy],
+                [params.cuboid.minz, params.cuboid.maxz],
+            )
+            .map_err(|e| ConversionError::InvalidGeometryParams(e.to_string()))?,
+        )),
+        GeomSelection::Cylinder => Ok(Some(
+            gorder::input::Geometry::cylinder(
+                reference,
+                params.cylinder.radius,
+                [params.cylinder.start, params.cylinder.end],
+                params.cylinder.orientation,
+            )
+            .map_err(|e| ConversionError::InvalidGeometryParams(e.to_string()))?,
+        )),
+        GeomSelection::Sphere => Ok(Some(
+            gorder::input::Geometry::sphere(reference, params.sphere.radius)
                 .map_err(|e| ConversionError::InvalidGeometryParams(e.to_string()))?,
-            )),
-            GeomSelection::Sphere => Ok(Some(
-                gorder::input::Geometry::sphere(reference, params.sphere.radius)
-                    .map_err(|e| ConversionError::InvalidGeometryParams(e.to_string()))?,
-            )),
-        }
+        )),
     }
 }
 
@@ -158,7 +195,88 @@ fn get_reference_selection(reference: &gorder::input::GeomReference) -> String {
     }
 }
 
+/// Check that the start of the span is not larger than its end.
+pub(crate) fn span_valid(start: f32, end: f32) -> bool {
+    start <= end
+}
+
+/// Format a number, representing infinities by `∞` and `-∞`.
+fn format_value(value: f32) -> String {
+    if value == f32::INFINITY {
+        String::from("∞")
+    } else if value == f32::NEG_INFINITY {
+        String::from("-∞")
+    } else {
+        format!("{}", value)
+    }
+}
+
+/// Format a length stored in nanometers in the provided unit, without the unit.
+fn format_length(value: f32, unit: LengthUnit) -> String {
+    format_value(unit.from_nm(value) as f32)
+}
+
+/// Format a span of lengths in the provided unit. Infinite ends are open intervals.
+fn format_span(start: f32, end: f32, unit: LengthUnit) -> String {
+    format!(
+        "{}{}, {}{}",
+        if start.is_infinite() { "(" } else { "[" },
+        format_length(start, unit),
+        format_length(end, unit),
+        if end.is_infinite() { ")" } else { "]" },
+    )
+}
+
 impl GeomSelectionParams {
+    /// Describe the reference point of the geometry. Lengths are written in the provided unit.
+    fn reference_summary(&self, unit: LengthUnit) -> String {
+        match self.reference_type {
+            GeomReferenceType::Point => format!(
+                "point ({}, {}, {}){}",
+                format_length(self.ref_point.x, unit),
+                format_length(self.ref_point.y, unit),
+                format_length(self.ref_point.z, unit),
+                unit.suffix()
+            ),
+            GeomReferenceType::Center => String::from("box center"),
+            GeomReferenceType::Selection => format!("selection '{}'", self.ref_selection),
+        }
+    }
+
+    /// Get a one-line description of the selected region. Lengths are written in the provided unit.
+    fn summary(&self, geometry: GeomSelection, unit: LengthUnit) -> Option<String> {
+        match geometry {
+            GeomSelection::None => None,
+            GeomSelection::Cuboid => Some(format!(
+                "Cuboid x {}, y {}, z {}{}, relative to {}",
+                format_span(self.cuboid.minx, self.cuboid.maxx, unit),
+                format_span(self.cuboid.miny, self.cuboid.maxy, unit),
+                format_span(self.cuboid.minz, self.cuboid.maxz, unit),
+                unit.suffix(),
+                self.reference_summary(unit)
+            )),
+            GeomSelection::Cylinder => Some(format!(
+                "Cylinder r={}{} along {}, span {}{}, centered on {}",
+                format_length(self.cylinder.radius, unit),
+                unit.suffix(),
+                match self.cylinder.orientation {
+                    Axis::X => "x",
+                    Axis::Y => "y",
+                    Axis::Z => "z",
+                },
+                format_span(self.cylinder.start, self.cylinder.end, unit),
+                unit.suffix(),
+                self.reference_summary(unit)
+            )),
+            GeomSelection::Sphere => Some(format!(
+                "Sphere r={}{}, centered on {}",
+                format_length(self.sphere.radius, unit),
+                unit.suffix(),
+                self.reference_summary(unit)
+            )),
+        }
+    }
+
     /// Allows drag value to get changed from infinity.
     fn change_from_infinity(response: &Response, value: &mut f32, target: f32) {
         if value.is_infinite() && response.dragged() {
@@ -168,25 +286,13 @@ impl GeomSelectionParams {
 
     /// Specify span along a particular dimension.
     fn specify_span(ui: &mut Ui, start: &mut f32, end: &mut f32) {
-        let start_response = ui
-            .add(
-                egui::DragValue::new(start)
-                    .speed(0.05)
-                    .range(f32::NEG_INFINITY..=*end)
-                    .suffix(" nm"),
-            )
+        let start_response = length_drag_value(ui, start, 0.05, f32::NEG_INFINITY..=*end)
             .on_hover_ui(|ui| {
                 ui.label("start");
             });
 
-        let end_response = ui
-            .add(
-                egui::DragValue::new(end)
-                    .speed(0.05)
-                    .range(*start..=f32::INFINITY)
-                    .suffix(" nm"),
-            )
-            .on_hover_ui(|ui| {
+        let end_response =
+            length_drag_value(ui, end, 0.05, *start..=f32::INFINITY).on_hover_ui(|ui| {
                 ui.label("end");
             });
 
@@ -196,6 +302,12 @@ impl GeomSelectionParams {
         } else {
             Self::change_from_infinity(&end_response, end, 0.0);
         }
+
+        if !span_valid(*start, *end) {
+            accessibility::invalid_marker(ui, "invalid span").on_hover_ui(|ui| {
+                ui.label("Start of the span is larger than its end.");
+            });
+        }
     }
 
     /// Specify the reference point.
@@ -232,7 +344,7 @@ impl GeomSelectionParams {
             GeomReferenceType::Center => (),
             GeomReferenceType::Selection => {
                 ui.horizontal(|ui| {
-                    GuiAnalysis::specify_string(
+                    GuiAnalysis::specify_selection(
                     &mut self.ref_selection,
                     ui,
                     " Selection:  ",
@@ -253,22 +365,35 @@ impl GeomSelectionParams {
                 .into_iter()
                 .enumerate()
             {
-                ui.add(
-                    egui::DragValue::new(&mut self.ref_point[i])
-                        .speed(0.05)
-                        .range(-f32::MAX..=f32::MAX)
-                        .suffix(" nm"),
-                )
+                length_drag_value(ui, &mut self.ref_point[i], 0.05, -f32::MAX..=f32::MAX)
+                    .on_hover_ui(|ui| {
+                        ui.label(hint);
+                    });
+            }
+
+            let center = structure::box_center(ui.ctx());
+            if ui
+                .add_enabled(center.is_some(), egui::Button::new("From structure"))
                 .on_hover_ui(|ui| {
-                    ui.label(hint);
-                });
+                    ui.label("Set the point to the center of the simulation box of the structure.");
+                })
+                .on_disabled_hover_ui(|ui| {
+                    ui.label("Provide a structure file with a simulation box to use its center.");
+                })
+                .clicked()
+            {
+                if let Some(center) = center {
+                    for (i, coordinate) in center.into_iter().enumerate() {
+                        self.ref_point[i] = coordinate;
+                    }
+                }
             }
         });
     }
 }
 
 /// Parameters for cuboidal selection.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 struct CuboidParams {
     minx: f32,
     maxx: f32,
@@ -324,10 +449,17 @@ impl CuboidParams {
             GeomSelectionParams::specify_span(ui, &mut self.minz, &mut self.maxz);
         });
     }
+
+    /// Check that all spans are valid.
+    fn sanity_check(&self) -> bool {
+        span_valid(self.minx, self.maxx)
+            && span_valid(self.miny, self.maxy)
+            && span_valid(self.minz, self.maxz)
+    }
 }
 
 /// Parameters for cylindrical selection.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 struct CylinderParams {
     radius: f32,
     start: f32,
@@ -352,18 +484,10 @@ impl CylinderParams {
         ui.horizontal(|ui| {
             GuiAnalysis::label_with_hint(ui, "Radius:   ", "Radius of the cylinder.");
 
-            ui.add(
-                DragValue::new(&mut self.radius)
-                    .speed(0.05)
-                    .range(0.0..=f32::INFINITY)
-                    .suffix(" nm"),
-            );
+            length_drag_value(ui, &mut self.radius, 0.05, 0.0..=f32::INFINITY);
 
             if self.radius == 0.0 {
-                ui.label(
-                    RichText::new("❗")
-                        .color(egui::Color32::from_rgba_premultiplied(150, 0, 0, 100)),
-                );
+                accessibility::invalid_marker(ui, "zero radius");
             }
         });
 
@@ -390,14 +514,14 @@ impl CylinderParams {
         });
     }
 
-    /// Check that all parameters have been provided.
+    /// Check that all parameters have been provided and the span is valid.
     fn sanity_check(&self) -> bool {
-        self.radius > 0.0
+        self.radius > 0.0 && span_valid(self.start, self.end)
     }
 }
 
 /// Parameters for spherical selection.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 struct SphereParams {
     radius: f32,
 }
@@ -414,18 +538,10 @@ impl SphereParams {
         ui.horizontal(|ui| {
             GuiAnalysis::label_with_hint(ui, "Radius:   ", "Radius of the sphere.");
 
-            ui.add(
-                DragValue::new(&mut self.radius)
-                    .speed(0.05)
-                    .range(0.0..=f32::INFINITY)
-                    .suffix(" nm"),
-            );
+            length_drag_value(ui, &mut self.radius, 0.05, 0.0..=f32::INFINITY);
 
             if self.radius == 0.0 {
-                ui.label(
-                    RichText::new("❗")
-                        .color(egui::Color32::from_rgba_premultiplied(150, 0, 0, 100)),
-                );
+                accessibility::invalid_marker(ui, "zero radius");
             }
         });
     }
@@ -457,6 +573,19 @@ impl Display for GeomSelection {
 }
 
 impl GuiAnalysis {
+    /// Describe the analyzed regions for the summary of the analysis.
+    pub(crate) fn geometry_summary(&self, unit: LengthUnit) -> String {
+        let primary = self
+            .geom_selection_params
+            .summary(self.geom_selection, unit)
+            .unwrap_or_else(|| String::from("whole system"));
+
+        match self.additional_regions.len() {
+            0 => primary,
+            n => format!("{} (+{} additional region(s))", primary, n),
+        }
+    }
+
     /// Specify the parameters for geometric selection.
     pub(super) fn specify_geometry(&mut self, ui: &mut Ui) {
         Self::collapsing_with_warning(
@@ -465,83 +594,161 @@ impl GuiAnalysis {
             false,
             self.check_geometry_sanity(),
             |ui| {
-                ui.horizontal(|ui| {
-                    Self::label_with_hint(
-                        ui,
-                        "Geometry: ",
-                        "Geometry specifying the membrane region in which order parameters are to be calculated.",
-                    );
-
-                    ComboBox::from_label("")
-                        .selected_text(format!("{}", self.geom_selection))
-                        .show_ui(ui, |ui| {
-                            for variant in [
-                                GeomSelection::None,
-                                GeomSelection::Cuboid,
-                                GeomSelection::Cylinder,
-                                GeomSelection::Sphere,
-                            ] {
-                                ui.selectable_value(
-                                    &mut self.geom_selection,
-                                    variant,
-                                    format!("{}", variant),
-                                );
-                            }
-                        });
-
-                    if let GeomSelection::None = self.geom_selection {
+                Self::specify_region(
+                    ui,
+                    1,
+                    &mut self.geom_selection,
+                    &mut self.geom_selection_params,
+                );
+
+                let mut index_to_remove = None;
+                for (i, region) in self.additional_regions.iter_mut().enumerate() {
+                    ui.separator();
+                    let number = i + 2;
+
+                    ui.horizontal(|ui| {
                         ui.label(
-                            RichText::new("whole system selected")
-                                .font(egui::FontId::proportional(10.0)),
+                            RichText::new(format!("Region {}", number))
+                                .font(egui::FontId::monospace(12.0)),
                         );
-                    }
-                    ui.end_row();
-                });
 
-                match self.geom_selection {
-                    GeomSelection::None => (),
-                    GeomSelection::Cuboid => {
-                        self.geom_selection_params.cuboid.specify(ui);
-                        self.geom_selection_params.specify_reference(
-                            ui,
-                            "Reference:   ",
-                            "Reference point to which the dimensions of the cuboid relate.",
-                        );
-                    }
-                    GeomSelection::Cylinder => {
-                        self.geom_selection_params.cylinder.specify(ui);
-                        self.geom_selection_params.specify_reference(
-                            ui,
-                            "Reference:   ",
-                            "Reference point to which the dimensions of the cylinder relate.",
-                        );
-                    }
-                    GeomSelection::Sphere => {
-                        self.geom_selection_params.sphere.specify(ui);
-                        self.geom_selection_params.specify_reference(
-                            ui,
-                            "Center:   ",
-                            "Center of the sphere.",
-                        );
-                    }
+                        if ui
+                            .button("➖")
+                            .on_hover_ui(|ui| {
+                                ui.label("Remove this region.");
+                            })
+                            .clicked()
+                        {
+                            index_to_remove = Some(i);
+                        }
+                    });
+
+                    Self::specify_region(ui, number, &mut region.selection, &mut region.params);
+                }
+
+                if let Some(i) = index_to_remove {
+                    self.additional_regions.remove(i);
+                }
+
+                ui.separator();
+                if ui
+                    .button("➕ Add region")
+                    .on_hover_ui(|ui| {
+                        ui.label("Analyze another region of the membrane. Each additional region is analyzed separately and written into output files with the suffix '_regionN'.");
+                    })
+                    .clicked()
+                {
+                    self.additional_regions.push(GeomRegion {
+                        selection: GeomSelection::Cuboid,
+                        params: GeomSelectionParams::default(),
+                    });
                 }
             },
         );
     }
 
+    /// Specify the shape and parameters of a single geometry region.
+    /// `number` is the number of the region, starting from 1 for the primary region.
+    fn specify_region(
+        ui: &mut Ui,
+        number: usize,
+        selection: &mut GeomSelection,
+        params: &mut GeomSelectionParams,
+    ) {
+        ui.horizontal(|ui| {
+            Self::label_with_hint(
+                ui,
+                "Geometry: ",
+                "Geometry specifying the membrane region in which order parameters are to be calculated.",
+            );
+
+            ComboBox::from_id_salt(("guiorder_geometry", number))
+                .selected_text(format!("{}", selection))
+                .show_ui(ui, |ui| {
+                    for variant in [
+                        GeomSelection::None,
+                        GeomSelection::Cuboid,
+                        GeomSelection::Cylinder,
+                        GeomSelection::Sphere,
+                    ] {
+                        ui.selectable_value(selection, variant, format!("{}", variant));
+                    }
+                });
+
+            if let GeomSelection::None = selection {
+                ui.label(
+                    RichText::new("whole system selected").font(egui::FontId::proportional(10.0)),
+                );
+            }
+            ui.end_row();
+        });
+
+        match selection {
+            GeomSelection::None => (),
+            GeomSelection::Cuboid => {
+                params.cuboid.specify(ui);
+                params.specify_reference(
+                    ui,
+                    "Reference:   ",
+                    "Reference point to which the dimensions of the cuboid relate.",
+                );
+            }
+            GeomSelection::Cylinder => {
+                params.cylinder.specify(ui);
+                params.specify_reference(
+                    ui,
+                    "Reference:   ",
+                    "Reference point to which the dimensions of the cylinder relate.",
+                );
+            }
+            GeomSelection::Sphere => {
+                params.sphere.specify(ui);
+                params.specify_reference(ui, "Center:   ", "Center of the sphere.");
+            }
+        }
+
+        let summary = params.summary(*selection, LengthUnit::get(ui.ctx()));
+        let summary = match (summary, number) {
+            (summary, 1) => summary,
+            (summary, _) => Some(format!(
+                "{} -> outputs with suffix '{}'",
+                summary.unwrap_or_else(|| String::from("Whole system")),
+                region_suffix(number)
+            )),
+        };
+
+        if let Some(summary) = summary {
+            ui.label(
+                RichText::new(summary)
+                    .font(egui::FontId::monospace(10.0))
+                    .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+            );
+        }
+    }
+
     /// Check that all parameters for geometric selection have been provided.
     pub(super) fn check_geometry_sanity(&self) -> bool {
-        let shape_valid = match self.geom_selection {
-            GeomSelection::None | GeomSelection::Cuboid => true,
-            GeomSelection::Cylinder => self.geom_selection_params.cylinder.sanity_check(),
-            GeomSelection::Sphere => self.geom_selection_params.sphere.sanity_check(),
+        Self::region_sanity(self.geom_selection, &self.geom_selection_params)
+            && self
+                .additional_regions
+                .iter()
+                .all(|region| Self::region_sanity(region.selection, &region.params))
+    }
+
+    /// Check that all parameters of a single geometry region have been provided.
+    fn region_sanity(selection: GeomSelection, params: &GeomSelectionParams) -> bool {
+        let shape_valid = match selection {
+            GeomSelection::None => true,
+            GeomSelection::Cuboid => params.cuboid.sanity_check(),
+            GeomSelection::Cylinder => params.cylinder.sanity_check(),
+            GeomSelection::Sphere => params.sphere.sanity_check(),
         };
 
-        let ref_selection_valid = match self.geom_selection {
+        let ref_selection_valid = match selection {
             GeomSelection::None => true,
             _ => {
-                self.geom_selection_params.reference_type != GeomReferenceType::Selection
-                    || !self.geom_selection_params.ref_selection.is_empty()
+                params.reference_type != GeomReferenceType::Selection
+                    || !is_blank(&params.ref_selection)
             }
         };
 
@@ -857,4 +1064,153 @@ mod tests {
             _ => panic!("Invalid geometry."),
         }
     }
+
+    #[test]
+    fn geometry_summary_cylinder() {
+        let params = GeomSelectionParams {
+            cylinder: CylinderParams {
+                radius: 2.5,
+                start: 0.0,
+                end: f32::INFINITY,
+                orientation: Axis::Z,
+            },
+            reference_type: GeomReferenceType::Selection,
+            ref_selection: String::from("@protein"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            params
+                .summary(GeomSelection::Cylinder, LengthUnit::Nanometer)
+                .unwrap(),
+            "Cylinder r=2.5 nm along z, span [0, ∞) nm, centered on selection '@protein'"
+        );
+        assert_eq!(
+            params
+                .summary(GeomSelection::Cylinder, LengthUnit::Angstrom)
+                .unwrap(),
+            "Cylinder r=25 Å along z, span [0, ∞) Å, centered on selection '@protein'"
+        );
+    }
+
+    #[test]
+    fn geometry_summary_cuboid() {
+        let params = GeomSelectionParams {
+            cuboid: CuboidParams {
+                minx: -1.5,
+                maxx: 3.0,
+                ..Default::default()
+            },
+            reference_type: GeomReferenceType::Point,
+            ref_point: Vector3D::new(1.0, 2.0, 3.5),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            params
+                .summary(GeomSelection::Cuboid, LengthUnit::Nanometer)
+                .unwrap(),
+            "Cuboid x [-1.5, 3], y (-∞, ∞), z (-∞, ∞) nm, relative to point (1, 2, 3.5) nm"
+        );
+        assert_eq!(
+            params
+                .summary(GeomSelection::Cuboid, LengthUnit::Angstrom)
+                .unwrap(),
+            "Cuboid x [-15, 30], y (-∞, ∞), z (-∞, ∞) Å, relative to point (10, 20, 35) Å"
+        );
+    }
+
+    #[test]
+    fn geometry_summary_sphere_and_none() {
+        let params = GeomSelectionParams {
+            reference_type: GeomReferenceType::Center,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            params
+                .summary(GeomSelection::Sphere, LengthUnit::Nanometer)
+                .unwrap(),
+            "Sphere r=5 nm, centered on box center"
+        );
+        assert!(params
+            .summary(GeomSelection::None, LengthUnit::Nanometer)
+            .is_none());
+    }
+
+    #[test]
+    fn geometry_sanity_invalid_spans() {
+        let mut params = GuiAnalysis {
+            geom_selection: GeomSelection::Cuboid,
+            geom_selection_params: GeomSelectionParams {
+                cuboid: CuboidParams {
+                    miny: 4.0,
+                    maxy: 2.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(!params.check_geometry_sanity());
+
+        params.geom_selection_params.cuboid.maxy = 4.0;
+        assert!(params.check_geometry_sanity());
+
+        params.geom_selection = GeomSelection::Cylinder;
+        params.geom_selection_params.cylinder.start = 1.0;
+        params.geom_selection_params.cylinder.end = -1.0;
+        assert!(!params.check_geometry_sanity());
+
+        params.geom_selection_params.cylinder.end = f32::INFINITY;
+        assert!(params.check_geometry_sanity());
+    }
+
+    #[test]
+    fn geometry_sanity_imported_spans() {
+        let geometry = gorder::input::Geometry::cylinder(
+            gorder::input::GeomReference::Center,
+            2.0,
+            [1.0, 3.0],
+            Axis::X,
+        )
+        .unwrap();
+
+        let mut params = GuiAnalysis {
+            geom_selection: GeomSelection::from(Some(geometry.clone())),
+            geom_selection_params: GeomSelectionParams::from(Some(geometry)),
+            ..Default::default()
+        };
+        assert!(params.check_geometry_sanity());
+
+        // simulate an imported config with the span in the wrong order
+        std::mem::swap(
+            &mut params.geom_selection_params.cylinder.start,
+            &mut params.geom_selection_params.cylinder.end,
+        );
+        assert!(!params.check_geometry_sanity());
+    }
+
+    #[test]
+    fn geometry_sanity_blank_reference() {
+        let geometry = gorder::input::Geometry::cylinder(
+            gorder::input::GeomReference::Center,
+            2.0,
+            [1.0, 3.0],
+            Axis::X,
+        )
+        .unwrap();
+
+        let mut params = GuiAnalysis {
+            geom_selection: GeomSelection::from(Some(geometry.clone())),
+            geom_selection_params: GeomSelectionParams::from(Some(geometry)),
+            ..Default::default()
+        };
+        params.geom_selection_params.reference_type = GeomReferenceType::Selection;
+        params.geom_selection_params.ref_selection = String::from("   ");
+        assert!(!params.check_geometry_sanity());
+
+        params.geom_selection_params.ref_selection = String::from("resname POPC");
+        assert!(params.check_geometry_sanity());
+    }
 }
@@ -0,0 +1,113 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Running the analysis from the command line without launching the GUI.
+//!
+//! `guiorder --run <config>` reads the configuration file, runs the analysis and writes its results.
+//! Use `-` as the path to read the configuration from the standard input instead, e.g.
+//! `cat analysis.yaml | guiorder --run -`.
+
+use crate::{json, GuiAnalysis, GuiOrderApp};
+
+/// Exit code for a successfully finished analysis.
+const EXIT_SUCCESS: i32 = 0;
+/// Exit code for a failed conversion or analysis.
+const EXIT_FAILURE: i32 = 1;
+/// Exit code for invalid command line arguments.
+const EXIT_USAGE: i32 = 2;
+
+/// Path to the configuration file meaning that the configuration is read from the standard input.
+const STDIN_PATH: &str = "-";
+
+/// Get the path to the configuration file if headless mode is requested with `--run <config>`.
+/// Returns `None` if the GUI should be launched.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Option<Result<String, String>> {
+    match args.next()?.as_str() {
+        "--run" => Some(match (args.next(), args.next()) {
+            (Some(config), None) => Ok(config),
+            (None, _) => Err(String::from("missing path to the configuration file")),
+            (Some(_), Some(arg)) => Err(format!("unexpected argument '{}'", arg)),
+        }),
+        _ => None,
+    }
+}
+
+/// Import the configuration file, run the analysis, and write its results.
+/// The configuration is read from the standard input if `config` is `-`.
+fn run(config: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let imported = if config == STDIN_PATH {
+        json::read_config_from(std::io::stdin().lock())?
+    } else {
+        json::read_config(config)?
+    };
+    let analysis = GuiAnalysis::try_from(imported)?;
+    let converted = analysis.to_runnables()?;
+
+    let silent = analysis.other_params.silent;
+    GuiOrderApp::start_logging(silent);
+
+    let result = GuiOrderApp::run_all(converted);
+    GuiOrderApp::display_result(result.is_ok(), silent);
+    result
+}
+
+/// Run the analysis without the GUI if requested by the command line arguments.
+/// Returns the exit code of the application or `None` if the GUI should be launched.
+pub(crate) fn handle_args(args: impl Iterator<Item = String>) -> Option<i32> {
+    let config = match parse_args(args)? {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            eprintln!("usage: guiorder [--run <config.yaml>]");
+            eprintln!("       use '-' as the configuration to read it from the standard input");
+            return Some(EXIT_USAGE);
+        }
+    };
+
+    match run(&config) {
+        Ok(_) => Some(EXIT_SUCCESS),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            Some(EXIT_FAILURE)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn parse_run_args() {
+        assert_eq!(parse_args(args(&[])), None);
+        assert_eq!(parse_args(args(&["--other"])), None);
+        assert_eq!(
+            parse_args(args(&["--run", "analysis.yaml"])),
+            Some(Ok(String::from("analysis.yaml")))
+        );
+        assert_eq!(
+            parse_args(args(&["--run", "-"])),
+            Some(Ok(String::from(STDIN_PATH)))
+        );
+        assert!(parse_args(args(&["--run"])).unwrap().is_err());
+        assert!(parse_args(args(&["--run", "a.yaml", "b.yaml"]))
+            .unwrap()
+            .is_err());
+    }
+
+    #[test]
+    fn fail_on_missing_config() {
+        assert_eq!(
+            handle_args(args(&["--run", "tests/nonexistent.yaml"])),
+            Some(EXIT_FAILURE)
+        );
+        assert_eq!(handle_args(args(&["--run"])), Some(EXIT_USAGE));
+    }
+}
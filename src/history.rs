@@ -0,0 +1,157 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Undoing and redoing changes of the analysis options.
+
+use std::collections::VecDeque;
+
+use eframe::egui::{self, Key, KeyboardShortcut, Modifiers};
+
+use crate::{GuiAnalysis, GuiOrderApp};
+
+/// Maximal number of snapshots that can be undone.
+const MAX_HISTORY: usize = 100;
+
+/// Snapshots of the analysis options.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct History {
+    /// Snapshots that can be restored by undoing, the most recent last.
+    undo: VecDeque<GuiAnalysis>,
+    /// Snapshots that can be restored by redoing, the most recent last.
+    redo: Vec<GuiAnalysis>,
+    /// Last recorded state of the analysis options.
+    committed: Option<GuiAnalysis>,
+}
+
+impl History {
+    /// Record the analysis options, if they have changed since the last snapshot.
+    fn record(&mut self, analysis: &GuiAnalysis) {
+        match self.committed.take() {
+            Some(previous) if previous != *analysis => {
+                self.undo.push_back(previous);
+                if self.undo.len() > MAX_HISTORY {
+                    self.undo.pop_front();
+                }
+                self.redo.clear();
+            }
+            Some(committed) => {
+                self.committed = Some(committed);
+                return;
+            }
+            None => (),
+        }
+
+        self.committed = Some(analysis.clone());
+    }
+
+    /// Get the analysis options before the last change.
+    fn undo(&mut self, current: &GuiAnalysis) -> Option<GuiAnalysis> {
+        self.record(current);
+        let previous = self.undo.pop_back()?;
+        if let Some(committed) = self.committed.take() {
+            self.redo.push(committed);
+        }
+
+        self.committed = Some(previous.clone());
+        Some(previous)
+    }
+
+    /// Get the analysis options after the last undone change.
+    fn redo(&mut self) -> Option<GuiAnalysis> {
+        let next = self.redo.pop()?;
+        if let Some(committed) = self.committed.take() {
+            self.undo.push_back(committed);
+        }
+
+        self.committed = Some(next.clone());
+        Some(next)
+    }
+}
+
+impl GuiOrderApp {
+    /// Record the changes of the analysis options and handle the undo and redo shortcuts.
+    /// Snapshots are only taken when no widget is being edited so that every keystroke
+    /// or every step of a drag does not create a separate snapshot.
+    pub(super) fn handle_history(&mut self, ctx: &egui::Context) {
+        let editing = ctx.memory(|m| m.focused().is_some()) || ctx.dragged_id().is_some();
+        if editing {
+            // text fields handle the shortcuts themselves
+            return;
+        }
+
+        let undo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+        let redo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Y);
+        let redo_shift = KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z);
+
+        // the more specific shortcut must be consumed first
+        let (redo_pressed, undo_pressed) = ctx.input_mut(|i| {
+            let redo_pressed = i.consume_shortcut(&redo_shift) || i.consume_shortcut(&redo);
+            (redo_pressed, i.consume_shortcut(&undo))
+        });
+
+        let restored = if undo_pressed {
+            self.history.undo(&self.analysis)
+        } else if redo_pressed {
+            self.history.redo()
+        } else {
+            self.history.record(&self.analysis);
+            None
+        };
+
+        if let Some(analysis) = restored {
+            self.analysis = analysis;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_structure(structure: &str) -> GuiAnalysis {
+        GuiAnalysis {
+            structure: structure.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut history = History::default();
+        history.record(&with_structure("a.tpr"));
+        history.record(&with_structure("b.tpr"));
+        history.record(&with_structure("b.tpr"));
+
+        // unrecorded changes are recorded before undoing
+        let undone = history.undo(&with_structure("c.tpr")).unwrap();
+        assert_eq!(undone.structure, "b.tpr");
+        assert_eq!(history.undo(&undone).unwrap().structure, "a.tpr");
+        assert!(history.undo(&with_structure("a.tpr")).is_none());
+
+        assert_eq!(history.redo().unwrap().structure, "b.tpr");
+        assert_eq!(history.redo().unwrap().structure, "c.tpr");
+        assert!(history.redo().is_none());
+    }
+
+    #[test]
+    fn new_change_clears_redo() {
+        let mut history = History::default();
+        history.record(&with_structure("a.tpr"));
+        history.record(&with_structure("b.tpr"));
+
+        history.undo(&with_structure("b.tpr")).unwrap();
+        history.record(&with_structure("d.tpr"));
+        assert!(history.redo().is_none());
+    }
+
+    #[test]
+    fn bounded_history() {
+        let mut history = History::default();
+        for i in 0..MAX_HISTORY + 10 {
+            history.record(&with_structure(&format!("{}.tpr", i)));
+        }
+
+        assert_eq!(history.undo.len(), MAX_HISTORY);
+        assert_eq!(history.undo.front().unwrap().structure, "9.tpr");
+    }
+}
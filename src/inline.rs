@@ -0,0 +1,205 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Confirmation of discarding the inline leaflet assignment or membrane normals
+//! imported from a configuration file.
+//!
+//! Inline data can only be imported, not created in the GUI. Switching away from them
+//! would make them impossible to select again, so the user is asked first and the data
+//! are kept until the user confirms discarding them.
+
+use eframe::egui::{self, Id, RichText};
+
+use crate::{common::MembraneNormal, GuiAnalysis, LeafletClassification};
+
+fn pending_id() -> Id {
+    Id::new("guiorder_pending_inline_switch")
+}
+
+/// Switch away from an inline method waiting for confirmation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PendingSwitch {
+    /// Switch from the inline leaflet assignment to the specified method.
+    Leaflets(LeafletClassification),
+    /// Switch from the inline membrane normals to the specified normal.
+    Normal(MembraneNormal),
+}
+
+impl PendingSwitch {
+    fn description(self) -> &'static str {
+        match self {
+            PendingSwitch::Leaflets(_) => "inline leaflet assignment",
+            PendingSwitch::Normal(_) => "inline membrane normals",
+        }
+    }
+}
+
+impl GuiAnalysis {
+    /// Revert a switch away from the inline leaflet assignment and ask for confirmation instead.
+    pub(crate) fn guard_leaflets_switch(
+        &mut self,
+        ctx: &egui::Context,
+        previous: LeafletClassification,
+    ) {
+        if previous == LeafletClassification::FromMap
+            && self.leaflet_classification_method != LeafletClassification::FromMap
+        {
+            ctx.data_mut(|d| {
+                d.insert_temp(
+                    pending_id(),
+                    PendingSwitch::Leaflets(self.leaflet_classification_method),
+                )
+            });
+            self.leaflet_classification_method = LeafletClassification::FromMap;
+        }
+    }
+
+    /// Revert a switch away from the inline membrane normals and ask for confirmation instead.
+    pub(crate) fn guard_normal_switch(&mut self, ctx: &egui::Context, previous: MembraneNormal) {
+        if previous == MembraneNormal::FromMap && self.membrane_normal != MembraneNormal::FromMap {
+            ctx.data_mut(|d| {
+                d.insert_temp(pending_id(), PendingSwitch::Normal(self.membrane_normal))
+            });
+            self.membrane_normal = MembraneNormal::FromMap;
+        }
+    }
+
+    /// Perform the switch and discard the inline data.
+    fn apply_switch(&mut self, switch: PendingSwitch) {
+        match switch {
+            PendingSwitch::Leaflets(method) => {
+                self.leaflet_classification_method = method;
+                self.leaflet_classification_params.discard_from_map();
+            }
+            PendingSwitch::Normal(normal) => {
+                self.membrane_normal = normal;
+                self.from_map_normals = None;
+            }
+        }
+    }
+
+    /// Ask the user whether the inline data should be discarded, if a switch is pending.
+    pub(crate) fn confirm_inline_discard(&mut self, ctx: &egui::Context) {
+        let Some(switch) = ctx.data_mut(|d| d.get_temp::<PendingSwitch>(pending_id())) else {
+            return;
+        };
+
+        egui::Window::new("Discard inline data?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "This will discard the imported {} — continue?",
+                        switch.description()
+                    ))
+                    .font(egui::FontId::monospace(12.0)),
+                );
+                ui.label(
+                    RichText::new("Inline data cannot be created in guiorder, only imported.")
+                        .font(egui::FontId::monospace(12.0))
+                        .color(egui::Color32::from_rgb(200, 150, 0)),
+                );
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Discard").clicked() {
+                        self.apply_switch(switch);
+                        ui.ctx()
+                            .data_mut(|d| d.remove_temp::<PendingSwitch>(pending_id()));
+                    }
+                    if ui.button("Keep").clicked() {
+                        ui.ctx()
+                            .data_mut(|d| d.remove_temp::<PendingSwitch>(pending_id()));
+                    }
+                });
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LeafletClassificationParams;
+
+    #[test]
+    fn switch_from_inline_leaflets_is_deferred() {
+        let ctx = egui::Context::default();
+        let mut assignment = std::collections::HashMap::new();
+        assignment.insert(
+            String::from("POPC"),
+            vec![
+                vec![
+                    gorder::prelude::Leaflet::Upper,
+                    gorder::prelude::Leaflet::Lower,
+                ],
+                vec![
+                    gorder::prelude::Leaflet::Lower,
+                    gorder::prelude::Leaflet::Upper,
+                ],
+            ],
+        );
+        let inline = gorder::input::LeafletClassification::from_map(assignment);
+        let mut analysis = GuiAnalysis {
+            leaflet_classification_method: LeafletClassification::FromMap,
+            leaflet_classification_params: LeafletClassificationParams::try_from(Some(inline))
+                .unwrap(),
+            ..Default::default()
+        };
+
+        analysis.leaflet_classification_method = LeafletClassification::Global;
+        analysis.guard_leaflets_switch(&ctx, LeafletClassification::FromMap);
+        assert_eq!(
+            analysis.leaflet_classification_method,
+            LeafletClassification::FromMap
+        );
+
+        let switch = ctx.data_mut(|d| d.get_temp::<PendingSwitch>(pending_id()));
+        assert_eq!(
+            switch,
+            Some(PendingSwitch::Leaflets(LeafletClassification::Global))
+        );
+
+        analysis.apply_switch(switch.unwrap());
+        assert_eq!(
+            analysis.leaflet_classification_method,
+            LeafletClassification::Global
+        );
+        assert!(!analysis.leaflet_classification_params.has_from_map());
+    }
+
+    #[test]
+    fn switch_from_inline_normals_discards() {
+        let ctx = egui::Context::default();
+        let mut normals = std::collections::HashMap::new();
+        normals.insert(
+            String::from("POPC"),
+            vec![vec![
+                gorder::prelude::Vector3D::new(0.0, 0.0, 1.0),
+                gorder::prelude::Vector3D::new(0.0, 0.0, -1.0),
+            ]],
+        );
+        let mut analysis = GuiAnalysis {
+            membrane_normal: MembraneNormal::X,
+            from_map_normals: Some(gorder::input::MembraneNormal::FromMap(normals)),
+            ..Default::default()
+        };
+
+        // switches between other normals are not guarded
+        analysis.guard_normal_switch(&ctx, MembraneNormal::Z);
+        assert_eq!(analysis.membrane_normal, MembraneNormal::X);
+        assert_eq!(
+            ctx.data_mut(|d| d.get_temp::<PendingSwitch>(pending_id())),
+            None
+        );
+
+        analysis.guard_normal_switch(&ctx, MembraneNormal::FromMap);
+        assert_eq!(analysis.membrane_normal, MembraneNormal::FromMap);
+        assert!(analysis.from_map_normals.is_some());
+
+        analysis.apply_switch(PendingSwitch::Normal(MembraneNormal::X));
+        assert_eq!(analysis.membrane_normal, MembraneNormal::X);
+        assert!(analysis.from_map_normals.is_none());
+    }
+}
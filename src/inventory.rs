@@ -0,0 +1,114 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Overview of the residues and atoms present in the loaded structure.
+
+use eframe::egui::{self, RichText, Ui};
+
+use crate::{structure, GuiAnalysis};
+
+/// Maximal number of atom names listed for a single residue before the list is shortened.
+const MAX_LISTED_ATOMS: usize = 30;
+
+/// List the atom names, shortening the list if there are too many of them.
+fn atom_list(atoms: &[String]) -> String {
+    if atoms.len() <= MAX_LISTED_ATOMS {
+        return atoms.join(" ");
+    }
+
+    format!(
+        "{} … (+{} more)",
+        atoms[..MAX_LISTED_ATOMS].join(" "),
+        atoms.len() - MAX_LISTED_ATOMS
+    )
+}
+
+impl GuiAnalysis {
+    /// Collapsible list of the residue names present in the loaded structure with their counts and atoms,
+    /// together with a button for loading the structure again. Nothing is shown if no structure is loaded.
+    pub(crate) fn structure_inventory(ui: &mut Ui) {
+        let Some(residues) = structure::residue_inventory(ui.ctx()) else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.add_space(104.0);
+            if ui
+                .small_button("⟳")
+                .on_hover_ui(|ui| {
+                    ui.label("Reload the structure and the NDX file from the disk.");
+                })
+                .clicked()
+            {
+                structure::reload_structure(ui.ctx());
+            }
+
+            egui::CollapsingHeader::new(
+                RichText::new(format!(
+                    "Structure contents ({} residue names)",
+                    residues.len()
+                ))
+                .font(egui::FontId::monospace(12.0))
+                .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+            )
+            .id_salt("guiorder_structure_inventory")
+            .default_open(false)
+            .show(ui, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("guiorder_structure_inventory_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for residue in residues.iter() {
+                                    ui.label(
+                                        RichText::new(&residue.name)
+                                            .font(egui::FontId::monospace(12.0))
+                                            .strong(),
+                                    )
+                                    .on_hover_ui(|ui| {
+                                        ui.label(format!(
+                                            "Select using 'resname {}'.",
+                                            residue.name
+                                        ));
+                                    });
+                                    ui.label(
+                                        RichText::new(format!("×{}", residue.count))
+                                            .font(egui::FontId::monospace(12.0)),
+                                    );
+                                    ui.label(
+                                        RichText::new(atom_list(&residue.atoms))
+                                            .font(egui::FontId::monospace(12.0))
+                                            .color(egui::Color32::from_rgba_premultiplied(
+                                                150, 150, 150, 100,
+                                            )),
+                                    )
+                                    .on_hover_ui(|ui| {
+                                        ui.label(format!(
+                                            "{} atom names. Select using 'name ...'.",
+                                            residue.atoms.len()
+                                        ));
+                                    });
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shorten_atom_list() {
+        let atoms = (1..=40).map(|i| format!("C{}", i)).collect::<Vec<_>>();
+        assert_eq!(atom_list(&atoms[..3]), "C1 C2 C3");
+
+        let listed = atom_list(&atoms);
+        assert!(listed.starts_with("C1 C2"));
+        assert!(listed.ends_with("C30 … (+10 more)"));
+    }
+}
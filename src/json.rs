@@ -0,0 +1,271 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Importing and exporting the analysis options in the JSON format.
+
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+
+use eframe::egui::{self, Ui};
+use gorder::input::Analysis;
+
+use crate::{dialogs, file_drop, GuiAnalysis, GuiOrderApp};
+
+/// Extensions of configuration files in the JSON format.
+const JSON_EXTENSIONS: [&str; 1] = ["json"];
+
+/// Value written into JSON files in place of infinity, which cannot be represented in JSON.
+/// Values at least this large are read back as infinite.
+pub(crate) const JSON_INFINITY: f32 = f32::MAX;
+
+/// Check whether the configuration file is in the JSON format, based on its extension.
+pub(crate) fn is_json(path: &Path) -> bool {
+    file_drop::has_extension(path, &JSON_EXTENSIONS)
+}
+
+/// Read the analysis options from a configuration file.
+/// Files with the `.json` extension are read as JSON, all other files as YAML.
+pub(crate) fn read_config(
+    path: &str,
+) -> Result<Analysis, Box<dyn std::error::Error + Send + Sync>> {
+    if !is_json(Path::new(path)) {
+        return Ok(Analysis::from_file(path)?);
+    }
+
+    let file = File::open(path)
+        .map_err(|e| format!("could not open the configuration file '{}': {}", path, e))?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|e| {
+        Box::from(format!(
+            "could not parse the configuration file '{}': {}",
+            path, e
+        ))
+    })
+}
+
+/// Read the analysis options from the content of a configuration file provided by the reader.
+/// The content is parsed as YAML, which also covers configurations in the JSON format.
+pub(crate) fn read_config_from(
+    reader: impl Read,
+) -> Result<Analysis, Box<dyn std::error::Error + Send + Sync>> {
+    serde_yaml::from_reader(reader)
+        .map_err(|e| Box::from(format!("could not parse the configuration: {}", e)))
+}
+
+/// Convert a YAML value into a JSON value.
+/// Tagged values are converted into single-key objects, which is how serde represents enums in JSON.
+/// Infinite numbers are replaced with `JSON_INFINITY` of the same sign.
+fn yaml_to_json(
+    value: serde_yaml::Value,
+) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+    use serde_json::Value as Json;
+    use serde_yaml::Value as Yaml;
+
+    Ok(match value {
+        Yaml::Null => Json::Null,
+        Yaml::Bool(value) => Json::Bool(value),
+        Yaml::Number(number) => {
+            if let Some(integer) = number.as_i64() {
+                Json::from(integer)
+            } else if let Some(integer) = number.as_u64() {
+                Json::from(integer)
+            } else {
+                let float = number.as_f64().unwrap_or(f64::NAN);
+                let float = if float.is_infinite() {
+                    f64::from(JSON_INFINITY).copysign(float)
+                } else {
+                    float
+                };
+
+                Json::Number(
+                    serde_json::Number::from_f64(float)
+                        .ok_or("undefined (NaN) values cannot be written into a JSON file")?,
+                )
+            }
+        }
+        Yaml::String(string) => Json::String(string),
+        Yaml::Sequence(items) => Json::Array(
+            items
+                .into_iter()
+                .map(yaml_to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Yaml::Mapping(mapping) => {
+            let mut object = serde_json::Map::new();
+            for (key, value) in mapping {
+                let Yaml::String(key) = key else {
+                    return Err(Box::from(format!(
+                        "key {:?} cannot be written into a JSON file",
+                        key
+                    )));
+                };
+                object.insert(key, yaml_to_json(value)?);
+            }
+            Json::Object(object)
+        }
+        Yaml::Tagged(tagged) => {
+            let mut object = serde_json::Map::new();
+            object.insert(
+                tagged.tag.to_string().trim_start_matches('!').to_owned(),
+                yaml_to_json(tagged.value)?,
+            );
+            Json::Object(object)
+        }
+    })
+}
+
+/// Serialize the analysis options into the content of a configuration JSON file.
+/// The options are serialized through a YAML value, which, unlike JSON, can hold infinite values.
+pub(crate) fn config_json(
+    analysis: &GuiAnalysis,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let converted = Analysis::try_from(analysis)?;
+    let json = yaml_to_json(serde_yaml::to_value(&converted)?)?;
+    Ok(format!("{}\n", serde_json::to_string_pretty(&json)?))
+}
+
+impl GuiOrderApp {
+    /// Export the analysis options into configuration JSON files, one for each geometry region.
+    fn export_json(&mut self, output: &Path) {
+        match self.export_configs_with(output, config_json) {
+            Ok(files) => self.open_success_window(&format!(
+                "Successfully exported analysis options into {}.",
+                files
+                    .iter()
+                    .map(|path| format!("'{}'", path.display()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            Err(e) => self.open_error_window(e),
+        }
+    }
+
+    /// Button for importing the analysis options from a JSON file.
+    pub(super) fn import_json_button(&mut self, ui: &mut Ui) {
+        let dialog = dialogs::id("import_json");
+        if ui
+            .button("📁 Import from JSON")
+            .on_hover_ui(|ui| {
+                ui.label("Load a JSON configuration file.");
+            })
+            .clicked()
+        {
+            dialogs::open_pick_file(ui.ctx(), dialog);
+        }
+
+        if let Some(input) = dialogs::take_file(ui.ctx(), dialog) {
+            self.import_yaml(&input.display().to_string());
+        }
+    }
+
+    /// Button for exporting the analysis options into a JSON file.
+    pub(super) fn export_json_button(&mut self, ui: &mut Ui, sane: bool) {
+        if ui
+            .add_enabled(sane, egui::Button::new("📁 Export to JSON"))
+            .on_hover_ui(|ui| {
+                ui.label("Export analysis options into a JSON configuration file.");
+            })
+            .on_disabled_hover_ui(|ui| {
+                ui.label("Cannot export analysis options because some are missing.");
+            })
+            .clicked()
+        {
+            ui.close_menu();
+            dialogs::open_save_file(ui.ctx(), dialogs::id("export_json"));
+        }
+    }
+
+    /// Export the JSON file once the user has selected the output path.
+    pub(super) fn finish_json_export(&mut self, ctx: &egui::Context) {
+        if let Some(path) = dialogs::take_file(ctx, dialogs::id("export_json")) {
+            self.export_json(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_json() {
+        assert!(is_json(Path::new("analysis.json")));
+        assert!(is_json(Path::new("dir/analysis.JSON")));
+        assert!(!is_json(Path::new("analysis.yaml")));
+        assert!(!is_json(Path::new("analysis")));
+    }
+
+    #[test]
+    fn read_config_from_reader() {
+        let content = std::fs::read_to_string("tests/parameters.yaml").unwrap();
+        let from_reader = read_config_from(content.as_bytes()).unwrap();
+        let from_file = read_config("tests/parameters.yaml").unwrap();
+        assert_eq!(
+            serde_yaml::to_string(&from_reader).unwrap(),
+            serde_yaml::to_string(&from_file).unwrap()
+        );
+
+        assert!(read_config_from("structure: [".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn import_and_export_json() {
+        let output = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        let path_to_output = output.path().to_path_buf();
+
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+        app.export_json(&path_to_output);
+        assert!(!std::fs::read_to_string(&path_to_output)
+            .unwrap()
+            .contains("null"));
+
+        let mut imported = GuiOrderApp::default();
+        assert!(imported.import_yaml(path_to_output.to_str().unwrap()));
+        assert_eq!(
+            format!("{:?}", imported.analysis),
+            format!("{:?}", app.analysis)
+        );
+        assert_eq!(
+            GuiOrderApp::config_yaml(&imported.analysis).unwrap(),
+            GuiOrderApp::config_yaml(&app.analysis).unwrap()
+        );
+    }
+
+    #[test]
+    fn export_infinite_values() {
+        let output = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        let path_to_output = output.path().to_path_buf();
+
+        let yaml = "structure: system.tpr
+trajectory: md.xtc
+output: order.yaml
+type: !CGOrder
+  beads: \"@membrane\"
+geometry: !Cylinder
+  reference: !Point [4.0, 3.5, 2.5]
+  radius: 2.5
+  span: [-.inf, .inf]
+  orientation: z
+";
+        let mut app = GuiOrderApp::default();
+        app.import_analysis(serde_yaml::from_str(yaml).unwrap());
+        assert!(app.analysis.frame_selection_params.end.is_infinite());
+        app.export_json(&path_to_output);
+
+        let content = std::fs::read_to_string(&path_to_output).unwrap();
+        assert!(!content.contains("null"));
+        serde_json::from_str::<serde_json::Value>(&content).unwrap();
+
+        let mut imported = GuiOrderApp::default();
+        assert!(imported.import_yaml(path_to_output.to_str().unwrap()));
+        assert!(imported.analysis.frame_selection_params.end.is_infinite());
+        assert_eq!(imported.analysis, app.analysis);
+        assert_eq!(
+            GuiOrderApp::config_yaml(&imported.analysis).unwrap(),
+            GuiOrderApp::config_yaml(&app.analysis).unwrap()
+        );
+    }
+}
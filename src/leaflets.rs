@@ -3,12 +3,27 @@
 
 //! Parameters for leaflet assignment.
 
-use std::fmt::Display;
+use std::{fmt::Display, time::SystemTime};
 
 use eframe::egui::{self, RichText, Ui};
 use gorder::input::{Axis, Frequency};
 
-use crate::{common::MembraneNormal, error::ConversionError, GuiAnalysis};
+use crate::{
+    accessibility,
+    common::MembraneNormal,
+    error::ConversionError,
+    focus, paths,
+    selection::is_blank,
+    trajectory::{self, ScanState, TrajectorySpan},
+    units::{self, length_drag_value, valid_radius},
+    GuiAnalysis,
+};
+
+/// Extensions of the NDX files specifying the leaflets.
+const NDX_EXTENSIONS: [&str; 1] = ["ndx"];
+
+/// Number of seconds after which the modification time of a leaflet assignment file is checked again.
+const LEAFLET_FILE_CHECK_INTERVAL: f64 = 2.0;
 
 /// Leaflet assignment method.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -21,6 +36,7 @@ pub(crate) enum LeafletClassification {
     Clustering,
     FromFile,
     FromNdx,
+    FromMap,
 }
 
 impl TryFrom<Option<gorder::input::LeafletClassification>> for LeafletClassification {
@@ -45,7 +61,7 @@ impl TryFrom<Option<gorder::input::LeafletClassification>> for LeafletClassifica
                     Ok(LeafletClassification::FromNdx)
                 }
                 gorder::input::LeafletClassification::FromMap(_) => {
-                    Err(ConversionError::FromMapLeaflets)
+                    Ok(LeafletClassification::FromMap)
                 }
             }
         } else {
@@ -64,12 +80,13 @@ impl Display for LeafletClassification {
             LeafletClassification::Clustering => write!(f, "clustering"),
             LeafletClassification::FromFile => write!(f, "leaflet assignment file"),
             LeafletClassification::FromNdx => write!(f, "NDX files"),
+            LeafletClassification::FromMap => write!(f, "inline assignment"),
         }
     }
 }
 
 /// Parameters for leaflet assignment.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub(crate) struct LeafletClassificationParams {
     global_params: LeafletGlobalParams,
     local_params: LeafletLocalParams,
@@ -78,7 +95,50 @@ pub(crate) struct LeafletClassificationParams {
     from_file_params: LeafletFromFileParams,
     from_ndx_params: LeafletFromNdxParams,
     frequency: Frequency,
+    /// Last N used for the "every Nth frame" frequency.
+    /// Kept separately so it is not lost when switching between frequency options.
+    every_n: usize,
     membrane_normal: Option<MembraneNormal>,
+    /// Inline leaflet assignment from an imported configuration.
+    /// Kept unchanged so that it can be exported again.
+    from_map: Option<gorder::input::LeafletClassification>,
+}
+
+impl LeafletClassificationParams {
+    /// Check whether an inline leaflet assignment has been imported.
+    pub(crate) fn has_from_map(&self) -> bool {
+        self.from_map.is_some()
+    }
+
+    /// Discard the imported inline leaflet assignment.
+    pub(crate) fn discard_from_map(&mut self) {
+        self.from_map = None;
+    }
+
+    /// Get default parameters with placeholder paths of the files the leaflets are read from.
+    pub(crate) fn with_placeholder_paths() -> Self {
+        Self {
+            from_file_params: LeafletFromFileParams {
+                file: String::from("leaflets.yaml"),
+            },
+            from_ndx_params: LeafletFromNdxParams {
+                ndx: vec![String::from("leaflets.ndx")],
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Use the files the leaflets are read from of the other parameters.
+    pub(crate) fn copy_input_paths(&mut self, other: &LeafletClassificationParams) {
+        self.from_file_params.file = other.from_file_params.file.clone();
+        self.from_ndx_params.ndx = other.from_ndx_params.ndx.clone();
+    }
+
+    /// Get mutable access to the paths of the files the leaflets are read from.
+    pub(crate) fn input_paths_mut(&mut self) -> impl Iterator<Item = &mut String> {
+        std::iter::once(&mut self.from_file_params.file).chain(self.from_ndx_params.ndx.iter_mut())
+    }
 }
 
 fn convert_axis_option(axis: Option<Axis>) -> Option<MembraneNormal> {
@@ -146,9 +206,10 @@ impl TryFrom<Option<gorder::input::LeafletClassification>> for LeafletClassifica
                     frequency: params.frequency(),
                     ..Default::default()
                 }),
-                gorder::input::LeafletClassification::FromMap(_) => {
-                    Err(ConversionError::FromMapLeaflets)
-                }
+                gorder::input::LeafletClassification::FromMap(params) => Ok(Self {
+                    from_map: Some(gorder::input::LeafletClassification::FromMap(params)),
+                    ..Default::default()
+                }),
             }
         } else {
             Ok(LeafletClassificationParams::default())
@@ -183,6 +244,12 @@ impl TryFrom<&GuiAnalysis> for Option<gorder::input::LeafletClassification> {
                 .with_frequency(params.frequency),
                 params.membrane_normal,
             ))),
+            LeafletClassification::Local if !valid_radius(params.local_params.radius) => {
+                Err(ConversionError::InvalidLeafletParams(format!(
+                    "radius for the local leaflet assignment must be positive, not '{}'",
+                    params.local_params.radius
+                )))
+            }
             LeafletClassification::Local => Ok(Some(add_normal(
                 gorder::input::LeafletClassification::local(
                     &params.local_params.membrane,
@@ -222,6 +289,11 @@ impl TryFrom<&GuiAnalysis> for Option<gorder::input::LeafletClassification> {
                 )
                 .with_frequency(params.frequency),
             )),
+            LeafletClassification::FromMap => params
+                .from_map
+                .clone()
+                .map(Some)
+                .ok_or(ConversionError::FromMapLeaflets),
         }
     }
 }
@@ -236,7 +308,7 @@ enum RawFrequency {
 }
 
 /// Parameters for the global assignment method.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct LeafletGlobalParams {
     membrane: String,
     heads: String,
@@ -245,14 +317,14 @@ struct LeafletGlobalParams {
 impl LeafletGlobalParams {
     /// Specify the parameters for the global assignment method.
     fn specify(&mut self, ui: &mut Ui) {
-        GuiAnalysis::specify_string(
+        GuiAnalysis::specify_selection(
             &mut self.membrane,
             ui,
             "Membrane:        ",
             "Selection of all lipid atoms forming the membrane.",
             true,
         );
-        GuiAnalysis::specify_string(
+        GuiAnalysis::specify_head_selection(
             &mut self.heads,
             ui,
             "Lipid heads:     ",
@@ -263,12 +335,12 @@ impl LeafletGlobalParams {
 
     /// Check that all required parameters are provided.
     fn sanity_check(&self) -> bool {
-        !self.membrane.is_empty() && !self.heads.is_empty()
+        !is_blank(&self.membrane) && !is_blank(&self.heads)
     }
 }
 
 /// Parameters for the local assignment method.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 struct LeafletLocalParams {
     membrane: String,
     heads: String,
@@ -278,7 +350,7 @@ struct LeafletLocalParams {
 impl LeafletLocalParams {
     /// Specify the parameters for the local assignment method.
     fn specify(&mut self, ui: &mut Ui) {
-        GuiAnalysis::specify_string(
+        GuiAnalysis::specify_selection(
             &mut self.membrane,
             ui,
             "Membrane:       ",
@@ -286,7 +358,7 @@ impl LeafletLocalParams {
             true,
         );
 
-        GuiAnalysis::specify_string(
+        GuiAnalysis::specify_head_selection(
             &mut self.heads,
             ui,
             "Lipid heads:    ",
@@ -301,26 +373,17 @@ impl LeafletLocalParams {
                 "Radius of the cylinder for the calculation of local membrane center.",
             );
 
-            ui.add(
-                egui::DragValue::new(&mut self.radius)
-                    .speed(0.025)
-                    .range(0.0..=f32::MAX)
-                    .suffix(" nm"),
-            )
-            .labelled_by(label.id);
+            length_drag_value(ui, &mut self.radius, 0.025, 0.0..=f32::MAX).labelled_by(label.id);
 
-            if self.radius == 0.0 {
-                ui.label(
-                    RichText::new("❗")
-                        .color(egui::Color32::from_rgba_premultiplied(150, 0, 0, 100)),
-                );
+            if !valid_radius(self.radius) {
+                accessibility::invalid_marker(ui, "invalid radius");
             }
         });
     }
 
     /// Check that all required parameters are provided.
     fn sanity_check(&self) -> bool {
-        !self.membrane.is_empty() && !self.heads.is_empty() && self.radius > 0.0
+        !is_blank(&self.membrane) && !is_blank(&self.heads) && valid_radius(self.radius)
     }
 }
 
@@ -335,7 +398,7 @@ impl Default for LeafletLocalParams {
 }
 
 /// Parameters for the individual assignment method.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct LeafletIndividualParams {
     heads: String,
     methyls: String,
@@ -344,7 +407,7 @@ struct LeafletIndividualParams {
 impl LeafletIndividualParams {
     /// Specify the parameters for the individual assignment method.
     fn specify(&mut self, ui: &mut Ui) {
-        GuiAnalysis::specify_string(
+        GuiAnalysis::specify_head_selection(
             &mut self.heads,
             ui,
             "Lipid heads:     ",
@@ -352,7 +415,7 @@ impl LeafletIndividualParams {
             true,
         );
 
-        GuiAnalysis::specify_string(
+        GuiAnalysis::specify_selection(
             &mut self.methyls,
             ui,
             "Lipid methyls:   ",
@@ -363,12 +426,12 @@ impl LeafletIndividualParams {
 
     /// Check that all required parameters are provided.
     fn sanity_check(&self) -> bool {
-        !self.heads.is_empty() && !self.methyls.is_empty()
+        !is_blank(&self.heads) && !is_blank(&self.methyls)
     }
 }
 
 /// Parameters for the clustering assignment method.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct LeafletClusteringParams {
     heads: String,
 }
@@ -376,7 +439,7 @@ struct LeafletClusteringParams {
 impl LeafletClusteringParams {
     /// Specify the parameters for the clustering assignment method.
     fn specify(&mut self, ui: &mut Ui) {
-        GuiAnalysis::specify_string(
+        GuiAnalysis::specify_head_selection(
             &mut self.heads,
             ui,
             "Lipid heads: ",
@@ -387,16 +450,24 @@ impl LeafletClusteringParams {
 
     /// Check that all required parameters are provided.
     fn sanity_check(&self) -> bool {
-        !self.heads.is_empty()
+        !is_blank(&self.heads)
     }
 }
 
 /// Parameters for the "from file" assignment method.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct LeafletFromFileParams {
     file: String,
 }
 
+/// Result of checking the content of a leaflet assignment file.
+#[derive(Debug, Clone)]
+struct CachedFileCheck {
+    modified: Option<SystemTime>,
+    checked_at: f64,
+    result: Result<(), String>,
+}
+
 impl LeafletFromFileParams {
     /// Specify the parameters for the "from file" assignment method.
     fn specify(&mut self, ui: &mut Ui) {
@@ -407,16 +478,97 @@ impl LeafletFromFileParams {
             "Path to a leaflet assignment file.",
             true,
         );
+
+        if let Err(reason) = Self::check_file(ui.ctx(), &self.file) {
+            ui.horizontal(|ui| {
+                ui.add_space(104.0);
+                ui.label(accessibility::invalid_message(
+                    "not a leaflet assignment file",
+                ))
+                .on_hover_ui(|ui| {
+                    ui.label(reason);
+                });
+            });
+        }
+    }
+
+    /// Check that the file looks like a leaflet assignment file.
+    /// The result is cached for each path and only checked again if the file is modified.
+    /// The modification time is checked at most every `LEAFLET_FILE_CHECK_INTERVAL` seconds.
+    /// Missing files are not reported here since they are already marked by the input field.
+    fn check_file(ctx: &egui::Context, file: &str) -> Result<(), String> {
+        let id = egui::Id::new(("guiorder_leaflet_file", file));
+        let now = ctx.input(|i| i.time);
+        let cached = ctx.data_mut(|d| d.get_temp::<CachedFileCheck>(id));
+
+        if let Some(cached) = &cached {
+            if now - cached.checked_at < LEAFLET_FILE_CHECK_INTERVAL {
+                return cached.result.clone();
+            }
+        }
+
+        let expanded = paths::expand_path(file);
+        let modified = std::fs::metadata(&expanded).and_then(|m| m.modified()).ok();
+
+        let result = match (cached, modified) {
+            (_, None) => Ok(()),
+            (Some(cached), Some(_)) if cached.modified == modified => cached.result,
+            _ => std::fs::read_to_string(&expanded)
+                .map_err(|e| format!("File could not be read: {}", e))
+                .and_then(|content| Self::check_content(&content)),
+        };
+
+        ctx.data_mut(|d| {
+            d.insert_temp(
+                id,
+                CachedFileCheck {
+                    modified,
+                    checked_at: now,
+                    result: result.clone(),
+                },
+            )
+        });
+
+        result
+    }
+
+    /// Check that the content is a YAML map of molecule names to lists of leaflet assignments.
+    fn check_content(content: &str) -> Result<(), String> {
+        let value = serde_yaml::from_str::<serde_yaml::Value>(content)
+            .map_err(|e| format!("File is not a valid YAML file: {}", e))?;
+
+        let map = value.as_mapping().ok_or_else(|| {
+            String::from("Expected a map of molecule names to leaflet assignments.")
+        })?;
+
+        if map.is_empty() {
+            return Err(String::from("File contains no leaflet assignment."));
+        }
+
+        for (molecule, assignment) in map {
+            let name = molecule
+                .as_str()
+                .ok_or_else(|| String::from("Molecule names must be strings."))?;
+
+            if !assignment.is_sequence() {
+                return Err(format!(
+                    "Leaflet assignment for molecule '{}' is not a list.",
+                    name
+                ));
+            }
+        }
+
+        Ok(())
     }
 
     /// Check that all required parameters are provided.
     fn sanity_check(&self) -> bool {
-        !self.file.is_empty()
+        !is_blank(&self.file)
     }
 }
 
 /// Parameters for the "from NDX" assignment method.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct LeafletFromNdxParams {
     heads: String,
     ndx: Vec<String>,
@@ -432,49 +584,142 @@ impl LeafletFromNdxParams {
             ui,
             "NDX files: ",
             "Path to NDX files specifying the leaflets.",
+            &NDX_EXTENSIONS,
             true,
         );
-        GuiAnalysis::specify_string(
+        self.duplicate_ndx_label(ui);
+        GuiAnalysis::specify_head_selection(
             &mut self.heads,
             ui,
             "Lipid heads:   ",
             "Selection of lipid atoms representing lipid heads. One atom per molecule!",
             true,
         );
-        GuiAnalysis::specify_string(
+        // group names are read from the first NDX file
+        let ndx = self.ndx.first().map(String::as_str).unwrap_or_default();
+        GuiAnalysis::specify_ndx_group(
             &mut self.upper_leaflet,
             ui,
             "Upper leaflet: ",
             "Name of the NDX group containing atoms of the upper membrane leaflet.",
             true,
+            ndx,
         );
-        GuiAnalysis::specify_string(
+        GuiAnalysis::specify_ndx_group(
             &mut self.lower_leaflet,
             ui,
             "Lower leaflet: ",
             "Name of the NDX group containing atoms of the lower membrane leaflet.",
             true,
+            ndx,
         );
     }
 
+    /// Show a red warning listing the NDX files that are selected multiple times.
+    fn duplicate_ndx_label(&self, ui: &mut Ui) {
+        let duplicates = GuiAnalysis::duplicate_files(ui.ctx(), &self.ndx);
+        if duplicates.is_empty() {
+            return;
+        }
+
+        ui.label(accessibility::invalid_message(&format!(
+            "NDX files selected multiple times: {}.",
+            duplicates
+                .iter()
+                .map(|file| format!("'{}'", file))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )))
+        .on_hover_ui(|ui| {
+            ui.label("Each NDX file describes the leaflets in a different frame of the trajectory. Use 'Remove duplicates' to keep only the first occurrence of each file.");
+        });
+    }
+
     /// Check that all required parameters are provided.
     fn sanity_check(&self) -> bool {
-        !self.heads.is_empty()
+        !is_blank(&self.heads)
             && !self.ndx.iter().any(|file| file.is_empty())
-            && !self.upper_leaflet.is_empty()
-            && !self.lower_leaflet.is_empty()
+            && !is_blank(&self.upper_leaflet)
+            && !is_blank(&self.lower_leaflet)
     }
 }
 
 impl GuiAnalysis {
+    /// Describe the leaflet assignment method for the summary of the analysis.
+    pub(crate) fn leaflets_summary(&self) -> String {
+        match self.leaflet_classification_method {
+            LeafletClassification::None => String::from("not assigned"),
+            method => method.to_string(),
+        }
+    }
+
+    /// Get the number of NDX files and the estimated number of frames in which the leaflets
+    /// are assigned from them, if these numbers differ. The frames are estimated from the span
+    /// of the trajectory, the frame selection and the frequency of the assignment.
+    /// Glob patterns may match any number of NDX files, so they are never reported.
+    fn ndx_count_mismatch(&self, span: &TrajectorySpan) -> Option<(usize, usize)> {
+        if self.leaflet_classification_method != LeafletClassification::FromNdx {
+            return None;
+        }
+
+        let every = match self.leaflet_classification_params.frequency {
+            Frequency::Once => return None,
+            Frequency::Every(n) => n.get(),
+        };
+
+        let ndx_files = &self.leaflet_classification_params.from_ndx_params.ndx;
+        if ndx_files.iter().any(|file| paths::is_glob_pattern(file)) {
+            return None;
+        }
+
+        let frames = &self.frame_selection_params;
+        let assignments = span
+            .analyzed_frames(frames.begin, frames.end, frames.step)
+            .div_ceil(every);
+        (ndx_files.len() != assignments).then_some((ndx_files.len(), assignments))
+    }
+
+    /// Orange note shown if the number of NDX files differs from the estimated number of frames
+    /// in which the leaflets are assigned. Only shown once the trajectory has been scanned.
+    fn ndx_count_note(&self, ui: &mut Ui) {
+        let Some(ScanState::Done(span)) =
+            trajectory::trajectory_span(ui.ctx(), &self.structure, &self.trajectory)
+        else {
+            return;
+        };
+
+        let Some((ndx, assignments)) = self.ndx_count_mismatch(&span) else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.add_space(104.0);
+            ui.label(
+                RichText::new(format!(
+                    "{} NDX file(s) for ~{} frame(s) with leaflet assignment",
+                    ndx, assignments
+                ))
+                .font(egui::FontId::proportional(10.0))
+                .color(egui::Color32::from_rgb(200, 150, 0)),
+            )
+            .on_hover_ui(|ui| {
+                ui.label(format!(
+                    "One NDX file is read for each frame in which the leaflets are assigned. Based on the scanned trajectory, the frame selection and the frequency, the leaflets are assigned in about {} frame(s).",
+                    assignments
+                ));
+            });
+        });
+    }
+
     /// Specify the method for leaflet assignment and the required parameters.
     pub(super) fn specify_leaflet_classification(&mut self, ui: &mut Ui) {
         Self::collapsing_with_warning(
             ui,
             "Leaflet assignment",
             false,
-            self.check_leaflets_sanity(),
+            self.check_leaflets_sanity() && self.check_leaflet_files_sanity(ui.ctx()),
             |ui| {
+                let previous = self.leaflet_classification_method;
                 ui.horizontal(|ui| {
                     Self::label_with_hint(
                         ui,
@@ -493,7 +738,15 @@ impl GuiAnalysis {
                                 LeafletClassification::Clustering,
                                 LeafletClassification::FromFile,
                                 LeafletClassification::FromNdx,
+                                LeafletClassification::FromMap,
                             ] {
+                                // inline assignment can only be imported, not created
+                                if variant == LeafletClassification::FromMap
+                                    && self.leaflet_classification_params.from_map.is_none()
+                                {
+                                    continue;
+                                }
+
                                 ui.selectable_value(
                                     &mut self.leaflet_classification_method,
                                     variant,
@@ -510,6 +763,7 @@ impl GuiAnalysis {
                     }
                     ui.end_row();
                 });
+                self.guard_leaflets_switch(ui.ctx(), previous);
 
                 ui.vertical(|ui| match self.leaflet_classification_method {
                     LeafletClassification::None => (),
@@ -518,6 +772,7 @@ impl GuiAnalysis {
 
                         Self::specify_frequency(
                             &mut self.leaflet_classification_params.frequency,
+                            &mut self.leaflet_classification_params.every_n,
                             ui,
                             "Frequency:       ",
                         );
@@ -528,6 +783,7 @@ impl GuiAnalysis {
 
                         Self::specify_frequency(
                             &mut self.leaflet_classification_params.frequency,
+                            &mut self.leaflet_classification_params.every_n,
                             ui,
                             "Frequency:      ",
                         );
@@ -541,6 +797,7 @@ impl GuiAnalysis {
 
                         Self::specify_frequency(
                             &mut self.leaflet_classification_params.frequency,
+                            &mut self.leaflet_classification_params.every_n,
                             ui,
                             "Frequency:       ",
                         );
@@ -554,6 +811,7 @@ impl GuiAnalysis {
 
                         Self::specify_frequency(
                             &mut self.leaflet_classification_params.frequency,
+                            &mut self.leaflet_classification_params.every_n,
                             ui,
                             "Frequency:   ",
                         );
@@ -565,6 +823,7 @@ impl GuiAnalysis {
 
                         Self::specify_frequency(
                             &mut self.leaflet_classification_params.frequency,
+                            &mut self.leaflet_classification_params.every_n,
                             ui,
                             "Frequency:   ",
                         );
@@ -576,9 +835,18 @@ impl GuiAnalysis {
 
                         Self::specify_frequency(
                             &mut self.leaflet_classification_params.frequency,
+                            &mut self.leaflet_classification_params.every_n,
                             ui,
                             "Frequency:     ",
                         );
+                        self.ndx_count_note(ui);
+                    }
+                    LeafletClassification::FromMap => {
+                        ui.label(
+                            RichText::new("Leaflet assignment is provided inline in the imported configuration.\nIt cannot be edited in guiorder but will be exported unchanged.")
+                                .font(egui::FontId::monospace(12.0))
+                                .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                        );
                     }
                 });
             },
@@ -586,14 +854,17 @@ impl GuiAnalysis {
     }
 
     /// Specify the frequency of leaflet assignment.
-    fn specify_frequency(frequency: &mut Frequency, ui: &mut Ui, label: &str) {
+    fn specify_frequency(frequency: &mut Frequency, every_n: &mut usize, ui: &mut Ui, label: &str) {
         ui.horizontal(|ui| {
             Self::label_with_hint(ui, label, "Frequency of the leaflet assignment.");
 
-            let (mut raw_frequency, mut n) = match frequency {
-                Frequency::Once => (RawFrequency::Once, 0),
-                Frequency::Every(n) if n.get() == 1 => (RawFrequency::Every, 1),
-                Frequency::Every(n) => (RawFrequency::EveryN, n.get()),
+            let mut raw_frequency = match frequency {
+                Frequency::Once => RawFrequency::Once,
+                Frequency::Every(n) if n.get() == 1 => RawFrequency::Every,
+                Frequency::Every(n) => {
+                    *every_n = n.get();
+                    RawFrequency::EveryN
+                }
             };
 
             ui.radio_value(&mut raw_frequency, RawFrequency::Once, "once");
@@ -604,19 +875,20 @@ impl GuiAnalysis {
                 RawFrequency::Once => *frequency = Frequency::once(),
                 RawFrequency::Every => *frequency = Frequency::every(1).unwrap(),
                 RawFrequency::EveryN => {
-                    if n < 2 {
-                        n = 2;
-                    }
-
-                    ui.add(
-                        egui::DragValue::new(&mut n)
-                            .update_while_editing(false)
-                            .range(1..=usize::MAX)
-                            .speed(1)
-                            .prefix("N = "),
+                    *every_n = (*every_n).max(2);
+
+                    focus::chained(
+                        ui.add(
+                            egui::DragValue::new(every_n)
+                                .update_while_editing(false)
+                                .range(2..=usize::MAX)
+                                .speed(1)
+                                .prefix("N = ")
+                                .custom_parser(units::parse_integer),
+                        ),
                     );
 
-                    *frequency = Frequency::every(n).unwrap();
+                    *frequency = Frequency::every(*every_n).unwrap();
                 }
             }
         });
@@ -638,8 +910,8 @@ impl GuiAnalysis {
                 "Membrane normal used for the leaflet classification. Can be decoupled from the global membrane normal."
             );
 
-            // if any of these buttons is clicked, membrane normal for leaflet assignment 
-            // is permanently decoupled from the global membrane normal
+            // if any of these buttons is clicked, membrane normal for leaflet assignment
+            // is decoupled from the global membrane normal until it is reset
             if ui.add(egui::RadioButton::new(raw_normal == MembraneNormal::X, "x")).clicked() {
                 self.leaflet_classification_params.membrane_normal = Some(MembraneNormal::X);
             }
@@ -652,13 +924,51 @@ impl GuiAnalysis {
                 self.leaflet_classification_params.membrane_normal = Some(MembraneNormal::Z);
             }
 
+            // indicate whether the leaflet normal follows the global membrane normal
+            let linked = self.leaflet_classification_params.membrane_normal.is_none();
+            if linked {
+                ui.label(
+                    RichText::new("🔗")
+                        .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                )
+                .on_hover_ui(|ui| {
+                    ui.label("Follows the global membrane normal.");
+                });
+            } else if ui
+                .small_button("🔗 reset")
+                .on_hover_ui(|ui| {
+                    ui.label("Decoupled from the global membrane normal. Click to follow the global membrane normal again.");
+                })
+                .clicked()
+            {
+                self.leaflet_classification_params.membrane_normal = None;
+            }
+
             // signal to the user that membrane normal must be explicitly set
-            if raw_normal == MembraneNormal::Dynamic || raw_normal == MembraneNormal::FromFile {
-                ui.label(RichText::new("❗").color(egui::Color32::from_rgba_premultiplied(150, 0, 0, 100)));
+            if matches!(
+                raw_normal,
+                MembraneNormal::Dynamic | MembraneNormal::FromFile | MembraneNormal::FromMap
+            ) {
+                accessibility::invalid_marker(ui, "set the normal");
             }
         });
     }
 
+    /// Check the files used for the leaflet assignment: the leaflet assignment file must look like
+    /// a leaflet assignment file and no NDX file may be selected multiple times.
+    pub(super) fn check_leaflet_files_sanity(&self, ctx: &egui::Context) -> bool {
+        let params = &self.leaflet_classification_params;
+        match self.leaflet_classification_method {
+            LeafletClassification::FromFile => {
+                LeafletFromFileParams::check_file(ctx, &params.from_file_params.file).is_ok()
+            }
+            LeafletClassification::FromNdx => {
+                GuiAnalysis::duplicate_files(ctx, &params.from_ndx_params.ndx).is_empty()
+            }
+            _ => true,
+        }
+    }
+
     /// Check that all required options for leaflet assignment have been provided.
     pub(super) fn check_leaflets_sanity(&self) -> bool {
         (match self.leaflet_classification_method {
@@ -688,6 +998,7 @@ impl GuiAnalysis {
                 .leaflet_classification_params
                 .from_ndx_params
                 .sanity_check(),
+            LeafletClassification::FromMap => self.leaflet_classification_params.from_map.is_some(),
         }) && (match self.leaflet_classification_method {
             LeafletClassification::Global
             | LeafletClassification::Local
@@ -702,6 +1013,8 @@ impl GuiAnalysis {
 
 #[cfg(test)]
 mod tests {
+    use std::io::Write;
+
     use approx::assert_relative_eq;
 
     use super::*;
@@ -968,6 +1281,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn guiorder_to_gorder_local_invalid_radius() {
+        for radius in [0.0, -1.0, f32::NAN] {
+            let params = GuiAnalysis {
+                leaflet_classification_method: LeafletClassification::Local,
+                leaflet_classification_params: LeafletClassificationParams {
+                    local_params: LeafletLocalParams {
+                        membrane: String::from("@membrane"),
+                        heads: String::from("name P"),
+                        radius,
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            assert!(!params.check_leaflets_sanity());
+            assert!(matches!(
+                Option::<gorder::input::LeafletClassification>::try_from(&params),
+                Err(ConversionError::InvalidLeafletParams(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn import_zero_local_radius() {
+        let config = "structure: system.tpr\ntrajectory: md.xtc\ntype: !AAOrder\n  heavy_atoms: '@membrane and element name carbon'\n  hydrogens: '@membrane and element name hydrogen'\noutput: order.yaml\nleaflets: !Local\n  membrane: '@membrane'\n  heads: name P\n  radius: 0.0\n";
+
+        // parsing does not validate the radius, the converted analysis must be rejected
+        let analysis = crate::json::read_config_from(config.as_bytes()).unwrap();
+        let imported = GuiAnalysis::try_from(analysis).unwrap();
+        assert!(!imported.check_leaflets_sanity());
+        assert!(matches!(
+            Option::<gorder::input::LeafletClassification>::try_from(&imported),
+            Err(ConversionError::InvalidLeafletParams(_))
+        ));
+    }
+
     #[test]
     fn guiorder_to_gorder_individual() {
         let params = GuiAnalysis {
@@ -1103,4 +1454,209 @@ mod tests {
             _ => panic!("Invalid leaflet classification method."),
         }
     }
+
+    #[test]
+    fn from_map_round_trip() {
+        let mut assignment = std::collections::HashMap::new();
+        assignment.insert(
+            String::from("POPC"),
+            vec![
+                vec![
+                    gorder::prelude::Leaflet::Upper,
+                    gorder::prelude::Leaflet::Lower,
+                ],
+                vec![
+                    gorder::prelude::Leaflet::Lower,
+                    gorder::prelude::Leaflet::Upper,
+                ],
+            ],
+        );
+        let original = gorder::input::LeafletClassification::from_map(assignment);
+
+        let method = LeafletClassification::try_from(Some(original.clone())).unwrap();
+        assert_eq!(method, LeafletClassification::FromMap);
+
+        let params = GuiAnalysis {
+            leaflet_classification_method: method,
+            leaflet_classification_params: LeafletClassificationParams::try_from(Some(
+                original.clone(),
+            ))
+            .unwrap(),
+            ..Default::default()
+        };
+        assert!(params.check_leaflets_sanity());
+
+        let exported = Option::<gorder::input::LeafletClassification>::try_from(&params)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            serde_yaml::to_string(&exported).unwrap(),
+            serde_yaml::to_string(&original).unwrap()
+        );
+    }
+
+    #[test]
+    fn guiorder_to_gorder_from_map_missing() {
+        let params = GuiAnalysis {
+            leaflet_classification_method: LeafletClassification::FromMap,
+            ..Default::default()
+        };
+
+        assert!(!params.check_leaflets_sanity());
+        assert!(matches!(
+            Option::<gorder::input::LeafletClassification>::try_from(&params),
+            Err(ConversionError::FromMapLeaflets)
+        ));
+    }
+
+    #[test]
+    fn leaflet_file_content() {
+        assert!(LeafletFromFileParams::check_content(
+            "POPC:\n- [Upper, Lower, Upper]\n- [Upper, Lower, Lower]\nPOPE:\n- [Lower]\n"
+        )
+        .is_ok());
+
+        assert!(LeafletFromFileParams::check_content("").is_err());
+        assert!(LeafletFromFileParams::check_content("[1, 2, 3]").is_err());
+        assert!(LeafletFromFileParams::check_content("POPC: Upper").is_err());
+        assert!(LeafletFromFileParams::check_content("[ Upper ]\n1 2 3\n[ Lower").is_err());
+    }
+
+    #[test]
+    fn invalid_leaflet_file_blocks_analysis() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "[ Upper ]\n1 2 3\n").unwrap();
+
+        let mut params = GuiAnalysis {
+            leaflet_classification_method: LeafletClassification::FromFile,
+            leaflet_classification_params: LeafletClassificationParams {
+                from_file_params: LeafletFromFileParams {
+                    file: String::from("   "),
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(!params.check_leaflets_sanity());
+
+        let ctx = egui::Context::default();
+        params.leaflet_classification_params.from_file_params.file =
+            file.path().display().to_string();
+        assert!(params.check_leaflets_sanity());
+        assert!(!params.check_leaflet_files_sanity(&ctx));
+
+        // the file is not checked again within the interval
+        std::fs::write(file.path(), "POPC:\n- [Upper, Lower]\n").unwrap();
+        assert!(!params.check_leaflet_files_sanity(&ctx));
+        assert!(params.check_leaflet_files_sanity(&egui::Context::default()));
+    }
+
+    #[test]
+    fn whitespace_leaflet_selections_are_missing() {
+        let mut params = GuiAnalysis {
+            leaflet_classification_method: LeafletClassification::Global,
+            leaflet_classification_params: LeafletClassificationParams {
+                global_params: LeafletGlobalParams {
+                    membrane: String::from("@membrane"),
+                    heads: String::from("  "),
+                },
+                individual_params: LeafletIndividualParams {
+                    heads: String::from("name P"),
+                    methyls: String::from("\t"),
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(!params.check_leaflets_sanity());
+
+        params.leaflet_classification_method = LeafletClassification::Individual;
+        assert!(!params.check_leaflets_sanity());
+
+        params
+            .leaflet_classification_params
+            .individual_params
+            .methyls = String::from("name C218 C316");
+        assert!(params.check_leaflets_sanity());
+    }
+
+    #[test]
+    fn from_ndx_duplicates_and_counts() {
+        let mut params = GuiAnalysis {
+            trajectory: vec![String::from("md.xtc")],
+            leaflet_classification_method: LeafletClassification::FromNdx,
+            leaflet_classification_params: LeafletClassificationParams {
+                from_ndx_params: LeafletFromNdxParams {
+                    heads: String::from("name P"),
+                    ndx: vec![String::from("leaflets1.ndx"), String::from("leaflets1.ndx")],
+                    upper_leaflet: String::from("Upper"),
+                    lower_leaflet: String::from("Lower"),
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let ctx = egui::Context::default();
+        assert!(params.check_leaflets_sanity());
+        assert!(!params.check_leaflet_files_sanity(&ctx));
+
+        params.leaflet_classification_params.from_ndx_params.ndx[1] = String::from("leaflets2.ndx");
+        assert!(params.check_leaflet_files_sanity(&ctx));
+
+        // one trajectory file with two frames, leaflets assigned in every frame
+        let span = TrajectorySpan {
+            start: 0.0,
+            end: 100.0,
+            frames: 2,
+        };
+        assert_eq!(params.ndx_count_mismatch(&span), None);
+
+        // the mismatch is only a note and does not block the analysis
+        let span = TrajectorySpan {
+            start: 0.0,
+            end: 200.0,
+            frames: 3,
+        };
+        assert_eq!(params.ndx_count_mismatch(&span), Some((2, 3)));
+        assert!(params.check_leaflets_sanity());
+
+        params.leaflet_classification_params.frequency = Frequency::once();
+        assert_eq!(params.ndx_count_mismatch(&span), None);
+    }
+
+    #[test]
+    fn from_ndx_counts_follow_frames() {
+        let mut params = GuiAnalysis {
+            trajectory: vec![String::from("md.xtc")],
+            leaflet_classification_method: LeafletClassification::FromNdx,
+            leaflet_classification_params: LeafletClassificationParams {
+                from_ndx_params: LeafletFromNdxParams {
+                    heads: String::from("name P"),
+                    ndx: (1..=5).map(|i| format!("leaflets{}.ndx", i)).collect(),
+                    upper_leaflet: String::from("Upper"),
+                    lower_leaflet: String::from("Lower"),
+                },
+                frequency: Frequency::every(10).unwrap(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let span = TrajectorySpan {
+            start: 0.0,
+            end: 4900.0,
+            frames: 50,
+        };
+
+        // 50 frames, leaflets assigned every 10th frame
+        assert_eq!(params.ndx_count_mismatch(&span), None);
+
+        // only the first half of the trajectory is analyzed
+        params.frame_selection_params.end = 2400.0;
+        assert_eq!(params.ndx_count_mismatch(&span), Some((5, 3)));
+
+        // glob patterns may match any number of files
+        params.leaflet_classification_params.from_ndx_params.ndx =
+            vec![String::from("leaflets*.ndx")];
+        assert_eq!(params.ndx_count_mismatch(&span), None);
+    }
 }
@@ -0,0 +1,310 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Capturing of the log output so that it can be displayed inside the application.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
+
+use eframe::egui::{self, RichText};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::{window, GuiOrderApp};
+
+/// Maximal number of log messages kept in memory. Older messages are discarded.
+const MAX_RECORDS: usize = 10_000;
+
+/// Single captured log message.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LogRecord {
+    /// Number of messages captured before this one.
+    pub index: usize,
+    pub level: Level,
+    pub message: String,
+}
+
+/// All log messages captured in this session.
+static RECORDS: Mutex<Vec<LogRecord>> = Mutex::new(Vec::new());
+
+/// Total number of log messages captured in this session, including the discarded ones.
+static CAPTURED: AtomicUsize = AtomicUsize::new(0);
+
+/// Number of changes of the captured messages. Incremented when a message is captured or the log is cleared.
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Logger that captures all messages and passes them to the terminal logger.
+struct CapturingLogger {
+    terminal: Box<dyn Log>,
+}
+
+/// Store the message, discarding the oldest messages if there are too many.
+fn capture(level: Level, message: &str) {
+    let mut records = RECORDS.lock().unwrap();
+    if records.len() >= MAX_RECORDS {
+        let excess = records.len() + 1 - MAX_RECORDS;
+        records.drain(..excess);
+    }
+
+    records.push(LogRecord {
+        index: CAPTURED.fetch_add(1, Ordering::SeqCst),
+        level,
+        message: window::strip_ansi_codes(message),
+    });
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Discard all captured messages.
+fn clear() {
+    RECORDS.lock().unwrap().clear();
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            capture(record.level(), &record.args().to_string());
+        }
+
+        self.terminal.log(record);
+    }
+
+    fn flush(&self) {
+        self.terminal.flush();
+    }
+}
+
+/// Install the logger writing into the terminal and capturing the messages for the log window.
+pub(crate) fn init() {
+    let logger = CapturingLogger {
+        terminal: Box::new(colog::default_builder().build()),
+    };
+
+    if let Err(e) = log::set_boxed_logger(Box::new(logger)) {
+        eprintln!("Could not initialize the logger: {}", e);
+    }
+    log::set_max_level(LevelFilter::Info);
+}
+
+/// Get the captured messages that are at least as severe as the level.
+fn records(level: LevelFilter) -> Vec<LogRecord> {
+    RECORDS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|record| record.level <= level)
+        .cloned()
+        .collect()
+}
+
+/// Get the total number of messages captured so far.
+pub(crate) fn captured_count() -> usize {
+    CAPTURED.load(Ordering::SeqCst)
+}
+
+/// Get the warnings captured since `start` messages had been captured.
+pub(crate) fn warnings_since(start: usize) -> Vec<String> {
+    RECORDS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|record| record.index >= start && record.level == Level::Warn)
+        .map(|record| record.message.clone())
+        .collect()
+}
+
+/// Get the color in which the message of the given level is displayed.
+fn level_color(level: Level) -> egui::Color32 {
+    match level {
+        Level::Error => egui::Color32::from_rgb(200, 0, 0),
+        Level::Warn => egui::Color32::from_rgb(200, 150, 0),
+        Level::Info => egui::Color32::from_rgb(0, 150, 200),
+        Level::Debug | Level::Trace => egui::Color32::from_rgba_premultiplied(150, 150, 150, 100),
+    }
+}
+
+/// State of the window displaying the log.
+#[derive(Debug, Clone)]
+pub(crate) struct LogViewer {
+    /// Is the window open?
+    open: bool,
+    /// Least severe level of the displayed messages.
+    level: LevelFilter,
+    /// Displayed messages.
+    shown: ShownRecords,
+}
+
+impl Default for LogViewer {
+    fn default() -> Self {
+        LogViewer {
+            open: false,
+            level: LevelFilter::Info,
+            shown: ShownRecords::default(),
+        }
+    }
+}
+
+/// Messages displayed in the log window.
+#[derive(Debug, Clone, Default)]
+struct ShownRecords {
+    records: Vec<LogRecord>,
+    /// Generation of the captured messages and the level for which the messages were collected.
+    collected_for: Option<(usize, LevelFilter)>,
+}
+
+impl ShownRecords {
+    /// Collect the messages again if the captured messages or the level have changed.
+    fn refresh(&mut self, level: LevelFilter) -> &[LogRecord] {
+        let current = (GENERATION.load(Ordering::SeqCst), level);
+        if self.collected_for != Some(current) {
+            self.records = records(level);
+            self.collected_for = Some(current);
+        }
+
+        &self.records
+    }
+}
+
+impl GuiOrderApp {
+    /// Button opening the log window.
+    pub(super) fn log_button(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .small_button("📜 Log")
+            .on_hover_ui(|ui| {
+                ui.label("Show the output of the analysis.");
+            })
+            .clicked()
+        {
+            self.log_viewer.open = !self.log_viewer.open;
+        }
+    }
+
+    /// Render the window displaying the captured log messages, if open.
+    pub(super) fn render_log_window(&mut self, ctx: &egui::Context) {
+        let viewer = &mut self.log_viewer;
+        if !viewer.open {
+            return;
+        }
+
+        egui::Window::new("Log")
+            .open(&mut viewer.open)
+            .default_width(440.0)
+            .default_height(300.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Show:");
+                    egui::ComboBox::from_id_salt("guiorder_log_level")
+                        .selected_text(viewer.level.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in [LevelFilter::Error, LevelFilter::Warn, LevelFilter::Info]
+                            {
+                                ui.selectable_value(&mut viewer.level, level, level.as_str());
+                            }
+                        });
+
+                    if ui.button("🗑 Clear").clicked() {
+                        clear();
+                    }
+                });
+
+                ui.separator();
+
+                let records = viewer.shown.refresh(viewer.level);
+                if records.is_empty() {
+                    ui.label(
+                        RichText::new("No messages.")
+                            .font(egui::FontId::monospace(12.0))
+                            .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                    );
+                    return;
+                }
+
+                egui::ScrollArea::both()
+                    .auto_shrink([false, false])
+                    .stick_to_bottom(true)
+                    .show_rows(ui, 14.0, records.len(), |ui, range| {
+                        for record in &records[range] {
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new(format!("[{:<5}]", record.level))
+                                        .font(egui::FontId::monospace(12.0))
+                                        .color(level_color(record.level)),
+                                );
+                                ui.label(
+                                    RichText::new(&record.message)
+                                        .font(egui::FontId::monospace(12.0)),
+                                );
+                            });
+                        }
+                    });
+            });
+
+        // the log is updated from the analysis thread
+        if viewer.open {
+            ctx.request_repaint_after(std::time::Duration::from_millis(250));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_without_colors() {
+        capture(Level::Info, "\x1b[1mtest_capture\x1b[0m supplied");
+        assert!(records(LevelFilter::Info)
+            .iter()
+            .any(|r| r.message == "test_capture supplied"));
+    }
+
+    #[test]
+    fn filter_by_level() {
+        capture(Level::Warn, "test_filter_warning");
+        capture(Level::Debug, "test_filter_debug");
+
+        let shown = records(LevelFilter::Info);
+        assert!(shown.iter().any(|r| r.message == "test_filter_warning"));
+        assert!(!shown.iter().any(|r| r.message == "test_filter_debug"));
+
+        let shown = records(LevelFilter::Debug);
+        assert!(shown.iter().any(|r| r.message == "test_filter_debug"));
+    }
+
+    #[test]
+    fn refresh_after_change() {
+        let mut shown = ShownRecords::default();
+        assert!(!shown
+            .refresh(LevelFilter::Info)
+            .iter()
+            .any(|r| r.message == "test_refresh_info"));
+
+        capture(Level::Info, "test_refresh_info");
+        assert!(shown
+            .refresh(LevelFilter::Info)
+            .iter()
+            .any(|r| r.message == "test_refresh_info"));
+        assert!(!shown
+            .refresh(LevelFilter::Warn)
+            .iter()
+            .any(|r| r.message == "test_refresh_info"));
+    }
+
+    #[test]
+    fn collect_new_warnings() {
+        capture(Level::Warn, "test_old_warning");
+        let start = captured_count();
+        capture(Level::Warn, "test_new_warning");
+        capture(Level::Info, "test_new_info");
+
+        let warnings = warnings_since(start);
+        assert!(warnings.contains(&String::from("test_new_warning")));
+        assert!(!warnings.contains(&String::from("test_old_warning")));
+        assert!(!warnings.contains(&String::from("test_new_info")));
+    }
+}
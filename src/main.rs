@@ -4,30 +4,79 @@
 use std::{
     fs::File,
     io::{BufWriter, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
     thread::JoinHandle,
 };
 
+use close::CloseState;
 use colored::Colorize;
 use common::GuiAnalysis;
+use config_watch::WatchedConfig;
 use eframe::egui::{self, RichText, Ui};
+use file_drop::DropTargets;
 use gorder::colog_info;
+use history::History;
 use leaflets::{LeafletClassification, LeafletClassificationParams};
+use logging::LogViewer;
+use results::ResultsViewer;
+use status::RunStatus;
+use user_presets::UserPresets;
 use window::Windows;
+use yaml_header::YamlHeader;
 
+mod accessibility;
 mod analysis_types;
+mod bonds;
+mod changes;
+mod clipboard;
+mod close;
+mod command;
 mod common;
+mod config_watch;
 mod convert;
+mod dialogs;
+mod dry_run;
 mod error;
 mod estimate_error;
+mod extensions;
+mod file_drop;
+mod focus;
 mod frame_selection;
 mod geometry;
+mod headless;
+mod history;
+mod inline;
+mod inventory;
+mod json;
 mod leaflets;
+mod logging;
 mod membrane_normal;
+mod minimal;
+mod ndx;
 mod ordermaps;
 mod other_options;
+mod outputs;
+mod paths;
+mod pinned;
+mod plots;
+mod presets;
+mod python;
+mod results;
+mod saved_selections;
+mod scroll;
+mod sections;
+mod selection;
+mod status;
+mod structure;
+mod suggest;
+mod summary;
+mod trajectory;
+mod units;
+mod user_presets;
 mod window;
+mod xvg;
+mod yaml_header;
 
 pub const GUIORDER_VERSION: &str = env!("CARGO_PKG_VERSION");
 const LINE_SPACING: f32 = 10.0;
@@ -35,12 +84,17 @@ const LINE_SPACING: f32 = 10.0;
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
+            .with_app_id("guiorder")
             .with_inner_size([465.0, 640.0])
             .with_resizable(false),
         ..Default::default()
     };
 
-    colog::init();
+    logging::init();
+
+    if let Some(code) = headless::handle_args(std::env::args().skip(1)) {
+        std::process::exit(code);
+    }
 
     eframe::run_native(
         &format!("guiorder v{}", GUIORDER_VERSION),
@@ -58,6 +112,34 @@ fn main() -> eframe::Result {
 pub(crate) struct GuiOrderApp {
     analysis: GuiAnalysis,
     windows: Windows,
+    /// Status of the last (or currently running) analysis.
+    status: RunStatus,
+    /// Rows onto which files can be dropped.
+    drop_targets: DropTargets,
+    /// Results of the last successful analysis.
+    results: Option<ResultsViewer>,
+    /// Configuration file that was last imported.
+    watched_config: Option<WatchedConfig>,
+    /// State of the management of the user-defined presets.
+    user_presets: UserPresets,
+    /// Command being entered in the window for importing from a gorder command, if open.
+    command_input: Option<String>,
+    /// Configuration being entered in the window for importing a pasted YAML, if open.
+    pasted_yaml: Option<String>,
+    /// Analysis options as they were last imported.
+    imported: Option<GuiAnalysis>,
+    /// Is the window listing the changes since the last import open?
+    changes_open: bool,
+    /// State of the window displaying the log.
+    log_viewer: LogViewer,
+    /// Analysis options of the last (or currently running) analysis with the output paths actually used.
+    last_run: Option<GuiAnalysis>,
+    /// Number of log messages captured before the last (or currently running) analysis was started.
+    log_start: usize,
+    /// Snapshots of the analysis options for undoing and redoing changes.
+    history: History,
+    /// State of closing the application.
+    close_state: CloseState,
     /// Analysis running?
     running: Arc<Mutex<bool>>,
     thread_handle: Mutex<Option<JoinHandle<Result<(), Box<dyn std::error::Error + Send + Sync>>>>>,
@@ -65,9 +147,26 @@ pub(crate) struct GuiOrderApp {
 
 impl eframe::App for GuiOrderApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let tab_press = focus::begin_frame(ctx);
+        self.handle_history(ctx);
+        self.handle_close_request(ctx);
+        self.analysis.confirm_inline_discard(ctx);
+        self.render_status_bar(ctx);
+        sections::update_title(ctx, self.analysis.invalid_sections(ctx).len());
+        self.render_config_banner(ctx);
+        self.handle_dropped_files(ctx);
+        structure::request_structure(ctx, &self.analysis.structure, &self.analysis.ndx);
+        ndx::set_global_ndx(ctx, &self.analysis.ndx);
+
         egui::CentralPanel::default().show(ctx, |ui| {
-            egui::ScrollArea::vertical()
-                .auto_shrink([false, false])
+            let mut scroll_area = egui::ScrollArea::vertical()
+                .id_salt(scroll::MAIN_SCROLL_SALT)
+                .auto_shrink([false, false]);
+            if let Some(offset) = scroll::offset_to_restore(ctx) {
+                scroll_area = scroll_area.vertical_scroll_offset(offset);
+            }
+
+            let output = scroll_area
                 .show(ui, |ui| {
                     ui.vertical_centered(|ui| {
                         ui.heading(
@@ -81,26 +180,69 @@ impl eframe::App for GuiOrderApp {
                             ))
                             .font(egui::FontId::monospace(15.0)),
                         );
+
+                        if ui
+                            .small_button("❓ Selection help")
+                            .on_hover_ui(|ui| {
+                                ui.label("Show the syntax of the selection language with examples.");
+                            })
+                            .clicked()
+                        {
+                            self.open_selection_help_window();
+                        }
+
+                        if ui
+                            .small_button("ℹ About")
+                            .on_hover_ui(|ui| {
+                                ui.label("Show the versions and links to the documentation.");
+                            })
+                            .clicked()
+                        {
+                            self.open_about_window();
+                        }
                     });
 
                     ui.add_space(20.0);
                     self.import_yaml_button(ui);
                     ui.separator();
 
-                    GuiAnalysis::specify_input_file(
+                    // sections pinned by the user are shown first
+                    let pinned = pinned::pinned_sections(ui.ctx());
+                    if !pinned.is_empty() {
+                        ui.label(
+                            RichText::new("📌 Pinned")
+                                .font(egui::FontId::monospace(12.0))
+                                .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                        )
+                        .on_hover_ui(|ui| {
+                            ui.label("Right-click the heading of a section to pin or unpin it.");
+                        });
+                        for &section in &pinned {
+                            self.analysis.specify_pinnable(ui, section);
+                            ui.add_space(LINE_SPACING);
+                        }
+                        ui.separator();
+                    }
+
+                    sections::anchor(ui, sections::FILES_SECTION);
+                    self.drop_targets.structure = Some(GuiAnalysis::specify_input_file(
                         &mut self.analysis.structure,
                         ui,
                         "Structure:   ",
                         "Path to a file containing the structure of the system.",
                         true,
-                    );
-                    GuiAnalysis::specify_multiple_input_files(
+                    ));
+                    GuiAnalysis::structure_inventory(ui);
+                    self.analysis.suggest_output_yaml(ui.ctx());
+                    self.drop_targets.trajectory = Some(GuiAnalysis::specify_multiple_input_files(
                         &mut self.analysis.trajectory,
                         ui,
                         "Trajectory:  ",
                         "Path to a file containing the trajectory to analyze. Provide multiple files by clicking the '+' button or by selecting them interactively.",
+                        &file_drop::TRAJECTORY_EXTENSIONS,
                         true,
-                    );
+                    ));
+                    self.analysis.unexpected_trajectories_label(ui);
                     GuiAnalysis::specify_output_file(
                         &mut self.analysis.output.output_yaml,
                         ui,
@@ -108,29 +250,22 @@ impl eframe::App for GuiOrderApp {
                         "Path to an output YAML file where the full results of the analysis will be saved.",
                         true,
                     );
+                    GuiAnalysis::missing_extension_note(
+                        ui,
+                        &mut self.analysis.output.output_yaml,
+                        &file_drop::YAML_EXTENSIONS,
+                    );
+                    self.analysis.output_collisions_label(ui);
 
                     ui.separator();
+                    sections::anchor(ui, sections::ANALYSIS_TYPE_SECTION);
                     self.analysis.specify_analysis_type(ui);
                     ui.separator();
 
-                    ui.add_space(LINE_SPACING);
-                    self.analysis.specify_advanced_input(ui);
-                    ui.add_space(LINE_SPACING);
-                    self.analysis.specify_advanced_output(ui);
-                    ui.add_space(LINE_SPACING);
-                    self.analysis.specify_frame_selection(ui);
-                    ui.add_space(LINE_SPACING);
-                    self.analysis.specify_membrane_normal(ui);
-                    ui.add_space(LINE_SPACING);
-                    self.analysis.specify_leaflet_classification(ui);
-                    ui.add_space(LINE_SPACING);
-                    self.analysis.specify_ordermaps(ui);
-                    ui.add_space(LINE_SPACING);
-                    self.analysis.specify_geometry(ui);
-                    ui.add_space(LINE_SPACING);
-                    self.analysis.specify_estimate_error(ui);
-                    ui.add_space(LINE_SPACING);
-                    self.analysis.specify_other_options(ui);
+                    for section in pinned::unpinned_sections(&pinned) {
+                        ui.add_space(LINE_SPACING);
+                        self.analysis.specify_pinnable(ui, section);
+                    }
                     ui.add_space(LINE_SPACING);
 
                     ui.separator();
@@ -138,19 +273,22 @@ impl eframe::App for GuiOrderApp {
                         ui.add_space(54.0);
                         if GuiAnalysis::smart_button(
                             ui,
-                            self.analysis.check_sanity(),
+                            self.analysis.check_sanity(ui.ctx()),
                             false,
                             "📁 Export to YAML",
                             "Export analysis options into a YAML configuration file.",
                             "Cannot export analysis options because some are missing.",
                             "This should never appear.",
                         ).clicked() {
-                            if let Some(path) = rfd::FileDialog::new().save_file() {
-                                self.export_to_yaml(path);
-                            }
+                            dialogs::open_save_file(ui.ctx(), dialogs::id("export_yaml"));
+                        }
+                        if let Some(path) = dialogs::take_file(ui.ctx(), dialogs::id("export_yaml")) {
+                            self.export_to_yaml(path, YamlHeader::get(ui.ctx()));
                         }
 
-                        ui.add_space(54.0);
+                        self.export_menu(ui);
+
+                        ui.add_space(34.0);
                         ui.separator();
                         ui.add_space(46.0);
 
@@ -162,7 +300,7 @@ impl eframe::App for GuiOrderApp {
 
                         if GuiAnalysis::smart_button(
                             ui,
-                            self.analysis.check_sanity(),
+                            self.analysis.check_sanity(ui.ctx()),
                             *self.running.lock().unwrap(),
                             "🔥 Run the analysis",
                             &hint,
@@ -171,6 +309,8 @@ impl eframe::App for GuiOrderApp {
                         ).clicked() {
                             self.run_analysis();
                         };
+
+                        self.validate_button(ui);
                     });
 
                     ui.separator();
@@ -181,7 +321,7 @@ impl eframe::App for GuiOrderApp {
                             ui.add_space(10.0);
                             ui.spinner();
                             ui.label(
-                                RichText::new("Analysis is running. See the terminal for more details.")
+                                RichText::new("Analysis is running. See the log for more details.")
                                     .font(egui::FontId::monospace(12.0))
                             );
                         });
@@ -193,33 +333,59 @@ impl eframe::App for GuiOrderApp {
                             Some(handle) => {
                                 match handle.join().unwrap() {
                                     Ok(_) => {
+                                        if let RunStatus::Running(start) = self.status {
+                                            self.status = RunStatus::Succeeded(start.elapsed());
+                                        }
                                         Self::display_result(true, self.analysis.other_params.silent);
-                                        self.open_success_window("Analysis finished successfully.");
+                                        self.open_success_window_with_links(
+                                            "Analysis finished successfully.",
+                                            self.output_folders(),
+                                            self.output_file_links(),
+                                        );
+                                        self.open_results_window();
                                     }
                                     Err(e) => {
+                                        self.status = RunStatus::Failed;
                                         log::error!("{}", e);
                                         Self::display_result(false, self.analysis.other_params.silent);
                                         self.open_error_window(e);
                                     }
                                 }
+
+                                let warnings = logging::warnings_since(self.log_start);
+                                if !warnings.is_empty() {
+                                    self.open_analysis_warnings_window(warnings);
+                                }
                             }
                         }
                     }
 
                     // render windows
                     self.windows.render(ctx);
+                    self.render_results_window(ctx);
+                    self.render_import_command_window(ctx);
+                    self.render_paste_yaml_window(ctx);
+                    self.render_changes_window(ctx);
+                    self.render_log_window(ctx);
+                    self.render_presets_manager(ctx);
                 });
+            scroll::remember_offset(ctx, output.state.offset.y);
         });
+
+        Self::render_drop_overlay(ctx);
+        focus::end_frame(ctx, tab_press);
     }
 }
 
 /// Paths to all specified output files.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct OutputFiles {
     output_yaml: String,
     output_csv: String,
     output_tab: String,
     output_xvg: String,
+    /// Place all outputs into a new timestamped directory for each run?
+    timestamped_directory: bool,
 }
 
 impl From<&gorder::input::Analysis> for OutputFiles {
@@ -229,53 +395,98 @@ impl From<&gorder::input::Analysis> for OutputFiles {
             output_csv: value.output_csv().clone().unwrap_or_default(),
             output_tab: value.output_tab().clone().unwrap_or_default(),
             output_xvg: value.output_xvg().clone().unwrap_or_default(),
+            timestamped_directory: false,
         }
     }
 }
 
 impl GuiOrderApp {
-    /// Create a button for importing parameters from a YAML file.
+    /// Create buttons for importing parameters from a YAML or JSON file, a gorder command, a pasted YAML, or a preset.
     fn import_yaml_button(&mut self, ui: &mut Ui) {
-        ui.horizontal(|ui| {
-            ui.vertical_centered(|ui| {
-                let input_yaml = if ui
+        ui.columns(5, |columns| {
+            columns[0].vertical_centered(|ui| {
+                let dialog = dialogs::id("import_yaml");
+                if ui
                     .button("📁 Import from YAML")
                     .on_hover_ui(|ui| {
                         ui.label("Load a YAML configuration file.");
                     })
                     .clicked()
                 {
-                    rfd::FileDialog::new()
-                        .set_directory(".")
-                        .pick_file()
-                        .map(|path| path.display().to_string())
-                } else {
-                    None
-                };
-
-                if let Some(input) = input_yaml {
-                    self.import_yaml(&input);
+                    dialogs::open_pick_file(ui.ctx(), dialog);
+                }
+
+                if let Some(input) = dialogs::take_file(ui.ctx(), dialog) {
+                    self.import_yaml(&input.display().to_string());
                 }
             });
+
+            columns[1].vertical_centered(|ui| self.import_json_button(ui));
+            columns[2].vertical_centered(|ui| self.import_command_button(ui));
+            columns[3].vertical_centered(|ui| self.paste_yaml_button(ui));
+            columns[4].vertical_centered(|ui| self.presets_menu(ui));
         });
     }
 
-    /// Import parameters from a yaml file.
-    fn import_yaml(&mut self, input: &str) {
-        match gorder::input::Analysis::from_file(input) {
-            Err(e) => self.open_error_window(Box::from(e)),
-            Ok(analysis) => match analysis.try_into() {
-                Err(e) => self.open_error_window(Box::from(e)),
-                Ok(converted) => {
-                    self.analysis = converted;
+    /// Import parameters from a YAML or JSON configuration file.
+    /// The format is detected from the extension of the file.
+    /// Returns `true` if the parameters were successfully imported.
+    fn import_yaml(&mut self, input: &str) -> bool {
+        match json::read_config(input) {
+            Err(e) => self.open_error_window(e),
+            Ok(analysis) => {
+                if self.import_analysis(analysis) {
+                    self.watched_config = Some(WatchedConfig::new(input));
+                    return true;
                 }
-            },
+            }
+        }
+
+        false
+    }
+
+    /// Replace the current parameters with the imported analysis.
+    /// Returns `true` if the analysis was successfully converted.
+    fn import_analysis(&mut self, analysis: gorder::input::Analysis) -> bool {
+        match analysis.try_into() {
+            Err(e) => {
+                self.open_error_window(Box::from(e));
+                false
+            }
+            Ok(converted) => {
+                self.imported = Some(converted.clone());
+                self.analysis = converted;
+                true
+            }
         }
     }
 
     /// Convert the GuiAnalysis to gorder analysis structure and run the analysis.
     fn run_analysis(&mut self) {
-        let converted = match gorder::input::Analysis::try_from(&self.analysis) {
+        if !self.analysis.bonds.is_empty() {
+            if let Err(e) = bonds::check_bonds_file(paths::expand_path(&self.analysis.bonds)) {
+                self.open_error_window(Box::from(e));
+                return;
+            }
+        }
+
+        let run_directory = self
+            .analysis
+            .output
+            .timestamped_directory
+            .then(|| paths::run_directory(&chrono::Local::now()));
+        let effective = match &run_directory {
+            Some(directory) => match self.analysis.in_run_directory(directory) {
+                Ok(analysis) => analysis,
+                Err(e) => {
+                    self.open_error_window(Box::from(e));
+                    return;
+                }
+            },
+            None => self.analysis.clone(),
+        };
+
+        let converted = match effective.to_runnables() {
             Err(e) => {
                 self.open_error_window(Box::from(e));
                 return;
@@ -283,7 +494,47 @@ impl GuiOrderApp {
             Ok(x) => x,
         };
 
-        if !self.analysis.other_params.silent {
+        if let Some(directory) = &run_directory {
+            if let Err(e) = std::fs::create_dir_all(directory) {
+                self.open_error_window(Box::from(format!(
+                    "could not create the output directory '{}': {}",
+                    directory, e
+                )));
+                return;
+            }
+        }
+
+        if let Some(warning) = self.analysis.missing_bonds_warning() {
+            self.open_warning_window(warning);
+        }
+
+        let extension_warnings = self.analysis.output_extension_warnings();
+        if !extension_warnings.is_empty() {
+            self.open_warning_window(&extension_warnings.join("\n"));
+        }
+
+        self.last_run = Some(effective);
+        self.log_start = logging::captured_count();
+        Self::start_logging(self.analysis.other_params.silent);
+
+        let is_running = Arc::clone(&self.running);
+        *self.running.lock().unwrap() = true;
+        self.status = RunStatus::Running(std::time::Instant::now());
+
+        let handle = std::thread::spawn(
+            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let result = Self::run_all(converted);
+                *is_running.lock().unwrap() = false;
+                result
+            },
+        );
+
+        *self.thread_handle.lock().unwrap() = Some(handle);
+    }
+
+    /// Set the log level and print the header of the analysis.
+    fn start_logging(silent: bool) {
+        if !silent {
             log::set_max_level(log::LevelFilter::Info);
             let header = format!(">>> GORDER v{} <<<", gorder::GORDER_VERSION).bold();
             println!("\n{}\n", header);
@@ -295,69 +546,153 @@ impl GuiOrderApp {
             "Analysis parameters supplied by {}.",
             format!("guiorder v{}", GUIORDER_VERSION)
         );
+    }
 
-        let is_running = Arc::clone(&self.running);
-        *self.running.lock().unwrap() = true;
+    /// Run the analyses and write their results.
+    /// Analyses for the individual geometry regions are run one after another.
+    fn run_all(
+        analyses: Vec<gorder::input::Analysis>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        for analysis in analyses {
+            analysis.run()?.write()?;
+        }
+        Ok(())
+    }
 
-        let handle = std::thread::spawn(
-            move || -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-                match converted.run() {
-                    Err(e) => {
-                        *is_running.lock().unwrap() = false;
-                        Err(e)
-                    }
-                    Ok(results) => match results.write() {
-                        Err(e) => {
-                            *is_running.lock().unwrap() = false;
-                            Err(Box::from(e))
-                        }
-                        Ok(_) => {
-                            *is_running.lock().unwrap() = false;
-                            Ok(())
-                        }
-                    },
-                }
-            },
-        );
+    /// Get the analysis options of the last analysis, with the output paths actually used.
+    fn last_run(&self) -> &GuiAnalysis {
+        self.last_run.as_ref().unwrap_or(&self.analysis)
+    }
 
-        *self.thread_handle.lock().unwrap() = Some(handle);
+    /// Get the directories into which the results of the analysis were written.
+    fn output_folders(&self) -> Vec<(String, PathBuf)> {
+        let analysis = self.last_run();
+        let output_folder = Self::parent_directory(&analysis.output.output_yaml);
+        let mut folders = vec![(String::from("📂 Open output folder"), output_folder.clone())];
+
+        if let Some(directory) = analysis.ordermaps_params.output_directory() {
+            let ordermaps_folder = PathBuf::from(paths::expand_path(directory));
+            if std::fs::canonicalize(&ordermaps_folder).ok()
+                != std::fs::canonicalize(&output_folder).ok()
+            {
+                folders.push((String::from("📂 Open ordermaps folder"), ordermaps_folder));
+            }
+        }
+
+        folders
+    }
+
+    /// Get the directory containing the file. Returns the current directory for bare filenames.
+    fn parent_directory(file: &str) -> PathBuf {
+        match PathBuf::from(paths::expand_path(file)).parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        }
     }
 
     /// Convert the GuiAnalysis to gorder analysis structure and export it to an output yaml file.
-    fn export_to_yaml(&mut self, output: PathBuf) {
-        let converted = match gorder::input::Analysis::try_from(&self.analysis) {
+    /// Each additional geometry region is exported into a separate file with the suffix `_region{n}`.
+    fn export_to_yaml(&mut self, output: PathBuf, header: YamlHeader) {
+        let exported = match self.export_configs(&output, header) {
+            Ok(files) => files
+                .iter()
+                .map(|path| format!("'{}'", path.display()))
+                .collect::<Vec<_>>(),
             Err(e) => {
-                self.open_error_window(Box::from(e));
+                self.open_error_window(e);
                 return;
             }
-            Ok(x) => x,
         };
 
-        let file = match File::create(&output) {
-            Err(e) => {
-                self.open_error_window(Box::from(e));
-                return;
-            }
-            Ok(x) => x,
-        };
-        let mut writer = BufWriter::new(file);
+        if exported.len() == 1 {
+            self.open_success_window(&format!(
+                "Successfully exported analysis options into a configuration YAML file {}.",
+                exported[0]
+            ));
+        } else {
+            self.open_success_window(&format!(
+                "Successfully exported analysis options into configuration YAML files {} (one file per geometry region).",
+                exported.join(", ")
+            ));
+        }
+    }
 
-        writeln!(
-            writer,
-            "# Analysis options generated by 'guiorder v{}'.",
-            GUIORDER_VERSION
-        )
-        .unwrap();
+    /// Export the analysis options into configuration YAML files starting with `header`,
+    /// one for each geometry region. Returns the paths to the written files.
+    fn export_configs(
+        &mut self,
+        output: &Path,
+        header: YamlHeader,
+    ) -> Result<Vec<PathBuf>, Box<dyn std::error::Error + Send + Sync>> {
+        self.export_configs_with(output, |analysis| {
+            Self::config_yaml_with_header(analysis, header)
+        })
+    }
 
-        match serde_yaml::to_writer(&mut writer, &converted) {
-            Err(e) => {
-                self.open_error_window(Box::from(e));
-            }
-            Ok(_) => self.open_success_window(&format!(
-                "Successfully exported analysis options into a configuration YAML file '{}'.",
-                output.to_str().unwrap()
-            )),
+    /// Export the analysis options serialized using `serialize` into files, one for each geometry region.
+    /// Returns the paths to the written files.
+    fn export_configs_with(
+        &mut self,
+        output: &Path,
+        serialize: impl Fn(&GuiAnalysis) -> Result<String, Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Result<Vec<PathBuf>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut exported = Vec::new();
+        for (i, analysis) in self.analysis.region_analyses().iter().enumerate() {
+            let path = match i {
+                0 => output.to_path_buf(),
+                _ => PathBuf::from(paths::insert_suffix(
+                    &output.to_string_lossy(),
+                    &geometry::region_suffix(i + 1),
+                )),
+            };
+
+            Self::write_config_with(analysis, &path, &serialize)?;
+            self.config_written(&path);
+            exported.push(path);
         }
+
+        Ok(exported)
+    }
+
+    /// Write the analysis options into a configuration YAML file.
+    fn write_config(
+        analysis: &GuiAnalysis,
+        output: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Self::write_config_with(analysis, output, Self::config_yaml)
+    }
+
+    /// Write the analysis options serialized using `serialize` into a file.
+    fn write_config_with(
+        analysis: &GuiAnalysis,
+        output: &Path,
+        serialize: impl Fn(&GuiAnalysis) -> Result<String, Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut writer = BufWriter::new(File::create(output)?);
+        writer.write_all(serialize(analysis)?.as_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Serialize the analysis options into the content of a configuration YAML file.
+    fn config_yaml(
+        analysis: &GuiAnalysis,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Self::config_yaml_with_header(analysis, YamlHeader::Comment)
+    }
+
+    /// Serialize the analysis options into the content of a configuration YAML file starting with `header`.
+    fn config_yaml_with_header(
+        analysis: &GuiAnalysis,
+        header: YamlHeader,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let converted = gorder::input::Analysis::try_from(analysis)?;
+
+        Ok(format!(
+            "{}{}",
+            header.text(&chrono::Local::now()),
+            serde_yaml::to_string(&converted)?
+        ))
     }
 
     /// Display the result of the analysis.
@@ -394,67 +729,78 @@ impl GuiOrderApp {
 impl GuiAnalysis {
     /// Specify optional paths to a bonds file and an NDX file.
     fn specify_advanced_input(&mut self, ui: &mut Ui) {
-        ui.collapsing(
-            RichText::new("Advanced input").font(egui::FontId::monospace(12.0)),
-            |ui| {
-                Self::specify_input_file(
+        let sane = Self::check_bonds_sanity(ui.ctx(), &self.bonds);
+        Self::collapsing_with_warning(ui, "Advanced input", false, sane, |ui| {
+            Self::specify_input_file(
                 &mut self.bonds,
                 ui,
                 "Bonds file:   ",
                 "Path to a file containing information about the bonds of the system. (Optional)",
                 false,
             );
-                Self::specify_input_file(
+            Self::invalid_bonds_marker(ui, &self.bonds);
+            Self::specify_input_file(
                 &mut self.ndx,
                 ui,
                 "NDX file:     ",
                 "Path to an NDX file containing the groups associated with the system. (Optional)",
                 false,
             );
-            },
-        );
+            Self::ndx_groups_summary(ui, &self.ndx);
+        });
     }
 
     /// Specify paths to CSV, Table, and XVG output.
     fn specify_advanced_output(&mut self, ui: &mut Ui) {
-        ui.collapsing(
-            RichText::new("Advanced output").font(egui::FontId::monospace(12.0)),
-            |ui| {
-                Self::specify_output_file(
-                    &mut self.output.output_csv,
-                    ui,
-                    "Output CSV:   ",
-                    "Path to an output CSV file where the results will be saved. (Optional)",
-                    false,
-                );
-                Self::specify_output_file(
-                    &mut self.output.output_tab,
-                    ui,
-                    "Output Table: ",
-                    "Path to an output \"table\" file where the results will be saved. (Optional)",
-                    false,
-                );
-                Self::specify_output_file(
+        Self::collapsing_with_warning(ui, "Advanced output", false, true, |ui| {
+            Self::specify_output_file(
+                &mut self.output.output_csv,
+                ui,
+                "Output CSV:   ",
+                "Path to an output CSV file where the results will be saved. (Optional)",
+                false,
+            );
+            Self::missing_extension_note(
+                ui,
+                &mut self.output.output_csv,
+                &extensions::CSV_EXTENSIONS,
+            );
+            Self::specify_output_file(
+                &mut self.output.output_tab,
+                ui,
+                "Output Table: ",
+                "Path to an output \"table\" file where the results will be saved. (Optional)",
+                false,
+            );
+            Self::missing_extension_note(
+                ui,
+                &mut self.output.output_tab,
+                &extensions::TAB_EXTENSIONS,
+            );
+            Self::specify_output_file(
                 &mut self.output.output_xvg,
                 ui,
                 "Output XVG:   ",
                 "Filename pattern for output XVG files where the results will be saved. (Optional)",
                 false,
             );
-            },
-        );
+            Self::xvg_preview_label(ui, &self.output.output_xvg);
+            self.suggest_tables_button(ui);
+
+            ui.horizontal(|ui| {
+                Self::label_with_hint(
+                    ui,
+                    "Timestamped directory: ",
+                    "Check the box to place all output files and directories of each run into a new directory 'results/<date>_<time>'. Only the names of the specified outputs are used.",
+                );
+                ui.checkbox(&mut self.output.timestamped_directory, "");
+            });
+        });
     }
 
     /// Check that all options required for the analysis have been provided.
-    fn check_sanity(&self) -> bool {
-        self.check_leaflets_sanity()
-            && self.check_analysis_params_sanity()
-            && !self.structure.is_empty()
-            && !self.trajectory.iter().any(|file| file.is_empty())
-            && !self.output.output_yaml.is_empty()
-            && self.check_membrane_normal_sanity()
-            && self.check_ordermaps_sanity()
-            && self.check_geometry_sanity()
+    fn check_sanity(&self, ctx: &egui::Context) -> bool {
+        self.first_invalid_section(ctx).is_none()
     }
 }
 
@@ -673,12 +1019,50 @@ mod tests {
 
         let mut app = GuiOrderApp::default();
         app.import_yaml("tests/parameters.yaml");
-        app.export_to_yaml(path_to_output.clone());
+        app.export_to_yaml(path_to_output.clone(), YamlHeader::Comment);
 
+        // the version of guiorder in the header differs
         assert!(diff_files_ignore_first(
             path_to_output.to_str().unwrap(),
             "tests/exported.yaml",
             1
         ));
     }
+
+    #[test]
+    fn export_without_header() {
+        let output = NamedTempFile::new().unwrap();
+
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+        app.export_to_yaml(output.path().to_path_buf(), YamlHeader::None);
+
+        let exported = read_file_without_first_lines(output.path().to_str().unwrap(), 0);
+        let expected = read_file_without_first_lines("tests/exported.yaml", 1);
+        assert_eq!(exported, expected);
+    }
+
+    #[test]
+    fn export_with_full_header() {
+        let output = NamedTempFile::new().unwrap();
+
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+        app.export_to_yaml(output.path().to_path_buf(), YamlHeader::Full);
+
+        let exported = read_file_without_first_lines(output.path().to_str().unwrap(), 0);
+        assert!(exported[..3].iter().all(|line| line.starts_with('#')));
+        assert!(exported[1].contains(gorder::GORDER_VERSION));
+        assert_eq!(
+            exported[3..],
+            read_file_without_first_lines("tests/exported.yaml", 1)
+        );
+
+        let mut imported = GuiOrderApp::default();
+        assert!(imported.import_yaml(output.path().to_str().unwrap()));
+        assert_eq!(
+            format!("{:?}", imported.analysis),
+            format!("{:?}", app.analysis)
+        );
+    }
 }
@@ -6,10 +6,17 @@
 use eframe::egui::{self, RichText, Ui};
 use gorder::input::{Axis, DynamicNormal};
 
-use crate::{common::MembraneNormal, error::ConversionError, GuiAnalysis};
+use crate::{
+    accessibility,
+    common::MembraneNormal,
+    error::ConversionError,
+    selection::is_blank,
+    units::{length_drag_value, valid_radius, LengthUnit},
+    GuiAnalysis,
+};
 
 /// Parameters for dynamic membrane normal calculations.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct DynamicNormalParams {
     heads: String,
     radius: f32,
@@ -32,7 +39,7 @@ impl TryFrom<gorder::input::MembraneNormal> for DynamicNormalParams {
                 heads: dynamic.heads().clone(),
                 radius: dynamic.radius(),
             }),
-            gorder::input::MembraneNormal::FromMap(_) => Err(ConversionError::FromMapNormals),
+            gorder::input::MembraneNormal::FromMap(_) => Ok(Self::default()),
             gorder::input::MembraneNormal::Static(_) => Ok(Self::default()),
             gorder::input::MembraneNormal::FromFile(_) => Ok(Self::default()),
         }
@@ -50,6 +57,12 @@ impl TryFrom<&GuiAnalysis> for gorder::input::MembraneNormal {
             MembraneNormal::FromFile => Ok(gorder::input::MembraneNormal::FromFile(
                 value.from_file_normals.clone(),
             )),
+            MembraneNormal::Dynamic if !valid_radius(value.dynamic_normal_params.radius) => {
+                Err(ConversionError::InvalidMembraneNormal(format!(
+                    "radius for the dynamic membrane normal must be positive, not '{}'",
+                    value.dynamic_normal_params.radius
+                )))
+            }
             MembraneNormal::Dynamic => Ok(gorder::input::MembraneNormal::Dynamic(
                 DynamicNormal::new(
                     &value.dynamic_normal_params.heads,
@@ -57,11 +70,33 @@ impl TryFrom<&GuiAnalysis> for gorder::input::MembraneNormal {
                 )
                 .map_err(|e| ConversionError::InvalidMembraneNormal(e.to_string()))?,
             )),
+            MembraneNormal::FromMap => value
+                .from_map_normals
+                .clone()
+                .ok_or(ConversionError::FromMapNormals),
         }
     }
 }
 
 impl GuiAnalysis {
+    /// Describe the membrane normal for the summary of the analysis.
+    /// Lengths are written in the provided unit.
+    pub(crate) fn membrane_normal_summary(&self, unit: LengthUnit) -> String {
+        match self.membrane_normal {
+            MembraneNormal::X => String::from("x"),
+            MembraneNormal::Y => String::from("y"),
+            MembraneNormal::Z => String::from("z"),
+            MembraneNormal::Dynamic => format!(
+                "dynamic (heads '{}', radius {}{})",
+                self.dynamic_normal_params.heads,
+                unit.from_nm(self.dynamic_normal_params.radius) as f32,
+                unit.suffix()
+            ),
+            MembraneNormal::FromFile => format!("from file '{}'", self.from_file_normals),
+            MembraneNormal::FromMap => String::from("inline"),
+        }
+    }
+
     /// Specify the global membrane normal or parameters for its calculation.
     pub(super) fn specify_membrane_normal(&mut self, ui: &mut Ui) {
         Self::collapsing_with_warning(
@@ -70,6 +105,7 @@ impl GuiAnalysis {
             false,
             self.check_membrane_normal_sanity(),
             |ui| {
+                let previous = self.membrane_normal;
                 ui.horizontal(|ui| {
                     Self::label_with_hint(
                         ui,
@@ -90,11 +126,21 @@ impl GuiAnalysis {
                         MembraneNormal::FromFile,
                         "from file",
                     );
+
+                    // inline normals can only be imported, not created
+                    if self.from_map_normals.is_some() {
+                        ui.radio_value(
+                            &mut self.membrane_normal,
+                            MembraneNormal::FromMap,
+                            "inline",
+                        );
+                    }
                 });
+                self.guard_normal_switch(ui.ctx(), previous);
 
                 if self.membrane_normal == MembraneNormal::Dynamic {
                     ui.vertical(|ui| {
-                        Self::specify_string(
+                        Self::specify_head_selection(
                             &mut self.dynamic_normal_params.heads,
                             ui,
                             "Lipid heads: ",
@@ -109,21 +155,11 @@ impl GuiAnalysis {
                                 "Radius of the scanning sphere for identification of nearby lipid heads."
                             );
 
-                            ui.add(
-                                egui::DragValue::new(
-                                    &mut self.dynamic_normal_params.radius,
-                                )
-                                .speed(0.025)
-                                .range(0.0..=f32::MAX)
-                                .suffix(" nm"),
-                            )
+                            length_drag_value(ui, &mut self.dynamic_normal_params.radius, 0.025, 0.0..=f32::MAX)
                             .labelled_by(label.id);
 
-                            if self.dynamic_normal_params.radius == 0.0 {
-                                ui.label(
-                                    RichText::new("❗")
-                                        .color(egui::Color32::from_rgba_premultiplied(150, 0, 0, 100)),
-                                );
+                            if !valid_radius(self.dynamic_normal_params.radius) {
+                                accessibility::invalid_marker(ui, "invalid radius");
                             }
                         });
                     });
@@ -135,6 +171,12 @@ impl GuiAnalysis {
                         "Path to a file specifying the membrane normals to use for individual lipid molecules.",
                         true
                     );
+                } else if self.membrane_normal == MembraneNormal::FromMap {
+                    ui.label(
+                        RichText::new("Membrane normals are provided inline in the imported configuration.\nThey cannot be edited in guiorder but will be exported unchanged.")
+                            .font(egui::FontId::monospace(12.0))
+                            .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                    );
                 }
             },
         );
@@ -144,10 +186,11 @@ impl GuiAnalysis {
     pub(super) fn check_membrane_normal_sanity(&self) -> bool {
         match self.membrane_normal {
             MembraneNormal::Dynamic => {
-                !self.dynamic_normal_params.heads.is_empty()
-                    && self.dynamic_normal_params.radius > 0.0
+                !is_blank(&self.dynamic_normal_params.heads)
+                    && valid_radius(self.dynamic_normal_params.radius)
             }
             MembraneNormal::FromFile => !self.from_file_normals.is_empty(),
+            MembraneNormal::FromMap => self.from_map_normals.is_some(),
             _ => true,
         }
     }
@@ -236,6 +279,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn membrane_normal_summary_in_selected_unit() {
+        let params = GuiAnalysis {
+            membrane_normal: MembraneNormal::Dynamic,
+            dynamic_normal_params: DynamicNormalParams {
+                heads: String::from("name P"),
+                radius: 1.75,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            params.membrane_normal_summary(LengthUnit::Nanometer),
+            "dynamic (heads 'name P', radius 1.75 nm)"
+        );
+        assert_eq!(
+            params.membrane_normal_summary(LengthUnit::Angstrom),
+            "dynamic (heads 'name P', radius 17.5 Å)"
+        );
+    }
+
+    #[test]
+    fn guiorder_to_gorder_membrane_normal_dynamic_invalid_radius() {
+        for radius in [0.0, -1.0, f32::NAN] {
+            let params = GuiAnalysis {
+                membrane_normal: MembraneNormal::Dynamic,
+                dynamic_normal_params: DynamicNormalParams {
+                    heads: String::from("name P"),
+                    radius,
+                },
+                ..Default::default()
+            };
+
+            assert!(!params.check_membrane_normal_sanity());
+            assert!(matches!(
+                gorder::input::MembraneNormal::try_from(&params),
+                Err(ConversionError::InvalidMembraneNormal(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn import_zero_dynamic_normal_radius() {
+        let config = "structure: system.tpr\ntrajectory: md.xtc\ntype: !AAOrder\n  heavy_atoms: '@membrane and element name carbon'\n  hydrogens: '@membrane and element name hydrogen'\noutput: order.yaml\nmembrane_normal: !Dynamic\n  heads: name P\n  radius: 0.0\n";
+
+        // parsing does not validate the radius, the converted analysis must be rejected
+        let analysis = crate::json::read_config_from(config.as_bytes()).unwrap();
+        let imported = GuiAnalysis::try_from(analysis).unwrap();
+        assert!(!imported.check_membrane_normal_sanity());
+        assert!(matches!(
+            gorder::input::MembraneNormal::try_from(&imported),
+            Err(ConversionError::InvalidMembraneNormal(_))
+        ));
+    }
+
     #[test]
     fn guiorder_to_gorder_membrane_normal_from_file() {
         let params = GuiAnalysis {
@@ -251,4 +349,60 @@ mod tests {
             _ => panic!("Invalid membrane normal."),
         }
     }
+
+    #[test]
+    fn guiorder_to_gorder_membrane_normal_from_map() {
+        let mut normals = std::collections::HashMap::new();
+        normals.insert(
+            String::from("POPC"),
+            vec![vec![
+                gorder::prelude::Vector3D::new(0.0, 0.0, 1.0),
+                gorder::prelude::Vector3D::new(0.0, 0.0, -1.0),
+            ]],
+        );
+        let normals = gorder::input::MembraneNormal::FromMap(normals);
+        let params = GuiAnalysis {
+            membrane_normal: MembraneNormal::FromMap,
+            from_map_normals: Some(normals.clone()),
+            ..Default::default()
+        };
+
+        assert!(params.check_membrane_normal_sanity());
+        let converted = gorder::input::MembraneNormal::try_from(&params).unwrap();
+        assert!(matches!(
+            converted,
+            gorder::input::MembraneNormal::FromMap(_)
+        ));
+        assert_eq!(
+            serde_yaml::to_string(&converted).unwrap(),
+            serde_yaml::to_string(&normals).unwrap()
+        );
+    }
+
+    #[test]
+    fn guiorder_to_gorder_membrane_normal_from_map_missing() {
+        let params = GuiAnalysis {
+            membrane_normal: MembraneNormal::FromMap,
+            ..Default::default()
+        };
+
+        assert!(!params.check_membrane_normal_sanity());
+        assert!(matches!(
+            gorder::input::MembraneNormal::try_from(&params),
+            Err(ConversionError::FromMapNormals)
+        ));
+    }
+
+    #[test]
+    fn dynamic_normal_blank_heads() {
+        let params = GuiAnalysis {
+            membrane_normal: MembraneNormal::Dynamic,
+            dynamic_normal_params: DynamicNormalParams {
+                heads: String::from("   "),
+                radius: 2.0,
+            },
+            ..Default::default()
+        };
+        assert!(!params.check_membrane_normal_sanity());
+    }
 }
@@ -0,0 +1,149 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Export of minimal configuration files containing only the options that differ from the defaults.
+
+use std::path::Path;
+
+use eframe::egui::{self, Ui};
+use gorder::input::Analysis;
+use serde_yaml::Value;
+
+use crate::{dialogs, GuiAnalysis, GuiOrderApp, GUIORDER_VERSION};
+
+/// Options that are always written, even if they match the default values.
+const REQUIRED: [&str; 4] = ["structure", "trajectory", "analysis_type", "output_yaml"];
+
+/// Construct an analysis using only the required options of the provided analysis.
+fn default_analysis(
+    analysis: &GuiAnalysis,
+) -> Result<Analysis, Box<dyn std::error::Error + Send + Sync>> {
+    let mut builder = Analysis::builder();
+    builder
+        .structure(&analysis.structure)
+        .trajectory(analysis.trajectory.clone())
+        .output_yaml(&analysis.output.output_yaml)
+        .analysis_type(gorder::input::AnalysisType::from(analysis));
+
+    Ok(builder.build()?)
+}
+
+/// Remove all options that have the same values as in the default analysis.
+fn strip_defaults(full: Value, default: &Value) -> Value {
+    let Value::Mapping(mapping) = full else {
+        return full;
+    };
+
+    Value::Mapping(
+        mapping
+            .into_iter()
+            .filter(|(key, value)| {
+                let required = key.as_str().is_some_and(|key| REQUIRED.contains(&key));
+                required || (!value.is_null() && default.get(key) != Some(value))
+            })
+            .collect(),
+    )
+}
+
+/// Serialize the analysis options into a configuration YAML file containing only the non-default options.
+pub(crate) fn minimal_yaml(
+    analysis: &GuiAnalysis,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let converted = Analysis::try_from(analysis)?;
+    let default = serde_yaml::to_value(default_analysis(analysis)?)?;
+    let minimal = strip_defaults(serde_yaml::to_value(&converted)?, &default);
+
+    Ok(format!(
+        "# Minimal analysis options generated by 'guiorder v{}'.\n# Options not listed use the default values.\n{}",
+        GUIORDER_VERSION,
+        serde_yaml::to_string(&minimal)?
+    ))
+}
+
+impl GuiOrderApp {
+    /// Export the non-default analysis options into configuration YAML files.
+    fn export_minimal(&mut self, output: &Path) {
+        match self.export_configs_with(output, minimal_yaml) {
+            Ok(files) => self.open_success_window(&format!(
+                "Successfully exported minimal analysis options into {}.",
+                files
+                    .iter()
+                    .map(|path| format!("'{}'", path.display()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            Err(e) => self.open_error_window(e),
+        }
+    }
+
+    /// Button for exporting the minimal configuration.
+    pub(super) fn export_minimal_button(&mut self, ui: &mut Ui, sane: bool) {
+        if ui
+            .add_enabled(sane, egui::Button::new("🗜 Export minimal"))
+            .on_hover_ui(|ui| {
+                ui.label(
+                    "Export only the options that differ from the default values into a YAML file.",
+                );
+            })
+            .on_disabled_hover_ui(|ui| {
+                ui.label("Cannot export analysis options because some are missing.");
+            })
+            .clicked()
+        {
+            ui.close_menu();
+            dialogs::open_save_file(ui.ctx(), dialogs::id("export_minimal"));
+        }
+    }
+
+    /// Export the file once it has been specified in the dialog opened by the button.
+    pub(super) fn finish_minimal_export(&mut self, ctx: &egui::Context) {
+        if let Some(path) = dialogs::take_file(ctx, dialogs::id("export_minimal")) {
+            self.export_minimal(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_round_trip() {
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+
+        let minimal = minimal_yaml(&app.analysis).unwrap();
+        assert!(minimal.contains("structure:"));
+        assert!(minimal.contains("analysis_type:"));
+        assert!(!minimal.contains("bonds:"));
+
+        let output = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(output.path(), &minimal).unwrap();
+
+        let mut imported = GuiOrderApp::default();
+        imported.import_yaml(&output.path().display().to_string());
+        assert_eq!(
+            GuiOrderApp::config_yaml(&imported.analysis).unwrap(),
+            GuiOrderApp::config_yaml(&app.analysis).unwrap()
+        );
+    }
+
+    #[test]
+    fn minimal_omits_defaults() {
+        let analysis = GuiAnalysis {
+            structure: String::from("system.tpr"),
+            trajectory: vec![String::from("md.xtc")],
+            output: crate::OutputFiles {
+                output_yaml: String::from("order.yaml"),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let minimal = minimal_yaml(&analysis).unwrap();
+        assert!(!minimal.contains("step:"));
+        assert!(!minimal.contains("begin:"));
+        assert!(!minimal.contains("handle_pbc:"));
+        assert!(minimal.contains("output_yaml: order.yaml"));
+    }
+}
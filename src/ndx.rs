@@ -0,0 +1,226 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Reading group names from NDX files.
+
+use std::{path::Path, time::SystemTime};
+
+use eframe::egui::{self, Id, RichText, Ui};
+
+use crate::{paths, selection, GuiAnalysis};
+
+/// Number of seconds after which the modification time of an NDX file is checked again.
+const NDX_CHECK_INTERVAL: f64 = 2.0;
+
+/// Group names read from an NDX file.
+#[derive(Debug, Clone)]
+struct CachedGroups {
+    modified: Option<SystemTime>,
+    checked_at: f64,
+    groups: Option<Vec<String>>,
+}
+
+/// Get the names of all groups in the content of an NDX file, in the order of appearance.
+pub(crate) fn parse_group_names(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            line.trim()
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .map(|name| name.trim().to_owned())
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Get the names of all groups in the NDX file.
+/// The result is cached for each path and only read again if the file is modified.
+/// Returns `None` if the file could not be read or contains no groups.
+pub(crate) fn group_names(ctx: &egui::Context, path: &str) -> Option<Vec<String>> {
+    if path.is_empty() {
+        return None;
+    }
+
+    let id = Id::new(("guiorder_ndx_groups", path));
+    let now = ctx.input(|i| i.time);
+    let cached = ctx.data_mut(|d| d.get_temp::<CachedGroups>(id));
+
+    if let Some(cached) = &cached {
+        if now - cached.checked_at < NDX_CHECK_INTERVAL {
+            return cached.groups.clone();
+        }
+    }
+
+    let expanded = paths::expand_path(path);
+    let modified = std::fs::metadata(&expanded).and_then(|m| m.modified()).ok();
+
+    let groups = match cached {
+        Some(cached) if cached.modified.is_some() && cached.modified == modified => cached.groups,
+        _ => read_group_names(&expanded),
+    };
+
+    ctx.data_mut(|d| {
+        d.insert_temp(
+            id,
+            CachedGroups {
+                modified,
+                checked_at: now,
+                groups: groups.clone(),
+            },
+        )
+    });
+
+    groups
+}
+
+/// Remember the NDX file specified in the advanced input so its groups can be offered in selection fields.
+pub(crate) fn set_global_ndx(ctx: &egui::Context, path: &str) {
+    ctx.data_mut(|d| d.insert_temp(Id::new("guiorder_global_ndx"), path.to_owned()));
+}
+
+/// Get the names of all groups in the NDX file specified in the advanced input.
+pub(crate) fn global_group_names(ctx: &egui::Context) -> Option<Vec<String>> {
+    let path = ctx.data_mut(|d| d.get_temp::<String>(Id::new("guiorder_global_ndx")))?;
+    group_names(ctx, &path)
+}
+
+/// Format the group name as a groan selection query.
+pub(crate) fn group_query(name: &str) -> String {
+    if name.contains(char::is_whitespace) {
+        format!("@'{}'", name)
+    } else {
+        format!("@{}", name)
+    }
+}
+
+/// Read the group names from the NDX file.
+fn read_group_names(path: impl AsRef<Path>) -> Option<Vec<String>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let groups = parse_group_names(&content);
+    (!groups.is_empty()).then_some(groups)
+}
+
+impl GuiAnalysis {
+    /// Show the number of groups in the NDX file with the names of the groups on hover.
+    pub(crate) fn ndx_groups_summary(ui: &mut Ui, ndx: &str) {
+        let Some(groups) = group_names(ui.ctx(), ndx) else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.add_space(104.0);
+            ui.label(
+                RichText::new(format!("({} groups available)", groups.len()))
+                    .font(egui::FontId::monospace(12.0))
+                    .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+            )
+            .on_hover_ui(|ui| {
+                ui.label("Groups can be used in selections as '@group':");
+                for group in &groups {
+                    ui.label(RichText::new(group).font(egui::FontId::monospace(12.0)));
+                }
+            });
+        });
+    }
+
+    /// Create a menu for inserting a group from the global NDX file into the selection.
+    /// The group is joined with the existing query using the selected operator.
+    pub(crate) fn insert_group_menu(ui: &mut Ui, target: &mut String) {
+        let Some(groups) = global_group_names(ui.ctx()) else {
+            return;
+        };
+
+        ui.menu_button("@", |ui| {
+            if !selection::is_blank(target) {
+                selection::operator_selector(ui);
+                ui.separator();
+            }
+
+            let operator = selection::query_operator(ui.ctx());
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for group in &groups {
+                        if ui.button(group).clicked() {
+                            selection::append_to_query(target, &group_query(group), operator);
+                            ui.close_menu();
+                        }
+                    }
+                });
+        })
+        .response
+        .on_hover_ui(|ui| {
+            ui.label("Insert a group from the NDX file.");
+        });
+    }
+
+    /// Select a group from the NDX file using a combo box.
+    /// Falls back to a text field if the NDX file is not available.
+    pub(crate) fn specify_ndx_group(
+        target: &mut String,
+        ui: &mut Ui,
+        label: &str,
+        hint: &str,
+        required: bool,
+        ndx: &str,
+    ) {
+        let Some(groups) = group_names(ui.ctx(), ndx) else {
+            Self::specify_string(target, ui, label, hint, required);
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            Self::label_with_hint(ui, label, hint);
+
+            egui::ComboBox::from_id_salt(("guiorder_ndx_group", label))
+                .selected_text(target.as_str())
+                .show_ui(ui, |ui| {
+                    for group in groups {
+                        ui.selectable_value(target, group.clone(), group);
+                    }
+                });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_groups() {
+        let content = "[ System ]\n1 2 3\n4 5\n[Upper]\n1 2\n\n  [  Lower leaflet  ]  \n3\n[ ]\n";
+        assert_eq!(
+            parse_group_names(content),
+            vec![
+                String::from("System"),
+                String::from("Upper"),
+                String::from("Lower leaflet")
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_groups_empty() {
+        assert!(parse_group_names("1 2 3\n").is_empty());
+    }
+
+    #[test]
+    fn read_groups_from_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, b"[ Upper ]\n1 2\n[ Lower ]\n3 4\n").unwrap();
+
+        assert_eq!(
+            read_group_names(file.path()).unwrap(),
+            vec![String::from("Upper"), String::from("Lower")]
+        );
+        assert!(read_group_names("nonexistent.ndx").is_none());
+    }
+
+    #[test]
+    fn group_queries() {
+        assert_eq!(group_query("Upper"), "@Upper");
+        assert_eq!(group_query("Lower leaflet"), "@'Lower leaflet'");
+    }
+}
@@ -3,9 +3,20 @@
 
 //! Parameters for constructing ordermaps.
 
+use std::path::PathBuf;
+
 use eframe::egui::{self, RichText, Ui};
 
-use crate::{common::MembraneNormal, error::ConversionError, GuiAnalysis};
+use crate::{
+    accessibility,
+    common::MembraneNormal,
+    error::ConversionError,
+    focus,
+    geometry::span_valid,
+    paths, structure,
+    units::{self, length_drag_value, LengthUnit},
+    GuiAnalysis, LeafletClassification,
+};
 
 /// How are ordermap dimensions set?
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -34,8 +45,56 @@ enum Plane {
     XZ,
 }
 
+impl Plane {
+    /// Get the plane in which the ordermaps are constructed for a membrane normal.
+    fn from_normal(normal: MembraneNormal) -> Self {
+        match normal {
+            MembraneNormal::X => Plane::YZ,
+            MembraneNormal::Y => Plane::XZ,
+            MembraneNormal::Z => Plane::XY,
+            MembraneNormal::Dynamic | MembraneNormal::FromFile | MembraneNormal::FromMap => {
+                Plane::Unknown
+            }
+        }
+    }
+
+    /// Get the indices of the physical axes (x = 0, y = 1, z = 2) corresponding to the two dimensions of the map.
+    fn axes(self) -> [usize; 2] {
+        match self {
+            Plane::XY | Plane::Unknown => [0, 1],
+            Plane::YZ => [2, 1],
+            Plane::XZ => [0, 2],
+        }
+    }
+
+    /// Get the labels of the two dimensions of the map.
+    fn labels(self) -> (&'static str, &'static str) {
+        match self {
+            Plane::XY => ("X-dimension", "Y-dimension"),
+            Plane::YZ => ("Z-dimension", "Y-dimension"),
+            Plane::XZ => ("X-dimension", "Z-dimension"),
+            Plane::Unknown => ("Unknown dimension", "Unknown dimension"),
+        }
+    }
+}
+
+/// Number of bins in the ordermap above which a warning is shown.
+const LARGE_GRID_THRESHOLD: usize = 1_000_000;
+
+/// Approximate number of bytes written for a single bin of an ordermap.
+const BYTES_PER_BIN: u64 = 20;
+
+/// Approximate number of bytes of the header of an ordermap file.
+const HEADER_BYTES: u64 = 400;
+
+/// Estimated size of all ordermap files (in bytes) above which a warning is shown.
+const LARGE_OUTPUT_THRESHOLD: u64 = 1 << 30;
+
+/// Number of bins along a single manual dimension above which the bin size is considered unreasonably small.
+const MAX_BINS_PER_DIMENSION: f32 = 100_000.0;
+
 /// Parameters for the manual dimensions specification.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 struct ManualDimensions {
     start: f32,
     end: f32,
@@ -60,15 +119,17 @@ impl Default for ManualDimensions {
 }
 
 /// Parameters for the construction of ordermaps.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct OrderMapsParams {
     calculate_maps: bool,
     output_directory: String,
     plane: Option<Plane>,
     bin_size: [f32; 2],
-    dimensions: [OrderMapDimension; 2],
-    x_manual: ManualDimensions,
-    y_manual: ManualDimensions,
+    /// Way of setting the dimensions for each physical axis (x, y, z).
+    dimensions: [OrderMapDimension; 3],
+    /// Manual dimensions for each physical axis (x, y, z).
+    /// Tracked per axis so that they are preserved when the plane changes.
+    manual: [ManualDimensions; 3],
     min_samples: usize,
 }
 
@@ -79,70 +140,77 @@ impl Default for OrderMapsParams {
             output_directory: String::new(),
             plane: None,
             bin_size: [0.1, 0.1],
-            dimensions: [OrderMapDimension::default(), OrderMapDimension::default()],
-            x_manual: ManualDimensions::default(),
-            y_manual: ManualDimensions::default(),
+            dimensions: [OrderMapDimension::default(); 3],
+            manual: [
+                ManualDimensions::default(),
+                ManualDimensions::default(),
+                ManualDimensions::default(),
+            ],
             min_samples: 1,
         }
     }
 }
 
-impl From<Option<gorder::input::OrderMap>> for OrderMapsParams {
-    fn from(value: Option<gorder::input::OrderMap>) -> Self {
-        match value {
-            None => Self {
+impl From<&gorder::input::Analysis> for OrderMapsParams {
+    fn from(value: &gorder::input::Analysis) -> Self {
+        let Some(map) = value.map() else {
+            return Self {
                 calculate_maps: false,
                 ..Default::default()
-            },
-            Some(map) => Self {
-                calculate_maps: true,
-                output_directory: map.output_directory().clone().unwrap_or(String::new()),
-                plane: match map.plane() {
-                    None => None,
-                    Some(gorder::input::Plane::XY) => Some(Plane::XY),
-                    Some(gorder::input::Plane::XZ) => Some(Plane::XZ),
-                    Some(gorder::input::Plane::YZ) => Some(Plane::YZ),
-                },
-                bin_size: map.bin_size(),
-                dimensions: [map.dim()[0].into(), map.dim()[1].into()],
-                x_manual: map.dim()[0].into(),
-                y_manual: map.dim()[1].into(),
-                min_samples: map.min_samples(),
-            },
+            };
+        };
+
+        let plane = map.plane().as_ref().map(|plane| match plane {
+            gorder::input::Plane::XY => Plane::XY,
+            gorder::input::Plane::XZ => Plane::XZ,
+            gorder::input::Plane::YZ => Plane::YZ,
+        });
+
+        let active = plane.unwrap_or_else(|| {
+            MembraneNormal::try_from(value.membrane_normal().clone())
+                .map(Plane::from_normal)
+                .unwrap_or(Plane::Unknown)
+        });
+
+        let mut params = Self {
+            calculate_maps: true,
+            output_directory: map.output_directory().clone().unwrap_or(String::new()),
+            plane,
+            bin_size: map.bin_size(),
+            min_samples: map.min_samples(),
+            ..Default::default()
+        };
+
+        for (axis, span) in active.axes().into_iter().zip(map.dim()) {
+            params.dimensions[axis] = span.into();
+            params.manual[axis] = span.into();
         }
+
+        params
     }
 }
 
-impl TryFrom<&OrderMapsParams> for Option<gorder::input::OrderMap> {
+impl TryFrom<&GuiAnalysis> for Option<gorder::input::OrderMap> {
     type Error = ConversionError;
-    fn try_from(value: &OrderMapsParams) -> Result<Self, Self::Error> {
-        if !value.calculate_maps {
+    fn try_from(value: &GuiAnalysis) -> Result<Self, Self::Error> {
+        let params = &value.ordermaps_params;
+        if !params.calculate_maps {
             return Ok(None);
         }
 
-        let dimension_x = match value.dimensions[0] {
-            OrderMapDimension::Auto => gorder::input::GridSpan::Auto,
-            OrderMapDimension::Manual => {
-                gorder::input::GridSpan::manual(value.x_manual.start, value.x_manual.end).unwrap()
-            }
-        };
-
-        let dimension_y = match value.dimensions[1] {
-            OrderMapDimension::Auto => gorder::input::GridSpan::Auto,
-            OrderMapDimension::Manual => {
-                gorder::input::GridSpan::manual(value.y_manual.start, value.y_manual.end).unwrap()
-            }
-        };
+        let [axis_1, axis_2] = params.active_plane(value.membrane_normal).axes();
+        let dimension_x = params.grid_span(axis_1)?;
+        let dimension_y = params.grid_span(axis_2)?;
 
         let mut builder = gorder::input::OrderMap::builder();
 
         builder
-            .output_directory(&value.output_directory)
-            .bin_size(value.bin_size)
+            .output_directory(&params.output_directory)
+            .bin_size(params.bin_size)
             .dim([dimension_x, dimension_y])
-            .min_samples(value.min_samples);
+            .min_samples(params.min_samples);
 
-        if let Some(plane) = value.plane {
+        if let Some(plane) = params.plane {
             let converted_plane = match plane {
                 Plane::XY => gorder::input::Plane::XY,
                 Plane::XZ => gorder::input::Plane::XZ,
@@ -165,7 +233,201 @@ impl TryFrom<&OrderMapsParams> for Option<gorder::input::OrderMap> {
     }
 }
 
+impl OrderMapsParams {
+    /// Get the plane in which the ordermaps are constructed.
+    /// If the plane is not set explicitly, it is derived from the membrane normal.
+    fn active_plane(&self, normal: MembraneNormal) -> Plane {
+        self.plane.unwrap_or_else(|| Plane::from_normal(normal))
+    }
+
+    /// Get the grid span along the physical axis.
+    fn grid_span(&self, axis: usize) -> Result<gorder::input::GridSpan, ConversionError> {
+        match self.dimensions[axis] {
+            OrderMapDimension::Auto => Ok(gorder::input::GridSpan::Auto),
+            OrderMapDimension::Manual => {
+                gorder::input::GridSpan::manual(self.manual[axis].start, self.manual[axis].end)
+                    .map_err(|e| ConversionError::InvalidOrderMapParams(e.to_string()))
+            }
+        }
+    }
+
+    /// Get the number of bins along the two dimensions of the map.
+    /// Automatic dimensions are taken from the simulation box, if available.
+    /// Returns `None` for dimensions whose size is not known.
+    fn bin_counts(&self, plane: Plane, box_size: Option<[f32; 3]>) -> [Option<usize>; 2] {
+        let [axis_1, axis_2] = plane.axes();
+        [(axis_1, self.bin_size[0]), (axis_2, self.bin_size[1])].map(|(axis, bin_size)| {
+            let span = match self.dimensions[axis] {
+                OrderMapDimension::Auto => box_size.map(|size| size[axis]),
+                OrderMapDimension::Manual => Some(self.manual[axis].end - self.manual[axis].start),
+            };
+            bin_count(span?, bin_size)
+        })
+    }
+
+    /// Check that the bin sizes are reasonable for the manually specified dimensions in the plane.
+    /// Returns a description of the problem, if there is one. Automatic dimensions are not checked.
+    fn bin_size_problem(&self, plane: Plane, labels: (&str, &str)) -> Option<String> {
+        let [axis_1, axis_2] = plane.axes();
+        [
+            (axis_1, self.bin_size[0], labels.0),
+            (axis_2, self.bin_size[1], labels.1),
+        ]
+        .into_iter()
+        .find_map(|(axis, bin_size, label)| {
+            let manual = &self.manual[axis];
+            if self.dimensions[axis] == OrderMapDimension::Auto
+                || bin_size <= 0.0
+                || !span_valid(manual.start, manual.end)
+            {
+                return None;
+            }
+
+            let span = manual.end - manual.start;
+            if span < bin_size {
+                Some(format!(
+                    "Bin size is larger than the span of the {}.",
+                    label.to_lowercase()
+                ))
+            } else if span / bin_size > MAX_BINS_PER_DIMENSION {
+                Some(format!(
+                    "Bin size is too small for the span of the {}.",
+                    label.to_lowercase()
+                ))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Check that the manually specified dimensions in the plane have valid spans.
+    fn dimensions_valid(&self, plane: Plane) -> bool {
+        plane.axes().into_iter().all(|axis| {
+            self.dimensions[axis] == OrderMapDimension::Auto
+                || span_valid(self.manual[axis].start, self.manual[axis].end)
+        })
+    }
+
+    /// Get the directory into which the ordermaps will be written, if ordermaps are calculated.
+    pub(crate) fn output_directory(&self) -> Option<&str> {
+        if self.calculate_maps && !self.output_directory.is_empty() {
+            Some(&self.output_directory)
+        } else {
+            None
+        }
+    }
+
+    /// Get mutable access to the output directory for the ordermaps.
+    pub(crate) fn output_directory_mut(&mut self) -> &mut String {
+        &mut self.output_directory
+    }
+}
+
+/// Get the number of bins of the given size needed to cover the span.
+/// Returns `None` if the number can not be calculated.
+fn bin_count(span: f32, bin_size: f32) -> Option<usize> {
+    if !span.is_finite() || span < 0.0 || bin_size <= 0.0 {
+        return None;
+    }
+
+    Some(((span / bin_size) as f64 - 1e-6).ceil().max(1.0) as usize)
+}
+
+/// Estimate the number of ordermap files and their total size in bytes.
+/// With leaflet classification, every map is written for the full membrane and for each leaflet.
+fn estimate_output_size(bins: usize, maps: usize, leaflets: bool) -> (usize, u64) {
+    let files = if leaflets { 3 * maps } else { maps };
+    let bytes = files as u64 * (HEADER_BYTES + bins as u64 * BYTES_PER_BIN);
+    (files, bytes)
+}
+
+/// Format the number of bytes using a unit that keeps the number readable.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "kB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Describe the size of the ordermap grid.
+fn grid_summary(counts: [Option<usize>; 2]) -> String {
+    match counts {
+        [Some(a), Some(b)] => format!("grid: {} × {} bins", a, b),
+        _ => String::from("grid: auto"),
+    }
+}
+
 impl GuiAnalysis {
+    /// Describe the ordermaps for the summary of the analysis.
+    pub(crate) fn ordermaps_summary(&self, unit: LengthUnit) -> String {
+        let params = &self.ordermaps_params;
+        if !params.calculate_maps {
+            return String::from("off");
+        }
+
+        let plane = match params.active_plane(self.membrane_normal) {
+            Plane::XY => "xy",
+            Plane::YZ => "yz",
+            Plane::XZ => "xz",
+            Plane::Unknown => "automatic",
+        };
+
+        format!(
+            "{} plane, bins {} × {}{}, into '{}'",
+            plane,
+            unit.from_nm(params.bin_size[0]) as f32,
+            unit.from_nm(params.bin_size[1]) as f32,
+            unit.suffix(),
+            params.output_directory
+        )
+    }
+
+    /// Get the directory of the output YAML file if the ordermaps are written outside of it.
+    fn ordermaps_outside_output(&self) -> Option<PathBuf> {
+        let ordermaps = self.ordermaps_params.output_directory()?;
+        if self.output.output_yaml.trim().is_empty() {
+            return None;
+        }
+
+        let output_directory = paths::absolute(self.output.output_yaml.trim())
+            .parent()?
+            .to_path_buf();
+        (!paths::absolute(ordermaps.trim()).starts_with(&output_directory))
+            .then_some(output_directory)
+    }
+
+    /// Informative note shown if the ordermaps are written outside of the directory of the output YAML file.
+    fn ordermaps_location_note(&self, ui: &mut Ui) {
+        let Some(output_directory) = self.ordermaps_outside_output() else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.add_space(104.0);
+            ui.label(
+                RichText::new("ordermaps are written outside of the output directory")
+                    .font(egui::FontId::proportional(10.0))
+                    .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+            )
+            .on_hover_ui(|ui| {
+                ui.label(format!(
+                    "The output YAML file is written into '{}', but the ordermaps are not saved inside this directory.",
+                    output_directory.display()
+                ));
+            });
+        });
+    }
+
     /// Specify parameters for the construction of ordermaps.
     pub(super) fn specify_ordermaps(&mut self, ui: &mut Ui) {
         Self::collapsing_with_warning(
@@ -188,23 +450,15 @@ impl GuiAnalysis {
                 }
 
                 Self::specify_string(
-                &mut self.ordermaps_params.output_directory,
-                ui,
-                "Directory:    ", 
-                "Name of a directory for saving ordermaps. Directory does not have to already exist.", 
-                true
-            );
+                    &mut self.ordermaps_params.output_directory,
+                    ui,
+                    "Directory:    ",
+                    "Name of a directory for saving ordermaps. Directory does not have to already exist.",
+                    true,
+                );
+                self.ordermaps_location_note(ui);
 
-                let raw_plane = if let Some(plane) = self.ordermaps_params.plane {
-                    plane
-                } else {
-                    match self.membrane_normal {
-                        MembraneNormal::X => Plane::YZ,
-                        MembraneNormal::Y => Plane::XZ,
-                        MembraneNormal::Z => Plane::XY,
-                        MembraneNormal::Dynamic | MembraneNormal::FromFile => Plane::Unknown,
-                    }
-                };
+                let raw_plane = self.ordermaps_params.active_plane(self.membrane_normal);
 
                 // specify plane of the maps
                 ui.horizontal(|ui| {
@@ -236,39 +490,26 @@ impl GuiAnalysis {
                     }
 
                     if raw_plane == Plane::Unknown {
-                        ui.label(
-                            RichText::new("❗")
-                                .color(egui::Color32::from_rgba_premultiplied(150, 0, 0, 100)),
-                        );
+                        accessibility::invalid_marker(ui, "select a plane");
                     }
                 });
 
-                let (dim_1, dim_2) = match raw_plane {
-                    Plane::XY => ("X-dimension", "Y-dimension"),
-                    Plane::YZ => ("Z-dimension", "Y-dimension"),
-                    Plane::XZ => ("X-dimension", "Z-dimension"),
-                    Plane::Unknown => ("Unknown dimension", "Unknown dimension"),
-                };
+                let (dim_1, dim_2) = raw_plane.labels();
+                let [axis_1, axis_2] = raw_plane.axes();
 
                 // specify size of the maps
                 ui.vertical(|ui| {
                     Self::label_with_hint(ui, "Maps size: ", "Size of the ordermaps.");
 
-                    Self::specify_dimension(
-                        &mut self.ordermaps_params.dimensions[0],
-                        &mut self.ordermaps_params.x_manual.start,
-                        &mut self.ordermaps_params.x_manual.end,
-                        ui,
-                        dim_1,
-                    );
-
-                    Self::specify_dimension(
-                        &mut self.ordermaps_params.dimensions[1],
-                        &mut self.ordermaps_params.y_manual.start,
-                        &mut self.ordermaps_params.y_manual.end,
-                        ui,
-                        dim_2,
-                    );
+                    let params = &mut self.ordermaps_params;
+                    for (axis, label) in [(axis_1, dim_1), (axis_2, dim_2)] {
+                        Self::specify_dimension(
+                            &mut params.dimensions[axis],
+                            &mut params.manual[axis],
+                            ui,
+                            label,
+                        );
+                    }
                 });
 
                 // specify bin size
@@ -282,6 +523,23 @@ impl GuiAnalysis {
                     Self::specify_bin_size(&mut self.ordermaps_params.bin_size[1], ui, dim_2);
                 });
 
+                let counts = self
+                    .ordermaps_params
+                    .bin_counts(raw_plane, structure::box_size(ui.ctx()));
+                let problem = self
+                    .ordermaps_params
+                    .bin_size_problem(raw_plane, (dim_1, dim_2));
+                Self::grid_summary_label(ui, counts, problem);
+
+                if let ([Some(a), Some(b)], Some(maps)) = (counts, self.ordermap_count(ui.ctx())) {
+                    let leaflets =
+                        self.leaflet_classification_method != LeafletClassification::None;
+                    Self::output_size_label(
+                        ui,
+                        estimate_output_size(a.saturating_mul(b), maps, leaflets),
+                    );
+                }
+
                 // specify minimum number of samples per bin
                 ui.horizontal(|ui| {
                     Self::label_with_hint(
@@ -290,10 +548,13 @@ impl GuiAnalysis {
                         "Minimum number of samples required in a bin to calculate order parameter.",
                     );
 
-                    ui.add(
-                        egui::DragValue::new(&mut self.ordermaps_params.min_samples)
-                            .speed(2.5)
-                            .range(1..=usize::MAX),
+                    focus::chained(
+                        ui.add(
+                            egui::DragValue::new(&mut self.ordermaps_params.min_samples)
+                                .speed(2.5)
+                                .range(1..=usize::MAX)
+                                .custom_parser(units::parse_integer),
+                        ),
                     );
                 });
             },
@@ -303,11 +564,15 @@ impl GuiAnalysis {
     /// Specify the size of the map in a particular dimension.
     fn specify_dimension(
         dim: &mut OrderMapDimension,
-        dim_start: &mut f32,
-        dim_end: &mut f32,
+        manual: &mut ManualDimensions,
         ui: &mut Ui,
         dim_label: &str,
     ) {
+        let ManualDimensions {
+            start: dim_start,
+            end: dim_end,
+        } = manual;
+
         ui.horizontal(|ui| {
             Self::label_with_hint(
                 ui,
@@ -322,58 +587,112 @@ impl GuiAnalysis {
             ui.radio_value(dim, OrderMapDimension::Manual, "manual");
 
             if *dim == OrderMapDimension::Manual {
-                ui.add(
-                    egui::DragValue::new(dim_start)
-                        .speed(0.1)
-                        .range(-f32::MAX..=*dim_end)
-                        .suffix(" nm"),
-                )
-                .on_hover_ui(|ui| {
+                length_drag_value(ui, dim_start, 0.1, -f32::MAX..=*dim_end).on_hover_ui(|ui| {
                     ui.label("start");
                 });
 
-                ui.add(
-                    egui::DragValue::new(dim_end)
-                        .speed(0.1)
-                        .range(*dim_start..=f32::MAX)
-                        .suffix(" nm"),
-                )
-                .on_hover_ui(|ui| {
+                length_drag_value(ui, dim_end, 0.1, *dim_start..=f32::MAX).on_hover_ui(|ui| {
                     ui.label("end");
                 });
+
+                if !span_valid(*dim_start, *dim_end) {
+                    accessibility::invalid_marker(ui, "invalid span").on_hover_ui(|ui| {
+                        ui.label("Start of the span is larger than its end.");
+                    });
+                }
             }
         });
     }
 
     /// Specify the size of a bin in a particular dimension.
     fn specify_bin_size(bin_size: &mut f32, ui: &mut Ui, dim_label: &str) {
-        ui.add(
-            egui::DragValue::new(bin_size)
-                .speed(0.01)
-                .range(0.0..=f32::MAX)
-                .suffix(" nm"),
-        )
-        .on_hover_ui(|ui| {
+        length_drag_value(ui, bin_size, 0.01, 0.0..=f32::MAX).on_hover_ui(|ui| {
             ui.label(dim_label.to_lowercase());
         });
 
         if *bin_size == 0.0 {
-            ui.label(
-                RichText::new("❗").color(egui::Color32::from_rgba_premultiplied(150, 0, 0, 100)),
-            );
+            accessibility::invalid_marker(ui, "zero bin size");
         } else {
             ui.add_space(21.0);
         }
     }
 
+    /// Show the number of bins of the ordermap. Warns if the grid is very large.
+    /// Shows a red marker if the bin size is not reasonable.
+    fn grid_summary_label(ui: &mut Ui, counts: [Option<usize>; 2], problem: Option<String>) {
+        let large = match counts {
+            [Some(a), Some(b)] => a.saturating_mul(b) > LARGE_GRID_THRESHOLD,
+            _ => false,
+        };
+
+        ui.horizontal(|ui| {
+            ui.add_space(104.0);
+            let color = if large {
+                egui::Color32::from_rgb(200, 150, 0)
+            } else {
+                egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)
+            };
+
+            let response = ui.label(
+                RichText::new(grid_summary(counts))
+                    .font(egui::FontId::monospace(12.0))
+                    .color(color),
+            );
+
+            if large {
+                response.on_hover_ui(|ui| {
+                    ui.label("The ordermaps contain a very large number of bins. The output files may be huge.");
+                });
+            }
+
+            if let Some(problem) = problem {
+                accessibility::invalid_marker(ui, "invalid bins")
+                .on_hover_ui(|ui| {
+                    ui.label(problem);
+                });
+            }
+        });
+    }
+
+    /// Show the estimated number and total size of the ordermap files. Warns if the files are very large.
+    fn output_size_label(ui: &mut Ui, (files, bytes): (usize, u64)) {
+        ui.horizontal(|ui| {
+            ui.add_space(104.0);
+            let large = bytes > LARGE_OUTPUT_THRESHOLD;
+            let color = if large {
+                egui::Color32::from_rgb(200, 150, 0)
+            } else {
+                egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)
+            };
+
+            ui.label(
+                RichText::new(format!("output: ~{} in {} files", format_size(bytes), files))
+                    .font(egui::FontId::monospace(12.0))
+                    .color(color),
+            )
+            .on_hover_ui(|ui| {
+                ui.label("Rough estimate based on the number of bins and the atom types selected in the loaded structure.");
+                if large {
+                    ui.label("Make sure there is enough space on the disk or increase the bin size.");
+                }
+            });
+        });
+    }
+
     /// Check that all parameters for the construction of ordermaps have been provided.
     pub(super) fn check_ordermaps_sanity(&self) -> bool {
+        let plane = self.ordermaps_params.active_plane(self.membrane_normal);
         !self.ordermaps_params.calculate_maps
             || (!self.ordermaps_params.output_directory.is_empty()
                 && (self.ordermaps_params.plane.is_some()
                     || self.membrane_normal != MembraneNormal::Dynamic)
                 && self.ordermaps_params.bin_size[0] > 0.0
-                && self.ordermaps_params.bin_size[1] > 0.0)
+                && self.ordermaps_params.bin_size[1] > 0.0
+                && self.ordermaps_params.dimensions_valid(plane)
+                && self
+                    .ordermaps_params
+                    .bin_size_problem(plane, plane.labels())
+                    .is_none())
     }
 }
 
@@ -383,6 +702,48 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn ordermaps_outside_output_directory() {
+        let mut analysis = GuiAnalysis {
+            output: crate::OutputFiles {
+                output_yaml: String::from("results/order.yaml"),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        analysis.ordermaps_params.calculate_maps = true;
+
+        analysis.ordermaps_params.output_directory = String::from("results/ordermaps");
+        assert_eq!(analysis.ordermaps_outside_output(), None);
+
+        analysis.ordermaps_params.output_directory = String::from("results/../ordermaps");
+        assert_eq!(
+            analysis.ordermaps_outside_output(),
+            Some(paths::absolute("results"))
+        );
+
+        analysis.ordermaps_params.output_directory = String::from("/mnt/scratch/ordermaps");
+        assert!(analysis.ordermaps_outside_output().is_some());
+
+        analysis.ordermaps_params.calculate_maps = false;
+        assert_eq!(analysis.ordermaps_outside_output(), None);
+    }
+
+    #[test]
+    fn ordermaps_summary_in_selected_unit() {
+        let mut analysis = GuiAnalysis::default();
+        analysis.ordermaps_params.calculate_maps = true;
+        analysis.ordermaps_params.bin_size = [0.1, 0.25];
+        analysis.ordermaps_params.output_directory = String::from("ordermaps");
+
+        assert!(analysis
+            .ordermaps_summary(LengthUnit::Nanometer)
+            .ends_with("bins 0.1 × 0.25 nm, into 'ordermaps'"));
+        assert!(analysis
+            .ordermaps_summary(LengthUnit::Angstrom)
+            .ends_with("bins 1 × 2.5 Å, into 'ordermaps'"));
+    }
+
     #[test]
     fn gorder_to_guiorder_grid_span() {
         assert_eq!(
@@ -403,41 +764,98 @@ mod tests {
         assert_relative_eq!(params.end, 5.0);
     }
 
+    /// Create gorder analysis with the provided ordermap parameters.
+    fn analysis_with_map(
+        map: Option<gorder::input::OrderMap>,
+        normal: gorder::input::MembraneNormal,
+    ) -> gorder::input::Analysis {
+        let mut builder = gorder::input::Analysis::builder();
+        builder
+            .structure("tests/files/pcpepg.tpr")
+            .trajectory("tests/files/pcpepg.xtc")
+            .analysis_type(gorder::input::AnalysisType::aaorder(
+                "@membrane and element name carbon",
+                "@membrane and element name hydrogen",
+            ))
+            .membrane_normal(normal);
+
+        if let Some(map) = map {
+            builder.ordermap(map);
+        }
+
+        builder.build().unwrap()
+    }
+
     #[test]
     fn gorder_to_guiorder_ordermaps_params() {
-        let params = OrderMapsParams::from(None);
+        let params = OrderMapsParams::from(&analysis_with_map(
+            None,
+            gorder::input::MembraneNormal::Static(gorder::input::Axis::Z),
+        ));
         assert!(!params.calculate_maps);
 
-        let params = OrderMapsParams::from(Some(
-            gorder::input::OrderMap::builder()
-                .bin_size([0.05, 0.2])
-                .dim([
-                    gorder::input::GridSpan::Auto,
-                    gorder::input::GridSpan::manual(-3.0, 10.0).unwrap(),
-                ])
-                .min_samples(100)
-                .plane(gorder::input::Plane::XZ)
-                .output_directory("ordermaps")
-                .build()
-                .unwrap(),
+        let map = gorder::input::OrderMap::builder()
+            .bin_size([0.05, 0.2])
+            .dim([
+                gorder::input::GridSpan::Auto,
+                gorder::input::GridSpan::manual(-3.0, 10.0).unwrap(),
+            ])
+            .min_samples(100)
+            .plane(gorder::input::Plane::XZ)
+            .output_directory("ordermaps")
+            .build()
+            .unwrap();
+
+        let params = OrderMapsParams::from(&analysis_with_map(
+            Some(map),
+            gorder::input::MembraneNormal::Static(gorder::input::Axis::Z),
         ));
 
         assert!(params.calculate_maps);
         assert_relative_eq!(params.bin_size[0], 0.05);
         assert_relative_eq!(params.bin_size[1], 0.2);
+        // xz plane: the second dimension is the z-axis
         assert_eq!(params.dimensions[0], OrderMapDimension::Auto);
-        assert_eq!(params.dimensions[1], OrderMapDimension::Manual);
-        assert_relative_eq!(params.y_manual.start, -3.0);
-        assert_relative_eq!(params.y_manual.end, 10.0);
+        assert_eq!(params.dimensions[2], OrderMapDimension::Manual);
+        assert_relative_eq!(params.manual[2].start, -3.0);
+        assert_relative_eq!(params.manual[2].end, 10.0);
         assert_eq!(params.min_samples, 100);
         assert_eq!(params.plane, Some(Plane::XZ));
         assert_eq!(params.output_directory, String::from("ordermaps"));
     }
 
+    #[test]
+    fn gorder_to_guiorder_ordermaps_params_plane_from_normal() {
+        let map = gorder::input::OrderMap::builder()
+            .output_directory("ordermaps")
+            .dim([
+                gorder::input::GridSpan::manual(1.0, 2.0).unwrap(),
+                gorder::input::GridSpan::manual(3.0, 4.0).unwrap(),
+            ])
+            .build()
+            .unwrap();
+
+        // membrane normal along x: the map is constructed in the yz plane with dimensions [z, y]
+        let params = OrderMapsParams::from(&analysis_with_map(
+            Some(map),
+            gorder::input::MembraneNormal::Static(gorder::input::Axis::X),
+        ));
+
+        assert_eq!(params.plane, None);
+        assert_eq!(params.dimensions[0], OrderMapDimension::Auto);
+        assert_relative_eq!(params.manual[2].start, 1.0);
+        assert_relative_eq!(params.manual[2].end, 2.0);
+        assert_relative_eq!(params.manual[1].start, 3.0);
+        assert_relative_eq!(params.manual[1].end, 4.0);
+    }
+
     #[test]
     fn guiorder_to_gorder_ordermaps_params_no_maps() {
-        let params = OrderMapsParams {
-            calculate_maps: false,
+        let params = GuiAnalysis {
+            ordermaps_params: OrderMapsParams {
+                calculate_maps: false,
+                ..Default::default()
+            },
             ..Default::default()
         };
 
@@ -448,18 +866,28 @@ mod tests {
 
     #[test]
     fn guiorder_to_gorder_ordermaps_params() {
-        let params = OrderMapsParams {
-            calculate_maps: true,
-            output_directory: String::from("ordermaps"),
-            plane: Some(Plane::XZ),
-            bin_size: [0.2, 0.05],
-            dimensions: [OrderMapDimension::Manual, OrderMapDimension::Auto],
-            x_manual: ManualDimensions {
-                start: f32::NEG_INFINITY,
-                end: 3.5,
+        let params = GuiAnalysis {
+            ordermaps_params: OrderMapsParams {
+                calculate_maps: true,
+                output_directory: String::from("ordermaps"),
+                plane: Some(Plane::XZ),
+                bin_size: [0.2, 0.05],
+                dimensions: [
+                    OrderMapDimension::Manual,
+                    OrderMapDimension::Auto,
+                    OrderMapDimension::Auto,
+                ],
+                manual: [
+                    ManualDimensions {
+                        start: f32::NEG_INFINITY,
+                        end: 3.5,
+                    },
+                    ManualDimensions::default(),
+                    ManualDimensions::default(),
+                ],
+                min_samples: 10,
             },
-            y_manual: ManualDimensions::default(),
-            min_samples: 10,
+            ..Default::default()
         };
 
         let converted = Option::<gorder::input::OrderMap>::try_from(&params)
@@ -488,6 +916,228 @@ mod tests {
             gorder::input::GridSpan::Auto => (),
             _ => panic!("Invalid grid span."),
         }
-        assert_eq!(params.min_samples, 10);
+        assert_eq!(params.ordermaps_params.min_samples, 10);
+    }
+
+    #[test]
+    fn manual_dimensions_preserved_on_plane_change() {
+        let mut params = GuiAnalysis {
+            ordermaps_params: OrderMapsParams {
+                calculate_maps: true,
+                output_directory: String::from("ordermaps"),
+                plane: Some(Plane::XY),
+                dimensions: [OrderMapDimension::Manual; 3],
+                manual: [
+                    ManualDimensions {
+                        start: 1.0,
+                        end: 2.0,
+                    },
+                    ManualDimensions {
+                        start: 3.0,
+                        end: 4.0,
+                    },
+                    ManualDimensions {
+                        start: 5.0,
+                        end: 6.0,
+                    },
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let converted = Option::<gorder::input::OrderMap>::try_from(&params)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            converted.dim(),
+            [
+                gorder::input::GridSpan::Manual {
+                    start: 1.0,
+                    end: 2.0
+                },
+                gorder::input::GridSpan::Manual {
+                    start: 3.0,
+                    end: 4.0
+                },
+            ]
+        ));
+
+        params.ordermaps_params.plane = Some(Plane::YZ);
+
+        // the y range stays bound to the y-axis, the z range is used for the other dimension
+        let converted = Option::<gorder::input::OrderMap>::try_from(&params)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            converted.dim(),
+            [
+                gorder::input::GridSpan::Manual {
+                    start: 5.0,
+                    end: 6.0
+                },
+                gorder::input::GridSpan::Manual {
+                    start: 3.0,
+                    end: 4.0
+                },
+            ]
+        ));
+    }
+
+    #[test]
+    fn ordermaps_sanity_invalid_dimensions() {
+        let mut params = GuiAnalysis {
+            ordermaps_params: OrderMapsParams {
+                calculate_maps: true,
+                output_directory: String::from("ordermaps"),
+                dimensions: [
+                    OrderMapDimension::Manual,
+                    OrderMapDimension::Auto,
+                    OrderMapDimension::Auto,
+                ],
+                manual: [
+                    ManualDimensions {
+                        start: 5.0,
+                        end: 2.0,
+                    },
+                    ManualDimensions::default(),
+                    ManualDimensions::default(),
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(!params.check_ordermaps_sanity());
+
+        // auto dimensions ignore the manual values
+        params.ordermaps_params.dimensions[0] = OrderMapDimension::Auto;
+        assert!(params.check_ordermaps_sanity());
+
+        // dimensions outside of the plane are ignored
+        params.ordermaps_params.dimensions[0] = OrderMapDimension::Manual;
+        params.ordermaps_params.plane = Some(Plane::YZ);
+        assert!(params.check_ordermaps_sanity());
+    }
+
+    #[test]
+    fn ordermaps_sanity_imported_dimensions() {
+        let map = gorder::input::OrderMap::builder()
+            .output_directory("ordermaps")
+            .dim([
+                gorder::input::GridSpan::manual(1.0, 4.0).unwrap(),
+                gorder::input::GridSpan::Auto,
+            ])
+            .build()
+            .unwrap();
+
+        let mut params = GuiAnalysis {
+            ordermaps_params: OrderMapsParams::from(&analysis_with_map(
+                Some(map),
+                gorder::input::MembraneNormal::Static(gorder::input::Axis::Z),
+            )),
+            ..Default::default()
+        };
+        assert!(params.check_ordermaps_sanity());
+
+        // simulate an imported config with the span in the wrong order
+        params.ordermaps_params.manual[0] = ManualDimensions {
+            start: 4.0,
+            end: 1.0,
+        };
+        assert!(!params.check_ordermaps_sanity());
+    }
+
+    #[test]
+    fn estimate_ordermaps_size() {
+        assert_eq!(estimate_output_size(100, 5, false), (5, 5 * (400 + 2000)));
+        assert_eq!(estimate_output_size(100, 5, true), (15, 15 * (400 + 2000)));
+
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1_500_000), "1.5 MB");
+        assert_eq!(format_size(2_000_000_000), "2.0 GB");
+    }
+
+    #[test]
+    fn bin_counts() {
+        assert_eq!(bin_count(10.0, 0.1), Some(100));
+        assert_eq!(bin_count(10.0, 0.3), Some(34));
+        assert_eq!(bin_count(0.05, 0.1), Some(1));
+        assert_eq!(bin_count(f32::INFINITY, 0.1), None);
+        assert_eq!(bin_count(10.0, 0.0), None);
+
+        assert_eq!(grid_summary([Some(100), Some(50)]), "grid: 100 × 50 bins");
+        assert_eq!(grid_summary([None, Some(50)]), "grid: auto");
+    }
+
+    #[test]
+    fn bin_counts_for_plane() {
+        let params = OrderMapsParams {
+            calculate_maps: true,
+            bin_size: [0.5, 0.1],
+            dimensions: [
+                OrderMapDimension::Manual,
+                OrderMapDimension::Auto,
+                OrderMapDimension::Auto,
+            ],
+            manual: [
+                ManualDimensions {
+                    start: 2.0,
+                    end: 12.0,
+                },
+                ManualDimensions::default(),
+                ManualDimensions::default(),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(params.bin_counts(Plane::XY, None), [Some(20), None]);
+        assert_eq!(
+            params.bin_counts(Plane::XY, Some([8.0, 6.0, 10.0])),
+            [Some(20), Some(60)]
+        );
+        assert_eq!(
+            params.bin_counts(Plane::YZ, Some([8.0, 6.0, 10.0])),
+            [Some(20), Some(60)]
+        );
+    }
+
+    #[test]
+    fn ordermaps_sanity_bin_size() {
+        let mut params = GuiAnalysis {
+            ordermaps_params: OrderMapsParams {
+                calculate_maps: true,
+                output_directory: String::from("ordermaps"),
+                bin_size: [2.0, 0.1],
+                dimensions: [
+                    OrderMapDimension::Manual,
+                    OrderMapDimension::Auto,
+                    OrderMapDimension::Auto,
+                ],
+                manual: [
+                    ManualDimensions {
+                        start: 0.0,
+                        end: 1.5,
+                    },
+                    ManualDimensions::default(),
+                    ManualDimensions::default(),
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // bin size larger than the span
+        assert!(!params.check_ordermaps_sanity());
+
+        params.ordermaps_params.bin_size[0] = 0.1;
+        assert!(params.check_ordermaps_sanity());
+
+        // bin size absurdly small
+        params.ordermaps_params.bin_size[0] = 1e-6;
+        assert!(!params.check_ordermaps_sanity());
+
+        // auto dimensions are exempt
+        params.ordermaps_params.dimensions[0] = OrderMapDimension::Auto;
+        assert!(params.check_ordermaps_sanity());
     }
 }
@@ -3,12 +3,14 @@
 
 //! Specification of parameters that do not fit elsewhere.
 
-use eframe::egui::{self, Color32, DragValue, RichText, Ui};
+use std::path::Path;
 
-use crate::GuiAnalysis;
+use eframe::egui::{self, Color32, DragValue, Id, RichText, Ui};
+
+use crate::{file_drop, focus, structure, units, GuiAnalysis};
 
 /// Parameters that do not fit elsewhere.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct OtherParams {
     pub min_samples: usize,
     pub n_threads: usize,
@@ -17,6 +19,30 @@ pub(crate) struct OtherParams {
     pub silent: bool,
 }
 
+/// Get the number of cores available to the application (at least 1).
+pub(crate) fn available_cores() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Id of the flag allowing more threads than available cores.
+fn oversubscription_id() -> Id {
+    Id::new("guiorder_allow_oversubscription")
+}
+
+/// Get the maximal number of threads that can be selected.
+/// Without oversubscription, the number of threads is limited to the number of available cores.
+/// The limit is lifted if the number of threads already exceeds it, e.g., after importing a configuration.
+fn thread_limit(n_threads: usize, cores: usize, oversubscribe: bool) -> usize {
+    if oversubscribe || n_threads > cores {
+        usize::MAX
+    } else {
+        cores
+    }
+}
+
 impl Default for OtherParams {
     fn default() -> Self {
         Self {
@@ -41,7 +67,20 @@ impl From<&gorder::input::Analysis> for OtherParams {
     }
 }
 
+/// Warning shown when gorder may be unable to make the molecules whole.
+const NO_BONDS_WARNING: &str = "PBC handling is disabled, but no information about the bonds is available: the structure is not a TPR file and no bonds file is provided. gorder may be unable to reconstruct the molecules. Provide a bonds file in 'Advanced input' or enable PBC handling.";
+
 impl GuiAnalysis {
+    /// Get a warning if PBC handling is disabled and neither the structure
+    /// nor a bonds file provides the connectivity of the system.
+    pub(crate) fn missing_bonds_warning(&self) -> Option<&'static str> {
+        let has_bonds = !self.bonds.trim().is_empty()
+            || file_drop::has_extension(Path::new(self.structure.trim()), &["tpr"]);
+
+        (!self.other_params.handle_pbc && !self.structure.trim().is_empty() && !has_bonds)
+            .then_some(NO_BONDS_WARNING)
+    }
+
     /// Specify parameters that do not fit elsewhere.
     pub(super) fn specify_other_options(&mut self, ui: &mut Ui) {
         Self::collapsing_with_warning(ui, "Other options", false, true, |ui| {
@@ -52,25 +91,84 @@ impl GuiAnalysis {
                     "Minimum number of samples collected for each bond required to calculate order parameter for it.",
                 );
 
-                ui.add(
+                focus::chained(ui.add(
                     DragValue::new(&mut self.other_params.min_samples)
                         .speed(5)
-                        .range(1..=usize::MAX),
-                );
+                        .range(1..=usize::MAX)
+                        .custom_parser(units::parse_integer),
+                ));
             });
 
             ui.horizontal(|ui| {
+                let cores = available_cores();
                 Self::label_with_hint(
                     ui,
                     "Number of threads: ",
-                    "Number of threads used to perform the analysis.",
+                    &format!(
+                        "Number of threads used to perform the analysis. Detected {} available core(s).",
+                        cores
+                    ),
                 );
 
-                ui.add(
+                let oversubscribe = ui
+                    .ctx()
+                    .data_mut(|d| d.get_temp::<bool>(oversubscription_id()))
+                    .unwrap_or(false);
+
+                let limit = thread_limit(self.other_params.n_threads, cores, oversubscribe);
+                focus::chained(ui.add(
                     DragValue::new(&mut self.other_params.n_threads)
                         .speed(0.05)
-                        .range(1..=usize::MAX),
+                        .range(1..=limit)
+                        .custom_parser(units::parse_integer),
+                ));
+
+                let usage = RichText::new(format!("{} / {} cores", self.other_params.n_threads, cores))
+                    .font(egui::FontId::monospace(12.0));
+                if self.other_params.n_threads > cores {
+                    ui.label(usage.color(Color32::from_rgb(200, 150, 0)))
+                        .on_hover_ui(|ui| {
+                            ui.label("More threads than available cores. The analysis will probably not be any faster.");
+                        });
+                } else {
+                    ui.label(usage.color(Color32::from_rgba_premultiplied(150, 150, 150, 100)));
+                }
+
+                if ui
+                    .add_enabled(
+                        self.other_params.n_threads != cores,
+                        egui::Button::new("Use all cores"),
+                    )
+                    .on_hover_ui(|ui| {
+                        ui.label(format!("Use {} thread(s), one for each available core.", cores));
+                    })
+                    .clicked()
+                {
+                    self.other_params.n_threads = cores;
+                }
+            });
+
+            ui.horizontal(|ui| {
+                let id = oversubscription_id();
+                let mut oversubscribe = ui
+                    .ctx()
+                    .data_mut(|d| d.get_temp::<bool>(id))
+                    .unwrap_or(false)
+                    || self.other_params.n_threads > available_cores();
+
+                Self::label_with_hint(
+                    ui,
+                    "Oversubscribe: ",
+                    "Check the box to allow using more threads than there are available cores.",
                 );
+
+                if ui.checkbox(&mut oversubscribe, "").changed() {
+                    if !oversubscribe {
+                        self.other_params.n_threads =
+                            self.other_params.n_threads.min(available_cores());
+                    }
+                    ui.ctx().data_mut(|d| d.insert_temp(id, oversubscribe));
+                }
             });
 
             ui.horizontal(|ui| {
@@ -82,7 +180,22 @@ impl GuiAnalysis {
 
                 ui.checkbox(&mut self.other_params.handle_pbc, "");
 
-                if self.other_params.handle_pbc {
+                if self.other_params.handle_pbc
+                    && structure::box_orthogonal(ui.ctx()) == Some(false)
+                {
+                    ui.label(
+                        RichText::new("box is not orthogonal!")
+                            .font(egui::FontId::proportional(10.0))
+                            .color(Color32::from_rgb(200, 150, 0)),
+                    )
+                    .on_hover_ui(|ui| {
+                        ui.label("PBC handling requires an orthogonal simulation box. Make the molecules whole and disable PBC handling.");
+                    });
+
+                    if ui.small_button("Disable").clicked() {
+                        self.other_params.handle_pbc = false;
+                    }
+                } else if self.other_params.handle_pbc {
                     ui.label(
                         RichText::new("simulation box must be orthogonal")
                             .font(egui::FontId::proportional(10.0)),
@@ -97,6 +210,20 @@ impl GuiAnalysis {
 
             });
 
+            if let Some(warning) = self.missing_bonds_warning() {
+                ui.horizontal(|ui| {
+                    ui.add_space(104.0);
+                    ui.label(
+                        RichText::new("no bonds available!")
+                            .font(egui::FontId::proportional(10.0))
+                            .color(Color32::from_rgb(200, 150, 0)),
+                    )
+                    .on_hover_ui(|ui| {
+                        ui.label(warning);
+                    });
+                });
+            }
+
             ui.horizontal(|ui| {
                 Self::label_with_hint(
                     ui,
@@ -148,4 +275,38 @@ mod tests {
         assert!(params.overwrite);
         assert_eq!(params.n_threads, 8);
     }
+
+    #[test]
+    fn warn_about_missing_bonds() {
+        let mut analysis = GuiAnalysis {
+            structure: String::from("system.gro"),
+            ..Default::default()
+        };
+        assert!(analysis.missing_bonds_warning().is_none());
+
+        analysis.other_params.handle_pbc = false;
+        assert!(analysis.missing_bonds_warning().is_some());
+
+        analysis.bonds = String::from("system.bnd");
+        assert!(analysis.missing_bonds_warning().is_none());
+
+        analysis.bonds.clear();
+        analysis.structure = String::from("system.TPR");
+        assert!(analysis.missing_bonds_warning().is_none());
+    }
+
+    #[test]
+    fn limit_threads_to_cores() {
+        assert_eq!(thread_limit(4, 8, false), 8);
+        assert_eq!(thread_limit(4, 8, true), usize::MAX);
+        assert_eq!(thread_limit(16, 8, false), usize::MAX);
+        assert_eq!(thread_limit(8, 8, false), 8);
+    }
+
+    #[test]
+    fn default_single_thread() {
+        // same as the default of gorder, so that the thread count is not exported unless changed
+        assert_eq!(OtherParams::default().n_threads, 1);
+        assert!(available_cores() >= 1);
+    }
 }
@@ -0,0 +1,304 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Listing of the output files written by the analysis.
+
+use std::path::{Path, PathBuf};
+
+use eframe::egui::{self, RichText, Ui};
+
+use crate::{accessibility, paths, GuiAnalysis, GuiOrderApp};
+
+/// Output files written by an analysis, grouped by their kind.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct WrittenOutputs {
+    pub tables: Vec<PathBuf>,
+    pub maps: Vec<PathBuf>,
+    pub diagnostics: Vec<PathBuf>,
+}
+
+/// Get the path if it exists.
+fn existing(path: &str) -> Option<PathBuf> {
+    if path.is_empty() {
+        return None;
+    }
+
+    let path = PathBuf::from(paths::expand_path(path));
+    path.exists().then_some(path)
+}
+
+/// Get all files written for the XVG output.
+/// gorder writes one file per molecule type, inserting the name of the molecule
+/// into the filename, e.g. `order.xvg` -> `order_POPC.xvg`.
+fn xvg_files(pattern: &str) -> Vec<PathBuf> {
+    if pattern.is_empty() {
+        return Vec::new();
+    }
+
+    let escaped = glob::Pattern::escape(&paths::expand_path(pattern));
+    let mut files = glob::glob(&paths::insert_suffix(&escaped, "_*"))
+        .map(|matches| matches.filter_map(Result::ok).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    files.sort_by(|a, b| paths::natural_cmp(&a.to_string_lossy(), &b.to_string_lossy()));
+    files
+}
+
+impl GuiAnalysis {
+    /// Get the output files of the analysis that exist on the disk.
+    /// Includes the output files of all geometry regions.
+    pub(crate) fn written_outputs(&self) -> WrittenOutputs {
+        let mut outputs = WrittenOutputs::default();
+
+        for analysis in self.region_analyses() {
+            outputs.tables.extend(
+                [
+                    &analysis.output.output_yaml,
+                    &analysis.output.output_csv,
+                    &analysis.output.output_tab,
+                ]
+                .into_iter()
+                .filter_map(|path| existing(path)),
+            );
+            outputs
+                .tables
+                .extend(xvg_files(&analysis.output.output_xvg));
+
+            outputs.maps.extend(
+                analysis
+                    .ordermaps_params
+                    .output_directory()
+                    .and_then(existing),
+            );
+
+            outputs.diagnostics.extend(
+                analysis
+                    .estimate_error_params
+                    .output_convergence()
+                    .and_then(existing),
+            );
+        }
+
+        outputs
+    }
+}
+
+/// Find groups of outputs that point to the same path. Empty paths are ignored.
+fn collisions<'a>(outputs: &[(&'a str, &str)]) -> Vec<Vec<&'a str>> {
+    let mut groups: Vec<(PathBuf, Vec<&'a str>)> = Vec::new();
+    for &(name, path) in outputs {
+        if path.is_empty() {
+            continue;
+        }
+
+        let normalized = paths::absolute(path);
+        match groups.iter_mut().find(|(other, _)| *other == normalized) {
+            Some((_, names)) => names.push(name),
+            None => groups.push((normalized, vec![name])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter_map(|(_, names)| (names.len() > 1).then_some(names))
+        .collect()
+}
+
+impl GuiAnalysis {
+    /// Get groups of output files that would overwrite each other.
+    pub(crate) fn output_collisions(&self) -> Vec<Vec<&'static str>> {
+        collisions(&[
+            ("output YAML", self.output.output_yaml.as_str()),
+            ("output CSV", self.output.output_csv.as_str()),
+            ("output table", self.output.output_tab.as_str()),
+            ("output XVG", self.output.output_xvg.as_str()),
+            (
+                "convergence file",
+                self.estimate_error_params
+                    .output_convergence()
+                    .unwrap_or_default(),
+            ),
+            (
+                "ordermaps directory",
+                self.ordermaps_params.output_directory().unwrap_or_default(),
+            ),
+        ])
+    }
+
+    /// Show a red warning for every group of output files that would overwrite each other.
+    pub(crate) fn output_collisions_label(&self, ui: &mut Ui) {
+        for names in self.output_collisions() {
+            ui.label(accessibility::invalid_message(&format!(
+                "The same path is used for {}.",
+                names.join(", ")
+            )));
+        }
+    }
+}
+
+/// Names of molecule types used to preview the XVG filenames.
+const EXAMPLE_MOLECULES: [&str; 2] = ["POPC", "POPE"];
+
+/// Get example filenames of the XVG files written for the pattern.
+/// gorder inserts the name of the molecule type before the extension of the file.
+fn xvg_preview(pattern: &str) -> Vec<String> {
+    EXAMPLE_MOLECULES
+        .iter()
+        .map(|molecule| paths::insert_suffix(pattern.trim(), &format!("_{}", molecule)))
+        .collect()
+}
+
+/// Check whether the pattern is a filename with an extension.
+/// Without an extension, the name of the molecule type is appended to the end of the path.
+fn xvg_pattern_has_extension(pattern: &str) -> bool {
+    let pattern = pattern.trim();
+    !pattern.ends_with(['/', '\\'])
+        && Path::new(pattern)
+            .extension()
+            .is_some_and(|ext| !ext.is_empty())
+}
+
+impl GuiAnalysis {
+    /// Show example filenames of the XVG files written for the pattern.
+    /// Warns if the pattern has no extension.
+    pub(crate) fn xvg_preview_label(ui: &mut Ui, pattern: &str) {
+        if pattern.trim().is_empty() {
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.add_space(104.0);
+            let preview = format!("→ {}, …", xvg_preview(pattern).join(", "));
+            if xvg_pattern_has_extension(pattern) {
+                ui.label(
+                    RichText::new(preview)
+                        .font(egui::FontId::monospace(12.0))
+                        .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                )
+                .on_hover_ui(|ui| {
+                    ui.label("One XVG file is written for each molecule type, with the name of the molecule type inserted before the extension.");
+                });
+            } else {
+                ui.label(
+                    RichText::new(preview)
+                        .font(egui::FontId::monospace(12.0))
+                        .color(egui::Color32::from_rgb(200, 150, 0)),
+                )
+                .on_hover_ui(|ui| {
+                    ui.label("The pattern has no extension, so the name of the molecule type is appended to its end. Use a filename such as 'order.xvg'.");
+                });
+            }
+        });
+    }
+}
+
+/// Create a label for the button opening the file.
+fn open_label(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+
+    if path.is_dir() {
+        format!("📂 {}", name)
+    } else {
+        format!("📄 {}", name)
+    }
+}
+
+impl GuiOrderApp {
+    /// Get buttons for opening the written output files, grouped under headings.
+    pub(super) fn output_file_links(&self) -> Vec<(String, Vec<(String, PathBuf)>)> {
+        let outputs = self.last_run().written_outputs();
+        let links = |files: Vec<PathBuf>| {
+            files
+                .into_iter()
+                .map(|path| (open_label(&path), path))
+                .collect::<Vec<_>>()
+        };
+
+        vec![
+            (String::from("Tables"), links(outputs.tables)),
+            (String::from("Maps"), links(outputs.maps)),
+            (String::from("Diagnostics"), links(outputs.diagnostics)),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_xvg_files() {
+        assert_eq!(
+            xvg_preview("results/order.xvg"),
+            vec!["results/order_POPC.xvg", "results/order_POPE.xvg"]
+        );
+        assert!(xvg_pattern_has_extension("results/order.xvg"));
+        assert!(!xvg_pattern_has_extension("results/order"));
+        assert!(!xvg_pattern_has_extension("results.xvg/"));
+    }
+
+    #[test]
+    fn written_outputs_existing_only() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "order.yaml",
+            "order_POPC.xvg",
+            "order_POPE.xvg",
+            "order_L10.xvg",
+            "order_L2.xvg",
+        ] {
+            std::fs::File::create(dir.path().join(name)).unwrap();
+        }
+
+        let path = |name: &str| format!("{}/{}", dir.path().display(), name);
+        let analysis = GuiAnalysis {
+            output: crate::OutputFiles {
+                output_yaml: path("order.yaml"),
+                output_csv: path("order.csv"),
+                output_tab: String::new(),
+                output_xvg: path("order.xvg"),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let outputs = analysis.written_outputs();
+        assert_eq!(
+            outputs.tables,
+            vec![
+                dir.path().join("order.yaml"),
+                dir.path().join("order_L2.xvg"),
+                dir.path().join("order_L10.xvg"),
+                dir.path().join("order_POPC.xvg"),
+                dir.path().join("order_POPE.xvg"),
+            ]
+        );
+        assert!(outputs.maps.is_empty());
+        assert!(outputs.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn colliding_outputs() {
+        let mut analysis = GuiAnalysis {
+            output: crate::OutputFiles {
+                output_yaml: String::from("order.yaml"),
+                output_csv: String::from("out/order.csv"),
+                output_tab: String::from("out/./order.csv"),
+                output_xvg: String::new(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            analysis.output_collisions(),
+            vec![vec!["output CSV", "output table"]]
+        );
+
+        analysis.output.output_tab = String::from("order.tab");
+        assert!(analysis.output_collisions().is_empty());
+    }
+}
@@ -0,0 +1,448 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Helper functions for working with paths provided by the user.
+
+use std::{
+    cmp::Ordering,
+    iter::Peekable,
+    path::{Component, Path, PathBuf},
+    str::Chars,
+    sync::LazyLock,
+};
+
+use chrono::{DateTime, TimeZone};
+use regex::{Captures, Regex};
+
+use crate::error::ConversionError;
+
+/// Get the home directory of the current user.
+fn home_dir() -> Option<String> {
+    std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .ok()
+}
+
+/// Get the directory for the configuration files of the application, following the conventions of the platform.
+pub(crate) fn config_dir(app: &str) -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        PathBuf::from(std::env::var("APPDATA").ok()?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(home_dir()?).join("Library/Application Support")
+    } else {
+        match std::env::var("XDG_CONFIG_HOME") {
+            Ok(dir) if Path::new(&dir).is_absolute() => PathBuf::from(dir),
+            _ => PathBuf::from(home_dir()?).join(".config"),
+        }
+    };
+
+    Some(base.join(app))
+}
+
+/// Expand a leading `~` into the home directory of the current user.
+fn expand_tilde(path: &str) -> String {
+    let rest = if path == "~" {
+        ""
+    } else if let Some(rest) = path.strip_prefix("~/") {
+        rest
+    } else if let Some(rest) = path.strip_prefix("~\\") {
+        rest
+    } else {
+        return path.to_owned();
+    };
+
+    match home_dir() {
+        Some(home) if rest.is_empty() => home,
+        Some(home) => format!("{}/{}", home.trim_end_matches(['/', '\\']), rest),
+        None => path.to_owned(),
+    }
+}
+
+/// References to environment variables, either `${VAR}` or `$VAR`.
+static ENV_VAR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}|\$([A-Za-z_][A-Za-z0-9_]*)").unwrap()
+});
+
+/// Expand `$VAR` and `${VAR}` references into the values of the environment variables.
+/// References to undefined variables are kept unchanged.
+fn expand_env_vars(path: &str) -> String {
+    ENV_VAR
+        .replace_all(path, |caps: &Captures| {
+            let name = caps
+                .get(1)
+                .or_else(|| caps.get(2))
+                .map(|name| name.as_str())
+                .unwrap_or_default();
+
+            std::env::var(name).unwrap_or_else(|_| caps[0].to_owned())
+        })
+        .into_owned()
+}
+
+/// Expand a leading `~` and all environment variables in the path.
+pub(crate) fn expand_path(path: &str) -> String {
+    expand_env_vars(&expand_tilde(path))
+}
+
+/// Get the absolute path, e.g. of a file that does not exist yet.
+/// The path is expanded, made absolute and `.` and `..` components are resolved without accessing the disk.
+pub(crate) fn absolute(path: &str) -> PathBuf {
+    let expanded = PathBuf::from(expand_path(path));
+    let absolute = std::path::absolute(&expanded).unwrap_or(expanded);
+
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+
+    normalized
+}
+
+/// Check whether the path contains glob wildcards.
+pub(crate) fn is_glob_pattern(path: &str) -> bool {
+    path.contains('*') || path.contains('?')
+}
+
+/// Get all existing paths matching the glob pattern, sorted in natural order.
+pub(crate) fn expand_glob(pattern: &str) -> Result<Vec<String>, ConversionError> {
+    let mut matched = glob::glob(pattern)
+        .map_err(|e| ConversionError::InvalidGlobPattern(pattern.to_owned(), e.to_string()))?
+        .filter_map(Result::ok)
+        .map(|path| path.display().to_string())
+        .collect::<Vec<String>>();
+
+    if matched.is_empty() {
+        return Err(ConversionError::NoGlobMatch(pattern.to_owned()));
+    }
+
+    matched.sort_by(|a, b| natural_cmp(a, b));
+    Ok(matched)
+}
+
+/// Compare two paths in natural order, i.e. treating runs of digits as numbers
+/// so that `part2.xtc` is placed before `part10.xtc`.
+pub(crate) fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek(), b_chars.peek()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let a_number = take_digits(&mut a_chars);
+                let b_number = take_digits(&mut b_chars);
+                let a_trimmed = a_number.trim_start_matches('0');
+                let b_trimmed = b_number.trim_start_matches('0');
+
+                let ordering = a_trimmed
+                    .len()
+                    .cmp(&b_trimmed.len())
+                    .then_with(|| a_trimmed.cmp(b_trimmed))
+                    .then_with(|| a_number.len().cmp(&b_number.len()));
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                let ordering = x.cmp(y);
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+/// Insert the suffix at the end of the file name, before its extension.
+/// Paths without an extension (e.g. directories) get the suffix appended.
+/// Empty paths are kept empty.
+pub(crate) fn insert_suffix(path: &str, suffix: &str) -> String {
+    let trimmed = path.trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() {
+        return path.to_owned();
+    }
+
+    let at = suffix_position(trimmed);
+    format!("{}{}{}", &trimmed[..at], suffix, &trimmed[at..])
+}
+
+/// Reference to an environment variable written as `$VAR` at the end of the text.
+static TRAILING_ENV_VAR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$([A-Za-z_][A-Za-z0-9_]*)$").unwrap());
+
+/// Insert the suffix into a path that has not been expanded yet, see `insert_suffix`.
+/// `~` and environment variables are kept unexpanded. A variable ending the file name
+/// is rewritten from `$VAR` to `${VAR}`, so the suffix does not become part of its name.
+pub(crate) fn insert_suffix_unexpanded(path: &str, suffix: &str) -> String {
+    let trimmed = path.trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() {
+        return path.to_owned();
+    }
+
+    let at = suffix_position(trimmed);
+    let stem = TRAILING_ENV_VAR.replace(&trimmed[..at], |caps: &Captures| {
+        format!("${{{}}}", &caps[1])
+    });
+    format!("{}{}{}", stem, suffix, &trimmed[at..])
+}
+
+/// Get the position in the path (without trailing separators) at which a suffix is inserted,
+/// i.e. the start of the extension of the file name or the end of the path.
+fn suffix_position(path: &str) -> usize {
+    let name_start = path.rfind(['/', '\\']).map(|i| i + 1).unwrap_or(0);
+    match path[name_start..].rfind('.') {
+        Some(dot) if dot > 0 => name_start + dot,
+        _ => path.len(),
+    }
+}
+
+/// Directory containing the timestamped output directories of the individual runs.
+const RUN_DIRECTORY_BASE: &str = "results";
+
+/// Get the path of the output directory for an analysis started at `time`.
+/// The directory is named after the date and time of the start in the time zone of `time`,
+/// e.g. `results/2025-03-14_09-26-53`. If such directory already exists, a number is appended to the name.
+pub(crate) fn run_directory<Tz: TimeZone>(time: &DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    unique_directory(&format!(
+        "{}/{}",
+        RUN_DIRECTORY_BASE,
+        time.format("%Y-%m-%d_%H-%M-%S")
+    ))
+}
+
+/// Get a path to a directory that does not exist yet, appending `_2`, `_3`, etc. to `path` if needed.
+fn unique_directory(path: &str) -> String {
+    if !Path::new(path).exists() {
+        return path.to_owned();
+    }
+
+    let mut number = 2;
+    loop {
+        let candidate = format!("{}_{}", path, number);
+        if !Path::new(&candidate).exists() {
+            return candidate;
+        }
+        number += 1;
+    }
+}
+
+/// Consume a run of ASCII digits from the iterator.
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+        digits.push(c);
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolute_paths() {
+        assert_eq!(absolute("out/./order.csv"), absolute("out/order.csv"));
+        assert_eq!(absolute("out/../order.csv"), absolute("order.csv"));
+        assert_ne!(absolute("out/order.csv"), absolute("order.csv"));
+    }
+
+    #[test]
+    fn expand_tilde_home() {
+        let home = home_dir().unwrap();
+        let home = home.trim_end_matches(['/', '\\']);
+
+        assert_eq!(expand_path("~"), home_dir().unwrap());
+        assert_eq!(
+            expand_path("~/sims/run1/md.xtc"),
+            format!("{}/sims/run1/md.xtc", home)
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn config_dir_xdg() {
+        std::env::set_var("XDG_CONFIG_HOME", "/data/config");
+        assert_eq!(
+            config_dir("guiorder"),
+            Some(PathBuf::from("/data/config/guiorder"))
+        );
+
+        std::env::set_var("XDG_CONFIG_HOME", "relative/config");
+        assert_eq!(
+            config_dir("guiorder"),
+            Some(PathBuf::from(home_dir().unwrap()).join(".config/guiorder"))
+        );
+    }
+
+    #[test]
+    fn expand_tilde_not_leading() {
+        assert_eq!(expand_path("sims/~/md.xtc"), "sims/~/md.xtc");
+        assert_eq!(expand_path("~user/md.xtc"), "~user/md.xtc");
+    }
+
+    #[test]
+    fn expand_env_var_plain() {
+        std::env::set_var("GUIORDER_TEST_SIM_DIR", "/data/sims");
+        assert_eq!(
+            expand_path("$GUIORDER_TEST_SIM_DIR/md.xtc"),
+            "/data/sims/md.xtc"
+        );
+    }
+
+    #[test]
+    fn expand_env_var_braces() {
+        std::env::set_var("GUIORDER_TEST_RUN", "run1");
+        assert_eq!(
+            expand_path("sims/${GUIORDER_TEST_RUN}_part1.xtc"),
+            "sims/run1_part1.xtc"
+        );
+    }
+
+    #[test]
+    fn expand_env_var_undefined() {
+        assert_eq!(
+            expand_path("$GUIORDER_TEST_UNDEFINED/md.xtc"),
+            "$GUIORDER_TEST_UNDEFINED/md.xtc"
+        );
+        assert_eq!(expand_path("md.xtc"), "md.xtc");
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(is_glob_pattern("traj_part*.xtc"));
+        assert!(is_glob_pattern("traj_part?.xtc"));
+        assert!(!is_glob_pattern("traj_part1.xtc"));
+    }
+
+    #[test]
+    fn expand_glob_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["part2.xtc", "part10.xtc", "part1.xtc", "other.trr"] {
+            std::fs::File::create(dir.path().join(name)).unwrap();
+        }
+
+        let pattern = format!("{}/part*.xtc", dir.path().display());
+        assert_eq!(
+            expand_glob(&pattern).unwrap(),
+            vec![
+                format!("{}/part1.xtc", dir.path().display()),
+                format!("{}/part2.xtc", dir.path().display()),
+                format!("{}/part10.xtc", dir.path().display()),
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_glob_no_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let pattern = format!("{}/part*.xtc", dir.path().display());
+
+        match expand_glob(&pattern) {
+            Err(ConversionError::NoGlobMatch(x)) => assert_eq!(x, pattern),
+            _ => panic!("Pattern should not match anything."),
+        }
+    }
+
+    #[test]
+    fn natural_ordering() {
+        assert_eq!(natural_cmp("part2.xtc", "part10.xtc"), Ordering::Less);
+        assert_eq!(natural_cmp("part10.xtc", "part9.xtc"), Ordering::Greater);
+        assert_eq!(natural_cmp("part1.xtc", "part1.xtc"), Ordering::Equal);
+        assert_eq!(natural_cmp("part01.xtc", "part1.xtc"), Ordering::Greater);
+        assert_eq!(
+            natural_cmp("md.part0002.xtc", "md.part0010.xtc"),
+            Ordering::Less
+        );
+        assert_eq!(natural_cmp("a.xtc", "b.xtc"), Ordering::Less);
+        assert_eq!(natural_cmp("part.xtc", "part1.xtc"), Ordering::Less);
+    }
+
+    #[test]
+    fn insert_suffixes() {
+        assert_eq!(
+            insert_suffix("order.yaml", "_region2"),
+            "order_region2.yaml"
+        );
+        assert_eq!(
+            insert_suffix("results/order.csv", "_region2"),
+            "results/order_region2.csv"
+        );
+        assert_eq!(insert_suffix("ordermaps", "_region3"), "ordermaps_region3");
+        assert_eq!(
+            insert_suffix("out/ordermaps/", "_region2"),
+            "out/ordermaps_region2"
+        );
+        assert_eq!(
+            insert_suffix("run.1/order", "_region2"),
+            "run.1/order_region2"
+        );
+        assert_eq!(insert_suffix(".hidden", "_region2"), ".hidden_region2");
+        assert_eq!(insert_suffix("", "_region2"), "");
+    }
+
+    #[test]
+    fn insert_suffixes_unexpanded() {
+        assert_eq!(
+            insert_suffix_unexpanded("~/results/order.yaml", "_region2"),
+            "~/results/order_region2.yaml"
+        );
+        assert_eq!(
+            insert_suffix_unexpanded("$HOME/order.csv", "_region2"),
+            "$HOME/order_region2.csv"
+        );
+        assert_eq!(
+            insert_suffix_unexpanded("results/$NAME.yaml", "_region2"),
+            "results/${NAME}_region2.yaml"
+        );
+        assert_eq!(
+            insert_suffix_unexpanded("$OUT", "_region3"),
+            "${OUT}_region3"
+        );
+        assert_eq!(
+            insert_suffix_unexpanded("${OUT}/ordermaps/", "_region2"),
+            "${OUT}/ordermaps_region2"
+        );
+        assert_eq!(insert_suffix_unexpanded("", "_region2"), "");
+    }
+
+    #[test]
+    fn timestamped_run_directories() {
+        let time = chrono::Utc
+            .with_ymd_and_hms(2023, 11, 14, 22, 13, 20)
+            .unwrap();
+        assert_eq!(run_directory(&time), "results/2023-11-14_22-13-20");
+
+        let offset = chrono::FixedOffset::east_opt(3600).unwrap();
+        assert_eq!(
+            run_directory(&time.with_timezone(&offset)),
+            "results/2023-11-14_23-13-20"
+        );
+    }
+
+    #[test]
+    fn unique_run_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = format!("{}/2023-11-14_22-13-20", dir.path().display());
+        assert_eq!(unique_directory(&path), path);
+
+        std::fs::create_dir(&path).unwrap();
+        assert_eq!(unique_directory(&path), format!("{}_2", path));
+
+        std::fs::create_dir(format!("{}_2", path)).unwrap();
+        assert_eq!(unique_directory(&path), format!("{}_3", path));
+    }
+}
@@ -0,0 +1,176 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Sections of the form pinned to the top.
+
+use eframe::egui::{self, Id, Ui};
+use serde::{Deserialize, Serialize};
+
+use crate::GuiAnalysis;
+
+/// Collapsible section of the form that can be pinned to the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum PinnableSection {
+    AdvancedInput,
+    AdvancedOutput,
+    FrameSelection,
+    MembraneNormal,
+    Leaflets,
+    Ordermaps,
+    Geometry,
+    EstimateError,
+    OtherOptions,
+    Summary,
+}
+
+impl PinnableSection {
+    /// All sections that can be pinned, in the order in which they appear in the form.
+    pub(crate) const ALL: [PinnableSection; 10] = [
+        PinnableSection::AdvancedInput,
+        PinnableSection::AdvancedOutput,
+        PinnableSection::FrameSelection,
+        PinnableSection::MembraneNormal,
+        PinnableSection::Leaflets,
+        PinnableSection::Ordermaps,
+        PinnableSection::Geometry,
+        PinnableSection::EstimateError,
+        PinnableSection::OtherOptions,
+        PinnableSection::Summary,
+    ];
+
+    /// Get the heading of the section.
+    pub(crate) fn heading(self) -> &'static str {
+        match self {
+            PinnableSection::AdvancedInput => "Advanced input",
+            PinnableSection::AdvancedOutput => "Advanced output",
+            PinnableSection::FrameSelection => "Frame selection",
+            PinnableSection::MembraneNormal => "Membrane normal",
+            PinnableSection::Leaflets => "Leaflet assignment",
+            PinnableSection::Ordermaps => "Order parameter maps",
+            PinnableSection::Geometry => "Region selection",
+            PinnableSection::EstimateError => "Error estimation",
+            PinnableSection::OtherOptions => "Other options",
+            PinnableSection::Summary => "Summary",
+        }
+    }
+
+    /// Get the section with the specified heading, if it can be pinned.
+    pub(crate) fn from_heading(heading: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|section| section.heading() == heading.trim())
+    }
+}
+
+fn pinned_id() -> Id {
+    Id::new("guiorder_pinned_sections")
+}
+
+/// Get the pinned sections in the order in which they were pinned.
+pub(crate) fn pinned_sections(ctx: &egui::Context) -> Vec<PinnableSection> {
+    ctx.data_mut(|d| d.get_persisted::<Vec<PinnableSection>>(pinned_id()))
+        .unwrap_or_default()
+}
+
+/// Check whether the section is pinned.
+pub(crate) fn is_pinned(ctx: &egui::Context, section: PinnableSection) -> bool {
+    pinned_sections(ctx).contains(&section)
+}
+
+/// Pin the section to the top or unpin it.
+pub(crate) fn toggle(ctx: &egui::Context, section: PinnableSection) {
+    let mut pinned = pinned_sections(ctx);
+    toggle_in(&mut pinned, section);
+    ctx.data_mut(|d| d.insert_persisted(pinned_id(), pinned));
+}
+
+fn toggle_in(pinned: &mut Vec<PinnableSection>, section: PinnableSection) {
+    if let Some(position) = pinned.iter().position(|&s| s == section) {
+        pinned.remove(position);
+    } else {
+        pinned.push(section);
+    }
+}
+
+/// Get the sections that are not pinned, in the order in which they appear in the form.
+pub(crate) fn unpinned_sections(pinned: &[PinnableSection]) -> Vec<PinnableSection> {
+    PinnableSection::ALL
+        .into_iter()
+        .filter(|section| !pinned.contains(section))
+        .collect()
+}
+
+/// Add an option for pinning or unpinning the section to the context menu of its header.
+pub(crate) fn pin_menu(response: &egui::Response, section: PinnableSection) {
+    let pinned = is_pinned(&response.ctx, section);
+    response.context_menu(|ui| {
+        let text = if pinned {
+            "📌 Unpin"
+        } else {
+            "📌 Pin to the top"
+        };
+        if ui.button(text).clicked() {
+            toggle(ui.ctx(), section);
+            ui.close_menu();
+        }
+    });
+}
+
+impl GuiAnalysis {
+    /// Specify the options of the section.
+    pub(crate) fn specify_pinnable(&mut self, ui: &mut Ui, section: PinnableSection) {
+        match section {
+            PinnableSection::AdvancedInput => self.specify_advanced_input(ui),
+            PinnableSection::AdvancedOutput => self.specify_advanced_output(ui),
+            PinnableSection::FrameSelection => self.specify_frame_selection(ui),
+            PinnableSection::MembraneNormal => self.specify_membrane_normal(ui),
+            PinnableSection::Leaflets => self.specify_leaflet_classification(ui),
+            PinnableSection::Ordermaps => self.specify_ordermaps(ui),
+            PinnableSection::Geometry => self.specify_geometry(ui),
+            PinnableSection::EstimateError => self.specify_estimate_error(ui),
+            PinnableSection::OtherOptions => self.specify_other_options(ui),
+            PinnableSection::Summary => self.specify_summary(ui),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pin_and_unpin() {
+        let mut pinned = Vec::new();
+        toggle_in(&mut pinned, PinnableSection::Ordermaps);
+        toggle_in(&mut pinned, PinnableSection::FrameSelection);
+        assert_eq!(
+            pinned,
+            vec![PinnableSection::Ordermaps, PinnableSection::FrameSelection]
+        );
+
+        let unpinned = unpinned_sections(&pinned);
+        assert_eq!(unpinned.len(), PinnableSection::ALL.len() - 2);
+        assert_eq!(
+            unpinned[..3],
+            [
+                PinnableSection::AdvancedInput,
+                PinnableSection::AdvancedOutput,
+                PinnableSection::MembraneNormal,
+            ]
+        );
+
+        toggle_in(&mut pinned, PinnableSection::Ordermaps);
+        assert_eq!(pinned, vec![PinnableSection::FrameSelection]);
+    }
+
+    #[test]
+    fn sections_from_headings() {
+        for section in PinnableSection::ALL {
+            assert_eq!(
+                PinnableSection::from_heading(section.heading()),
+                Some(section)
+            );
+        }
+        assert_eq!(PinnableSection::from_heading("Trajectory:  "), None);
+    }
+}
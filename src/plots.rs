@@ -0,0 +1,256 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Plots of the results of the analysis.
+
+use std::path::PathBuf;
+
+use eframe::egui::{self, Rect, RichText, Ui};
+use egui_plot::{Legend, Line, Plot, PlotPoints, Points};
+
+use crate::{
+    dialogs,
+    results::{OrderResults, OrderValue, OrderValues},
+    xvg::Xvg,
+};
+
+/// Single series of points in a plot.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Series {
+    pub name: String,
+    pub points: Vec<[f64; 2]>,
+}
+
+/// Get the order parameter profiles of the individual molecule types.
+/// Each molecule type has a series for the full membrane and, if available, for the individual leaflets.
+/// Atoms (or bonds) are numbered from 1 in the order of the output file.
+pub(crate) fn order_series(results: &OrderResults) -> Vec<Series> {
+    let mut molecules: Vec<&str> = Vec::new();
+    for entry in &results.entries {
+        if !molecules.contains(&entry.molecule.as_str()) {
+            molecules.push(&entry.molecule);
+        }
+    }
+
+    let mut series = Vec::new();
+    for molecule in molecules {
+        let entries = results
+            .entries
+            .iter()
+            .filter(|e| e.molecule == molecule)
+            .collect::<Vec<_>>();
+
+        let leaflets: [(&str, fn(&OrderValues) -> Option<OrderValue>); 3] = [
+            ("full", |v| v.total),
+            ("upper", |v| v.upper),
+            ("lower", |v| v.lower),
+        ];
+
+        for (label, get) in leaflets {
+            let points = entries
+                .iter()
+                .enumerate()
+                .filter_map(|(i, e)| get(&e.values).map(|v| [(i + 1) as f64, v.mean as f64]))
+                .filter(|[_, y]| y.is_finite())
+                .collect::<Vec<_>>();
+
+            if !points.is_empty() {
+                series.push(Series {
+                    name: format!("{} {}", molecule, label),
+                    points,
+                });
+            }
+        }
+    }
+
+    series
+}
+
+/// Show the series as a line plot with markers. Series can be hidden by clicking on them in the legend.
+/// Returns the area occupied by the plot.
+pub(crate) fn line_plot(
+    ui: &mut Ui,
+    id: &str,
+    x_label: &str,
+    y_label: &str,
+    series: &[Series],
+) -> Rect {
+    Plot::new(id)
+        .legend(Legend::default())
+        .x_axis_label(x_label)
+        .y_axis_label(y_label)
+        .height(300.0)
+        .show(ui, |plot_ui| {
+            for s in series {
+                plot_ui.line(Line::new(PlotPoints::from(s.points.clone())).name(&s.name));
+                plot_ui.points(
+                    Points::new(PlotPoints::from(s.points.clone()))
+                        .name(&s.name)
+                        .radius(2.5),
+                );
+            }
+        })
+        .response
+        .rect
+}
+
+/// Plot displayed in the plot window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum PlotTab {
+    #[default]
+    Profiles,
+    Convergence,
+}
+
+/// Window with the plots of the results.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PlotWindow {
+    open: bool,
+    tab: PlotTab,
+    /// Convergence data written by the error estimation, if available.
+    convergence: Option<Xvg>,
+    /// Area of the plot on the screen in the last frame.
+    plot_rect: Option<Rect>,
+    /// File into which the plot should be exported once the screenshot is taken.
+    export_to: Option<PathBuf>,
+}
+
+impl PlotWindow {
+    pub(crate) fn new(convergence: Option<Xvg>) -> Self {
+        Self {
+            convergence,
+            ..Default::default()
+        }
+    }
+
+    /// Open or close the window.
+    pub(crate) fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Render the window, if open.
+    pub(crate) fn render(&mut self, ctx: &egui::Context, results: &OrderResults) {
+        if !self.open {
+            return;
+        }
+
+        let mut open = self.open;
+        egui::Window::new("Plots")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                if self.convergence.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(
+                            &mut self.tab,
+                            PlotTab::Profiles,
+                            "Order parameter profiles",
+                        );
+                        ui.selectable_value(&mut self.tab, PlotTab::Convergence, "Convergence");
+                    });
+                    ui.separator();
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new("Click on a series in the legend to hide it.")
+                            .font(egui::FontId::monospace(12.0))
+                            .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                    );
+
+                    if ui
+                        .button("💾 Export PNG")
+                        .on_hover_ui(|ui| {
+                            ui.label("Save the current view of the plot as a PNG image.");
+                        })
+                        .clicked()
+                    {
+                        dialogs::open_save_file(ui.ctx(), dialogs::id("export_png"));
+                    }
+
+                    if let Some(path) = dialogs::take_file(ui.ctx(), dialogs::id("export_png")) {
+                        self.export_to = Some(path);
+                        ui.ctx()
+                            .send_viewport_cmd(egui::ViewportCommand::Screenshot(
+                                egui::UserData::default(),
+                            ));
+                    }
+                });
+
+                let rect = match (self.tab, &self.convergence) {
+                    (PlotTab::Convergence, Some(convergence)) => line_plot(
+                        ui,
+                        "guiorder_convergence_plot",
+                        &convergence.x_label,
+                        &convergence.y_label,
+                        &convergence.series(),
+                    ),
+                    _ => line_plot(
+                        ui,
+                        "guiorder_order_plot",
+                        "Atom or bond",
+                        "Order parameter",
+                        &order_series(results),
+                    ),
+                };
+                self.plot_rect = Some(rect);
+            });
+
+        self.open = open;
+        self.save_screenshot(ctx);
+    }
+
+    /// Save the plot from the screenshot of the application, if the screenshot is available.
+    fn save_screenshot(&mut self, ctx: &egui::Context) {
+        let Some(path) = &self.export_to else {
+            return;
+        };
+
+        let screenshot = ctx.input(|i| {
+            i.raw.events.iter().find_map(|event| match event {
+                egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        });
+
+        let (Some(screenshot), Some(rect)) = (screenshot, self.plot_rect) else {
+            return;
+        };
+
+        let image = screenshot.region(&rect, Some(ctx.pixels_per_point()));
+        match image::save_buffer(
+            path,
+            image.as_raw(),
+            image.width() as u32,
+            image.height() as u32,
+            image::ColorType::Rgba8,
+        ) {
+            Ok(_) => log::info!("Plot exported into '{}'.", path.display()),
+            Err(e) => log::warn!("Could not export the plot into '{}': {}", path.display(), e),
+        }
+
+        self.export_to = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order_series_from_results() {
+        let results = OrderResults::read("tests/output/order.yaml").unwrap();
+        let series = order_series(&results);
+
+        assert_eq!(series.len(), 3);
+        assert_eq!(series[0].name, "POPC full");
+        assert_eq!(series[1].name, "POPC upper");
+        assert_eq!(series[2].name, "POPC lower");
+
+        assert_eq!(series[0].points.len(), 2);
+        assert_eq!(series[0].points[0][0], 1.0);
+        assert_eq!(series[0].points[1][0], 2.0);
+        approx::assert_relative_eq!(series[1].points[1][1], -0.0996, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,161 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Configuration presets for common setups.
+
+use eframe::egui::Ui;
+
+use crate::{GuiAnalysis, GuiOrderApp};
+
+/// Atomistic simulation of a POPC membrane using the CHARMM force field.
+const CHARMM_POPC: &str = r#"
+structure: system.tpr
+trajectory: md.xtc
+output: order.yaml
+type: !AAOrder
+  heavy_atoms: "resname POPC and element name carbon"
+  hydrogens: "resname POPC and element name hydrogen"
+membrane_normal: z
+leaflets: !Global
+  membrane: "resname POPC"
+  heads: "resname POPC and name P"
+"#;
+
+/// Coarse-grained simulation using the Martini force field.
+const MARTINI: &str = r#"
+structure: system.tpr
+trajectory: md.xtc
+output: order.yaml
+type: !CGOrder
+  beads: "@membrane"
+membrane_normal: z
+leaflets: !Global
+  membrane: "@membrane"
+  heads: "name PO4"
+"#;
+
+/// United-atom simulation using the GROMOS-compatible Berger lipids.
+const GROMOS: &str = r#"
+structure: system.tpr
+trajectory: md.xtc
+output: order.yaml
+type: !UAOrder
+  saturated: "@membrane and element name carbon and not name C15 C34 C24 C25"
+  unsaturated: "@membrane and name C24 C25"
+membrane_normal: z
+leaflets: !Global
+  membrane: "@membrane"
+  heads: "name P"
+"#;
+
+/// All built-in presets with their names.
+const PRESETS: [(&str, &str); 3] = [
+    ("Atomistic POPC (CHARMM)", CHARMM_POPC),
+    ("Coarse-grained Martini", MARTINI),
+    ("United-atom GROMOS", GROMOS),
+];
+
+impl GuiAnalysis {
+    /// Load analysis options from a YAML string. File paths are not loaded.
+    pub(crate) fn from_preset(
+        yaml: &str,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let analysis: gorder::input::Analysis = serde_yaml::from_str(yaml)?;
+        let mut converted = GuiAnalysis::try_from(analysis)?;
+        converted.copy_paths(&GuiAnalysis::default());
+        Ok(converted)
+    }
+
+    /// Use the input and output files of the other analysis.
+    pub(crate) fn copy_paths(&mut self, other: &GuiAnalysis) {
+        self.structure = other.structure.clone();
+        self.trajectory = other.trajectory.clone();
+        self.ndx = other.ndx.clone();
+        self.bonds = other.bonds.clone();
+        self.from_file_normals = other.from_file_normals.clone();
+        self.leaflet_classification_params
+            .copy_input_paths(&other.leaflet_classification_params);
+        self.output = other.output.clone();
+    }
+}
+
+impl GuiOrderApp {
+    /// Replace the analysis options with the preset, keeping the already specified input and output files.
+    fn load_preset(&mut self, yaml: &str) {
+        match GuiAnalysis::from_preset(yaml) {
+            Ok(mut preset) => {
+                preset.copy_paths(&self.analysis);
+                self.analysis = preset;
+            }
+            Err(e) => self.open_error_window(e),
+        }
+    }
+
+    /// Dropdown menu for loading the built-in presets.
+    pub(super) fn presets_menu(&mut self, ui: &mut Ui) {
+        ui.menu_button("📋 Presets", |ui| {
+            for (name, yaml) in PRESETS {
+                if ui
+                    .button(name)
+                    .on_hover_ui(|ui| {
+                        ui.label("Fill in the analysis options for this setup. Input and output files are kept.");
+                    })
+                    .clicked()
+                {
+                    ui.close_menu();
+                    self.load_preset(yaml);
+                }
+            }
+
+            ui.separator();
+            self.user_presets_menu(ui);
+        })
+        .response
+        .on_hover_ui(|ui| {
+            ui.label("Load a configuration for a common setup.");
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis_types::AnalysisType;
+
+    use super::*;
+
+    #[test]
+    fn load_builtin_presets() {
+        let expected = [
+            AnalysisType::AAOrder,
+            AnalysisType::CGOrder,
+            AnalysisType::UAOrder,
+        ];
+
+        for ((name, yaml), analysis_type) in PRESETS.into_iter().zip(expected) {
+            let preset = GuiAnalysis::from_preset(yaml)
+                .unwrap_or_else(|e| panic!("preset '{}' could not be loaded: {}", name, e));
+
+            assert_eq!(preset.analysis_type, analysis_type);
+            assert_eq!(preset.membrane_normal, crate::common::MembraneNormal::Z);
+            assert_eq!(
+                preset.leaflet_classification_method,
+                crate::LeafletClassification::Global
+            );
+            assert!(preset.structure.is_empty());
+            assert!(preset.trajectory.is_empty());
+            assert!(preset.output.output_yaml.is_empty());
+        }
+    }
+
+    #[test]
+    fn preset_keeps_paths() {
+        let mut app = GuiOrderApp::default();
+        app.analysis.structure = String::from("system.gro");
+        app.analysis.output.output_yaml = String::from("results.yaml");
+
+        app.load_preset(MARTINI);
+        assert_eq!(app.analysis.analysis_type, AnalysisType::CGOrder);
+        assert_eq!(app.analysis.structure, "system.gro");
+        assert_eq!(app.analysis.output.output_yaml, "results.yaml");
+    }
+}
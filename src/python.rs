@@ -0,0 +1,359 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Export of the analysis as a Python script using the Python bindings of gorder.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use eframe::egui::{self, Ui};
+use serde_yaml::Value;
+
+use crate::{dialogs, GuiAnalysis, GuiOrderApp, GUIORDER_VERSION};
+
+/// Indentation used in the generated script.
+const INDENT: &str = "    ";
+
+/// Convert a string into a Python string literal.
+fn python_string(string: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in string.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\t' => literal.push_str("\\t"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Convert a YAML number into a Python number.
+fn python_number(number: &serde_yaml::Number) -> String {
+    match number.as_f64() {
+        Some(x) if x.is_nan() => String::from("float(\"nan\")"),
+        Some(x) if x.is_infinite() && x > 0.0 => String::from("float(\"inf\")"),
+        Some(x) if x.is_infinite() => String::from("float(\"-inf\")"),
+        Some(x) if number.is_f64() => {
+            // values stored as f32 are written without the noise introduced by the conversion
+            let short = if (x as f32) as f64 == x {
+                (x as f32).to_string()
+            } else {
+                x.to_string()
+            };
+
+            if short.contains(['.', 'e']) {
+                short
+            } else {
+                format!("{}.0", short)
+            }
+        }
+        _ => number.to_string(),
+    }
+}
+
+/// Name of the keyword argument of the Python API corresponding to the key in the YAML configuration.
+fn python_keyword(key: &str) -> &str {
+    match key {
+        "map" => "ordermap",
+        key => key,
+    }
+}
+
+/// Convert a mapping into a Python dictionary.
+fn python_dict(key: &str, mapping: &serde_yaml::Mapping, level: usize) -> String {
+    let items = mapping
+        .iter()
+        .map(|(k, v)| {
+            format!(
+                "{}: {}",
+                python_value(key, k, level),
+                python_value(key, v, level)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!("{{{}}}", items.join(", "))
+}
+
+/// Convert keyword arguments into a Python call of `function`.
+/// Arguments with `None` values are omitted.
+fn python_call(function: &str, arguments: &Value, level: usize) -> String {
+    let Value::Mapping(mapping) = arguments else {
+        return format!("{}({})", function, python_value("", arguments, level));
+    };
+
+    let inner = INDENT.repeat(level + 1);
+    let arguments = mapping
+        .iter()
+        .filter(|(_, value)| !value.is_null())
+        .filter_map(|(key, value)| {
+            let key = key.as_str()?;
+            Some(format!(
+                "{}{}={},\n",
+                inner,
+                python_keyword(key),
+                python_value(key, value, level + 1)
+            ))
+        })
+        .collect::<String>();
+
+    if arguments.is_empty() {
+        format!("{}()", function)
+    } else {
+        format!("{}(\n{}{})", function, arguments, INDENT.repeat(level))
+    }
+}
+
+/// Convert a value of an enum variant into the corresponding object of the Python API.
+/// `key` is the name of the option the value belongs to.
+fn python_variant(key: &str, variant: &str, value: &Value, level: usize) -> String {
+    match (key, variant) {
+        ("analysis_type", _) => {
+            python_call(&format!("gorder.analysis_types.{}", variant), value, level)
+        }
+        ("leaflets", "Global") => python_call("gorder.leaflets.GlobalClassification", value, level),
+        ("leaflets", "Local") => python_call("gorder.leaflets.LocalClassification", value, level),
+        ("leaflets", "Individual") => {
+            python_call("gorder.leaflets.IndividualClassification", value, level)
+        }
+        ("leaflets", "Clustering") => {
+            python_call("gorder.leaflets.ClusteringClassification", value, level)
+        }
+        // both the file and the inline assignment are passed as the input of the manual classification
+        ("leaflets", "FromFile" | "FromMap") => python_call(
+            "gorder.leaflets.ManualClassification",
+            &manual_arguments(value),
+            level,
+        ),
+        ("leaflets", "FromNdx") => python_call("gorder.leaflets.NdxClassification", value, level),
+        ("geometry", _) => python_call(&format!("gorder.geometry.{}", variant), value, level),
+        ("membrane_normal", "Dynamic") => {
+            python_call("gorder.membrane_normal.DynamicNormal", value, level)
+        }
+        ("frequency", "Every") => format!(
+            "gorder.Frequency.every({})",
+            python_value(key, value, level)
+        ),
+        ("dim", "Manual") => format!(
+            "[{}, {}]",
+            python_value(key, &value["start"], level),
+            python_value(key, &value["end"], level)
+        ),
+        // static membrane normal, reference point or selection, membrane normals from a file
+        _ => python_value(key, value, level),
+    }
+}
+
+/// Get the arguments of the manual leaflet classification.
+/// The assignment (a file or a map) is passed as `input`, the frequency is kept.
+fn manual_arguments(value: &Value) -> Value {
+    let Value::Mapping(mapping) = value else {
+        return value.clone();
+    };
+
+    Value::Mapping(
+        mapping
+            .iter()
+            .map(|(key, value)| match key.as_str() {
+                Some("frequency") => (key.clone(), value.clone()),
+                _ => (Value::from("input"), value.clone()),
+            })
+            .collect(),
+    )
+}
+
+/// Convert a YAML value of the option `key` into a Python expression.
+fn python_value(key: &str, value: &Value, level: usize) -> String {
+    match value {
+        Value::Null => String::from("None"),
+        Value::Bool(true) => String::from("True"),
+        Value::Bool(false) => String::from("False"),
+        Value::Number(number) => python_number(number),
+        Value::String(string) => match (key, string.as_str()) {
+            ("frequency", "Once") => String::from("gorder.Frequency.once()"),
+            ("dim", "Auto") => String::from("None"),
+            ("reference", "Center") => python_string("center"),
+            (
+                "plane" | "orientation" | "membrane_normal",
+                axis @ ("X" | "Y" | "Z" | "XY" | "XZ" | "YZ"),
+            ) => python_string(&axis.to_lowercase()),
+            _ => python_string(string),
+        },
+        Value::Sequence(sequence) => format!(
+            "[{}]",
+            sequence
+                .iter()
+                .map(|item| python_value(key, item, level))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Mapping(mapping) => match key {
+            "map" => python_call("gorder.ordermap.OrderMap", value, level),
+            "estimate_error" => python_call("gorder.estimate_error.EstimateError", value, level),
+            _ => python_dict(key, mapping, level),
+        },
+        Value::Tagged(tagged) => {
+            let tag = tagged.tag.to_string();
+            python_variant(key, tag.trim_start_matches('!'), &tagged.value, level)
+        }
+    }
+}
+
+/// Generate a Python script running the provided analyses one after another.
+pub(crate) fn python_script(
+    analyses: &[GuiAnalysis],
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let mut script = format!(
+        "# Python script generated by 'guiorder v{}' for gorder v{}.\n\nimport gorder\n",
+        GUIORDER_VERSION,
+        gorder::GORDER_VERSION
+    );
+
+    for (i, analysis) in analyses.iter().enumerate() {
+        let converted = gorder::input::Analysis::try_from(analysis)?;
+        let value = serde_yaml::to_value(&converted)?;
+
+        script.push('\n');
+        if analyses.len() > 1 {
+            script.push_str(&format!("# geometry region {}\n", i + 1));
+        }
+        script.push_str(&format!(
+            "analysis = {}\n",
+            python_call("gorder.Analysis", &value, 0)
+        ));
+        script.push_str("results = analysis.run()\nresults.write()\n");
+    }
+
+    Ok(script)
+}
+
+impl GuiOrderApp {
+    /// Export the analysis options into a Python script.
+    fn export_to_python(&mut self, output: &Path) {
+        let result = python_script(&self.analysis.region_analyses()).and_then(|script| {
+            let mut writer = BufWriter::new(File::create(output)?);
+            writer.write_all(script.as_bytes())?;
+            writer.flush()?;
+            Ok(())
+        });
+
+        match result {
+            Ok(_) => self.open_success_window(&format!(
+                "Successfully exported analysis options into a Python script '{}'.",
+                output.display()
+            )),
+            Err(e) => self.open_error_window(e),
+        }
+    }
+
+    /// Button for exporting the analysis into a Python script.
+    pub(super) fn export_python_button(&mut self, ui: &mut Ui, sane: bool) {
+        if ui
+            .add_enabled(sane, egui::Button::new("🐍 Export Python"))
+            .on_hover_ui(|ui| {
+                ui.label("Export the options into a Python script using the gorder Python API.");
+            })
+            .on_disabled_hover_ui(|ui| {
+                ui.label("Cannot export the script: some options are invalid.");
+            })
+            .clicked()
+        {
+            ui.close_menu();
+            dialogs::open_save_file(ui.ctx(), dialogs::id("export_python"));
+        }
+    }
+
+    /// Export the file once it has been specified in the dialog opened by the button.
+    pub(super) fn finish_python_export(&mut self, ctx: &egui::Context) {
+        if let Some(path) = dialogs::take_file(ctx, dialogs::id("export_python")) {
+            self.export_to_python(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn python_literals() {
+        assert_eq!(python_string("@membrane"), "\"@membrane\"");
+        assert_eq!(python_string("say \"hi\"\\"), "\"say \\\"hi\\\"\\\\\"");
+        assert_eq!(
+            python_number(&serde_yaml::Number::from(f64::INFINITY)),
+            "float(\"inf\")"
+        );
+        assert_eq!(python_number(&serde_yaml::Number::from(4)), "4");
+        assert_eq!(
+            python_number(&serde_yaml::Number::from(0.02f32 as f64)),
+            "0.02"
+        );
+        assert_eq!(python_number(&serde_yaml::Number::from(2.0)), "2.0");
+    }
+
+    #[test]
+    fn leaflet_classes() {
+        let value: Value = serde_yaml::from_str("heads: name P\nfrequency: Once").unwrap();
+        for (variant, class) in [
+            ("Global", "GlobalClassification"),
+            ("Local", "LocalClassification"),
+            ("Individual", "IndividualClassification"),
+            ("Clustering", "ClusteringClassification"),
+            ("FromNdx", "NdxClassification"),
+        ] {
+            assert_eq!(
+                python_variant("leaflets", variant, &value, 0),
+                format!(
+                    "gorder.leaflets.{}(\n    heads=\"name P\",\n    frequency=gorder.Frequency.once(),\n)",
+                    class
+                )
+            );
+        }
+
+        let file: Value = serde_yaml::from_str("file: leaflets.yaml\nfrequency: Once").unwrap();
+        assert_eq!(
+            python_variant("leaflets", "FromFile", &file, 0),
+            "gorder.leaflets.ManualClassification(\n    input=\"leaflets.yaml\",\n    frequency=gorder.Frequency.once(),\n)"
+        );
+
+        let map: Value =
+            serde_yaml::from_str("assignment:\n  POPC: [[Upper]]\nfrequency: Once").unwrap();
+        assert_eq!(
+            python_variant("leaflets", "FromMap", &map, 0),
+            "gorder.leaflets.ManualClassification(\n    input={\"POPC\": [[\"Upper\"]]},\n    frequency=gorder.Frequency.once(),\n)"
+        );
+    }
+
+    #[test]
+    fn script_from_config() {
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+
+        let script = python_script(&app.analysis.region_analyses()).unwrap();
+
+        assert!(script.starts_with("# Python script generated by 'guiorder v"));
+        assert!(script.contains("import gorder\n"));
+        assert!(script.contains("    structure=\"tests/pcpepg.tpr\",\n"));
+        assert!(script.contains("    trajectory=[\"tests/pcpepg.xtc\"],\n"));
+        assert!(script.contains("    analysis_type=gorder.analysis_types.AAOrder(\n"));
+        assert!(script.contains("        heavy_atoms=\"resname POPC and name C210 C215\",\n"));
+        assert!(script.contains("    membrane_normal=\"x\",\n"));
+        assert!(script.contains("    leaflets=gorder.leaflets.GlobalClassification(\n"));
+        assert!(script.contains("        frequency=gorder.Frequency.once(),\n"));
+        assert!(script.contains("    ordermap=gorder.ordermap.OrderMap(\n"));
+        assert!(script.contains("        dim=[[0.5, 10.5], None],\n"));
+        assert!(script.contains("        plane=\"xy\",\n"));
+        assert!(script.contains("    estimate_error=gorder.estimate_error.EstimateError(\n"));
+        assert!(script.contains("    geometry=gorder.geometry.Cylinder(\n"));
+        assert!(script.contains("        orientation=\"z\",\n"));
+        assert!(script.contains("    handle_pbc=True,\n"));
+        assert!(!script.contains("bonds="));
+        assert!(script.ends_with("results = analysis.run()\nresults.write()\n"));
+    }
+}
@@ -0,0 +1,487 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Preview of the order parameters calculated by the analysis.
+
+use std::{cmp::Ordering, collections::HashMap, path::Path};
+
+use eframe::egui::{self, RichText, Ui};
+use serde_yaml::Value;
+
+use crate::{plots::PlotWindow, xvg::Xvg, GuiOrderApp};
+
+/// Height of a single row of the results table.
+const ROW_HEIGHT: f32 = 18.0;
+/// Width of the columns of the results table.
+const COLUMN_WIDTHS: [f32; 5] = [70.0, 150.0, 110.0, 110.0, 110.0];
+
+/// Order parameter with an optional error estimate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct OrderValue {
+    pub mean: f32,
+    pub error: Option<f32>,
+}
+
+impl OrderValue {
+    /// Read the order parameter from the YAML value.
+    /// The value is either a plain number or a map with `mean` and `error`.
+    fn parse(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(x) => Some(Self {
+                mean: x.as_f64()? as f32,
+                error: None,
+            }),
+            Value::Mapping(_) => Some(Self {
+                mean: value.get("mean")?.as_f64()? as f32,
+                error: value.get("error").and_then(Value::as_f64).map(|x| x as f32),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Format the order parameter for the table.
+    fn format(&self) -> String {
+        match self.error {
+            Some(error) => format!("{:.4} ± {:.4}", self.mean, error),
+            None => format!("{:.4}", self.mean),
+        }
+    }
+}
+
+/// Order parameters for the full membrane and the individual leaflets.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct OrderValues {
+    pub total: Option<OrderValue>,
+    pub upper: Option<OrderValue>,
+    pub lower: Option<OrderValue>,
+}
+
+impl OrderValues {
+    fn parse(value: &Value) -> Self {
+        let get = |key: &str| value.get(key).and_then(OrderValue::parse);
+        Self {
+            total: get("total"),
+            upper: get("upper"),
+            lower: get("lower"),
+        }
+    }
+}
+
+/// Order parameters of a single atom (atomistic analysis) or bond (coarse-grained analysis).
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OrderEntry {
+    pub molecule: String,
+    pub name: String,
+    pub values: OrderValues,
+}
+
+/// Order parameters read from the output YAML file of gorder.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct OrderResults {
+    /// Average order parameters of the individual molecule types.
+    pub averages: Vec<(String, OrderValues)>,
+    /// Order parameters of the individual atoms or bonds, in the order of the output file.
+    pub entries: Vec<OrderEntry>,
+}
+
+impl OrderResults {
+    /// Parse the content of the output YAML file.
+    pub(crate) fn parse(content: &str) -> Result<Self, String> {
+        let value: Value = serde_yaml::from_str(content).map_err(|e| e.to_string())?;
+        let Value::Mapping(molecules) = value else {
+            return Err(String::from(
+                "the file does not contain any order parameters",
+            ));
+        };
+
+        let mut results = OrderResults::default();
+        for (molecule, content) in &molecules {
+            // skip the average order of the entire system
+            let Some(molecule) = molecule.as_str().filter(|m| *m != "average order") else {
+                continue;
+            };
+
+            if let Some(average) = content.get("average order") {
+                results
+                    .averages
+                    .push((molecule.to_owned(), OrderValues::parse(average)));
+            }
+
+            let Some(Value::Mapping(entries)) = content.get("order parameters") else {
+                continue;
+            };
+
+            for (name, values) in entries {
+                let Some(name) = name.as_str() else {
+                    continue;
+                };
+
+                results.entries.push(OrderEntry {
+                    molecule: molecule.to_owned(),
+                    name: name
+                        .strip_prefix(molecule)
+                        .map(str::trim_start)
+                        .unwrap_or(name)
+                        .to_owned(),
+                    values: OrderValues::parse(values),
+                });
+            }
+        }
+
+        if results.entries.is_empty() {
+            return Err(String::from(
+                "the file does not contain any order parameters",
+            ));
+        }
+
+        Ok(results)
+    }
+
+    /// Read the output YAML file.
+    pub(crate) fn read(path: impl AsRef<Path>) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Self::parse(&content)
+    }
+}
+
+/// Column by which the results table can be sorted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Molecule,
+    Name,
+    Total,
+    Upper,
+    Lower,
+}
+
+impl Column {
+    const ALL: [Column; 5] = [
+        Column::Molecule,
+        Column::Name,
+        Column::Total,
+        Column::Upper,
+        Column::Lower,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            Column::Molecule => "Molecule",
+            Column::Name => "Atom/Bond",
+            Column::Total => "Full membrane",
+            Column::Upper => "Upper leaflet",
+            Column::Lower => "Lower leaflet",
+        }
+    }
+
+    /// Get the order parameter displayed in this column.
+    fn value(self, entry: &OrderEntry) -> Option<OrderValue> {
+        match self {
+            Column::Molecule | Column::Name => None,
+            Column::Total => entry.values.total,
+            Column::Upper => entry.values.upper,
+            Column::Lower => entry.values.lower,
+        }
+    }
+
+    /// Compare two entries by this column.
+    fn compare(self, a: &OrderEntry, b: &OrderEntry) -> Ordering {
+        match self {
+            Column::Molecule => a.molecule.cmp(&b.molecule),
+            Column::Name => crate::paths::natural_cmp(&a.name, &b.name),
+            _ => {
+                let key = |e: &OrderEntry| self.value(e).map(|v| v.mean).unwrap_or(f32::NAN);
+                key(a).total_cmp(&key(b))
+            }
+        }
+    }
+}
+
+/// Window displaying the results of the analysis.
+#[derive(Debug, Clone)]
+pub(crate) struct ResultsViewer {
+    /// Path to the file from which the results were read.
+    file: String,
+    results: OrderResults,
+    /// Indices of the entries in the displayed order.
+    order: Vec<usize>,
+    sort: Option<(Column, bool)>,
+    open: bool,
+    plot: PlotWindow,
+}
+
+impl ResultsViewer {
+    pub(crate) fn new(file: &str, results: OrderResults, convergence: Option<Xvg>) -> Self {
+        let order = (0..results.entries.len()).collect();
+        Self {
+            file: file.to_owned(),
+            results,
+            order,
+            sort: None,
+            open: true,
+            plot: PlotWindow::new(convergence),
+        }
+    }
+
+    /// Sort the entries by the column, keeping them grouped by molecule.
+    /// Clicking the same column again reverses the order.
+    fn sort_by(&mut self, column: Column) {
+        let ascending = match self.sort {
+            Some((current, ascending)) if current == column => !ascending,
+            _ => true,
+        };
+        self.sort = Some((column, ascending));
+
+        let entries = &self.results.entries;
+        // position of the first appearance of the molecule of each entry
+        let mut first = HashMap::new();
+        let group = entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| *first.entry(e.molecule.as_str()).or_insert(i))
+            .collect::<Vec<usize>>();
+
+        self.order.sort_by(|&a, &b| {
+            let within = column.compare(&entries[a], &entries[b]);
+            let within = if ascending { within } else { within.reverse() };
+
+            if column == Column::Molecule {
+                within.then_with(|| a.cmp(&b))
+            } else {
+                group[a].cmp(&group[b]).then(within)
+            }
+        });
+    }
+
+    /// Render the window. Returns `false` if the window has been closed.
+    fn render(&mut self, ctx: &egui::Context) -> bool {
+        let mut open = self.open;
+        egui::Window::new("Results")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(600.0)
+            .default_height(400.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!("Order parameters from '{}'.", self.file))
+                            .font(egui::FontId::monospace(12.0))
+                            .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                    );
+
+                    if ui
+                        .button("📈 Plot")
+                        .on_hover_ui(|ui| {
+                            ui.label("Show the order parameter profiles in a plot.");
+                        })
+                        .clicked()
+                    {
+                        self.plot.toggle();
+                    }
+                });
+
+                for (molecule, average) in &self.results.averages {
+                    if let Some(total) = average.total {
+                        ui.label(
+                            RichText::new(format!("{}: average {}", molecule, total.format()))
+                                .font(egui::FontId::monospace(12.0)),
+                        );
+                    }
+                }
+
+                ui.separator();
+                self.render_header(ui);
+                ui.separator();
+                self.render_rows(ui);
+            });
+
+        self.plot.render(ctx, &self.results);
+
+        self.open = open;
+        open
+    }
+
+    /// Render the clickable header of the table.
+    fn render_header(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            for (column, width) in Column::ALL.into_iter().zip(COLUMN_WIDTHS) {
+                let arrow = match self.sort {
+                    Some((current, true)) if current == column => " ⏶",
+                    Some((current, false)) if current == column => " ⏷",
+                    _ => "",
+                };
+
+                if ui
+                    .add_sized(
+                        [width, ROW_HEIGHT],
+                        egui::Button::new(
+                            RichText::new(format!("{}{}", column.title(), arrow))
+                                .font(egui::FontId::monospace(12.0)),
+                        )
+                        .frame(false),
+                    )
+                    .on_hover_ui(|ui| {
+                        ui.label("Sort by this column.");
+                    })
+                    .clicked()
+                {
+                    self.sort_by(column);
+                }
+            }
+        });
+    }
+
+    /// Render the rows of the table. Only the visible rows are rendered.
+    fn render_rows(&self, ui: &mut Ui) {
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, true])
+            .show_rows(ui, ROW_HEIGHT, self.order.len(), |ui, range| {
+                for &index in &self.order[range] {
+                    let entry = &self.results.entries[index];
+                    ui.horizontal(|ui| {
+                        for (column, width) in Column::ALL.into_iter().zip(COLUMN_WIDTHS) {
+                            let text = match column {
+                                Column::Molecule => entry.molecule.clone(),
+                                Column::Name => entry.name.clone(),
+                                _ => column
+                                    .value(entry)
+                                    .map(|v| v.format())
+                                    .unwrap_or_else(|| String::from("-")),
+                            };
+
+                            ui.add_sized(
+                                [width, ROW_HEIGHT],
+                                egui::Label::new(
+                                    RichText::new(text).font(egui::FontId::monospace(12.0)),
+                                ),
+                            );
+                        }
+                    });
+                }
+            });
+    }
+}
+
+impl GuiOrderApp {
+    /// Read the results of the finished analysis and open a window displaying them.
+    pub(super) fn open_results_window(&mut self) {
+        let analysis = self.last_run();
+        let file = &analysis.output.output_yaml;
+        let results = match OrderResults::read(crate::paths::expand_path(file)) {
+            Ok(results) => results,
+            Err(e) => {
+                log::warn!("Could not read the results from '{}': {}", file, e);
+                return;
+            }
+        };
+
+        // convergence data are only available if the error estimation was performed
+        let convergence = analysis
+            .estimate_error_params
+            .output_convergence()
+            .and_then(|path| Xvg::read(crate::paths::expand_path(path)).ok());
+
+        self.results = Some(ResultsViewer::new(file, results, convergence));
+    }
+
+    /// Render the window with the results, if open.
+    pub(super) fn render_results_window(&mut self, ctx: &egui::Context) {
+        if let Some(viewer) = &mut self.results {
+            if !viewer.render(ctx) {
+                self.results = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_results_file() {
+        let results = OrderResults::read("tests/output/order.yaml").unwrap();
+
+        assert_eq!(results.averages.len(), 1);
+        assert_eq!(results.averages[0].0, "POPC");
+        assert_eq!(
+            results.averages[0].1.total,
+            Some(OrderValue {
+                mean: -0.0646,
+                error: Some(0.0218)
+            })
+        );
+
+        assert_eq!(results.entries.len(), 2);
+        assert_eq!(results.entries[0].molecule, "POPC");
+        assert_eq!(results.entries[0].name, "C210 (64)");
+        assert_eq!(
+            results.entries[1].values.upper,
+            Some(OrderValue {
+                mean: -0.0996,
+                error: Some(0.018)
+            })
+        );
+    }
+
+    #[test]
+    fn parse_results_without_errors() {
+        let content =
+            "POPC:\n  order parameters:\n    POPC NC3 (1) - POPC PO4 (2):\n      total: 0.25\n";
+        let results = OrderResults::parse(content).unwrap();
+
+        assert!(results.averages.is_empty());
+        assert_eq!(results.entries[0].name, "NC3 (1) - POPC PO4 (2)");
+        assert_eq!(
+            results.entries[0].values,
+            OrderValues {
+                total: Some(OrderValue {
+                    mean: 0.25,
+                    error: None
+                }),
+                upper: None,
+                lower: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_results_invalid() {
+        assert!(OrderResults::parse("just text").is_err());
+        assert!(OrderResults::parse("POPC:\n  something: 1\n").is_err());
+    }
+
+    #[test]
+    fn sort_results() {
+        let entry = |molecule: &str, name: &str, total: f32| OrderEntry {
+            molecule: molecule.to_owned(),
+            name: name.to_owned(),
+            values: OrderValues {
+                total: Some(OrderValue {
+                    mean: total,
+                    error: None,
+                }),
+                ..Default::default()
+            },
+        };
+
+        let results = OrderResults {
+            averages: vec![],
+            entries: vec![
+                entry("POPE", "C2", 0.3),
+                entry("POPE", "C1", 0.1),
+                entry("POPC", "C1", 0.2),
+            ],
+        };
+
+        let mut viewer = ResultsViewer::new("order.yaml", results, None);
+        viewer.sort_by(Column::Total);
+        // entries stay grouped by molecule
+        assert_eq!(viewer.order, vec![1, 0, 2]);
+
+        viewer.sort_by(Column::Total);
+        assert_eq!(viewer.order, vec![0, 1, 2]);
+
+        viewer.sort_by(Column::Molecule);
+        assert_eq!(viewer.order, vec![2, 0, 1]);
+    }
+}
@@ -0,0 +1,148 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Library of named selections that can be reused across analyses.
+
+use std::collections::BTreeMap;
+
+use eframe::egui::{self, Id, RichText, Ui};
+use serde::{Deserialize, Serialize};
+
+use crate::GuiAnalysis;
+
+/// Selections provided by guiorder.
+const BUILTIN_SELECTIONS: [(&str, &str); 5] = [
+    ("Phosphorus atoms", "name P"),
+    ("POPC phosphate", "resname POPC and name P"),
+    ("Martini phosphate beads", "name PO4"),
+    ("Martini tail ends", "name C4A C4B"),
+    ("Martini cholesterol hydroxyl", "resname CHOL and name ROH"),
+];
+
+/// Selections saved by the user.
+/// Stored in the persisted egui memory so they survive application restarts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SavedSelections {
+    selections: BTreeMap<String, String>,
+}
+
+impl SavedSelections {
+    fn id() -> Id {
+        Id::new("guiorder_saved_selections")
+    }
+
+    /// Get all selections saved by the user.
+    fn get(ctx: &egui::Context) -> BTreeMap<String, String> {
+        ctx.data_mut(|d| d.get_persisted::<SavedSelections>(Self::id()))
+            .unwrap_or_default()
+            .selections
+    }
+
+    /// Save a selection under the provided name, replacing any selection with the same name.
+    fn save(ctx: &egui::Context, name: &str, query: &str) {
+        ctx.data_mut(|d| {
+            d.get_persisted_mut_or_default::<SavedSelections>(Self::id())
+                .selections
+                .insert(name.to_owned(), query.to_owned());
+        });
+    }
+
+    /// Remove a saved selection.
+    fn remove(ctx: &egui::Context, name: &str) {
+        ctx.data_mut(|d| {
+            d.get_persisted_mut_or_default::<SavedSelections>(Self::id())
+                .selections
+                .remove(name);
+        });
+    }
+}
+
+impl GuiAnalysis {
+    /// Create a menu for inserting a saved selection or saving the current one.
+    pub(crate) fn saved_selections_menu(ui: &mut Ui, target: &mut String) {
+        ui.menu_button("📚", |ui| {
+            ui.label(RichText::new("Built-in").font(egui::FontId::monospace(12.0)));
+            for (name, query) in BUILTIN_SELECTIONS {
+                if Self::selection_entry(ui, name, query) {
+                    *target = query.to_owned();
+                    ui.close_menu();
+                }
+            }
+
+            let saved = SavedSelections::get(ui.ctx());
+            if !saved.is_empty() {
+                ui.separator();
+                ui.label(RichText::new("Saved").font(egui::FontId::monospace(12.0)));
+            }
+
+            for (name, query) in &saved {
+                ui.horizontal(|ui| {
+                    if Self::selection_entry(ui, name, query) {
+                        *target = query.clone();
+                        ui.close_menu();
+                    }
+
+                    if ui
+                        .small_button("🗑")
+                        .on_hover_ui(|ui| {
+                            ui.label("Remove this selection from the library.");
+                        })
+                        .clicked()
+                    {
+                        SavedSelections::remove(ui.ctx(), name);
+                    }
+                });
+            }
+
+            ui.separator();
+            Self::save_selection_form(ui, target);
+        })
+        .response
+        .on_hover_ui(|ui| {
+            ui.label("Use a selection from the library or save the current one.");
+        });
+    }
+
+    /// Button for a single selection in the library. Returns `true` if clicked.
+    fn selection_entry(ui: &mut Ui, name: &str, query: &str) -> bool {
+        ui.button(name)
+            .on_hover_ui(|ui| {
+                ui.label(RichText::new(query).font(egui::FontId::monospace(12.0)));
+            })
+            .clicked()
+    }
+
+    /// Field for naming the current selection and a button for saving it.
+    fn save_selection_form(ui: &mut Ui, target: &str) {
+        let id = Id::new("guiorder_saved_selection_name");
+        let mut name = ui
+            .ctx()
+            .data_mut(|d| d.get_temp::<String>(id))
+            .unwrap_or_default();
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut name)
+                    .hint_text("Name")
+                    .desired_width(120.0),
+            );
+
+            let can_save = !name.trim().is_empty() && !target.trim().is_empty();
+            if ui
+                .add_enabled(can_save, egui::Button::new("💾 Save current"))
+                .on_hover_ui(|ui| {
+                    ui.label("Save the current selection into the library.");
+                })
+                .on_disabled_hover_ui(|ui| {
+                    ui.label("Provide a name and a non-empty selection to save it.");
+                })
+                .clicked()
+            {
+                SavedSelections::save(ui.ctx(), name.trim(), target);
+                name.clear();
+            }
+        });
+
+        ui.ctx().data_mut(|d| d.insert_temp(id, name));
+    }
+}
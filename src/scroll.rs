@@ -0,0 +1,56 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Persistence of the scroll position of the main panel.
+
+use eframe::egui::{self, Id};
+
+/// Salt of the id of the scroll area of the main panel.
+/// Keeps the scroll position stable regardless of the widgets rendered around the scroll area.
+pub(crate) const MAIN_SCROLL_SALT: &str = "guiorder_main_scroll";
+
+fn offset_id() -> Id {
+    Id::new("guiorder_main_scroll_offset")
+}
+
+fn restored_id() -> Id {
+    Id::new("guiorder_main_scroll_restored")
+}
+
+/// Get the remembered scroll offset of the main panel if it should be restored.
+/// The offset is only restored once, in the first frame after the application starts,
+/// so that it never fights with scrolling by the user or with sections being collapsed.
+pub(crate) fn offset_to_restore(ctx: &egui::Context) -> Option<f32> {
+    ctx.data_mut(|d| {
+        if d.get_temp::<bool>(restored_id()).is_some() {
+            return None;
+        }
+
+        d.insert_temp(restored_id(), true);
+        d.get_persisted::<f32>(offset_id())
+    })
+}
+
+/// Remember the scroll offset of the main panel across application restarts.
+pub(crate) fn remember_offset(ctx: &egui::Context, offset: f32) {
+    ctx.data_mut(|d| d.insert_persisted(offset_id(), offset));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restore_offset_once() {
+        let ctx = egui::Context::default();
+        assert_eq!(offset_to_restore(&ctx), None);
+
+        let ctx = egui::Context::default();
+        remember_offset(&ctx, 250.0);
+        assert_eq!(offset_to_restore(&ctx), Some(250.0));
+        assert_eq!(offset_to_restore(&ctx), None);
+
+        remember_offset(&ctx, 300.0);
+        assert_eq!(offset_to_restore(&ctx), None);
+    }
+}
@@ -0,0 +1,227 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Navigation to the sections of the form containing invalid options.
+
+use eframe::egui::{self, Id, Ui};
+
+use crate::{accessibility, GuiAnalysis, GUIORDER_VERSION};
+
+/// Section of the form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Section {
+    /// Name of the section. For collapsible sections, this is their heading.
+    pub name: &'static str,
+    /// Can the section be collapsed?
+    pub collapsible: bool,
+}
+
+impl Section {
+    const fn fixed(name: &'static str) -> Self {
+        Self {
+            name,
+            collapsible: false,
+        }
+    }
+
+    const fn collapsible(name: &'static str) -> Self {
+        Self {
+            name,
+            collapsible: true,
+        }
+    }
+}
+
+/// Section containing the input files and the output YAML file.
+pub(crate) const FILES_SECTION: Section = Section::fixed("Input and output files");
+/// Section containing the analysis type and its parameters.
+pub(crate) const ANALYSIS_TYPE_SECTION: Section = Section::fixed("Analysis type");
+
+/// Get the id under which the state of the collapsible section is stored.
+pub(crate) fn section_id(heading: &str) -> Id {
+    Id::new(("guiorder_section", heading.trim()))
+}
+
+fn title_id() -> Id {
+    Id::new("guiorder_window_title")
+}
+
+/// Get the title of the window reflecting the number of sections with invalid options.
+fn window_title(issues: usize) -> String {
+    let title = format!("guiorder v{}", GUIORDER_VERSION);
+    match issues {
+        0 => title,
+        1 => format!("{} — 1 issue", title),
+        n => format!("{} — {} issues", title, n),
+    }
+}
+
+/// Show the number of sections with invalid options in the title of the window.
+/// The title is only changed if the number changes.
+pub(crate) fn update_title(ctx: &egui::Context, issues: usize) {
+    let title = window_title(issues);
+    let changed = ctx.data_mut(|d| {
+        if d.get_temp::<String>(title_id()).as_deref() == Some(title.as_str()) {
+            false
+        } else {
+            d.insert_temp(title_id(), title.clone());
+            true
+        }
+    });
+
+    if changed {
+        ctx.send_viewport_cmd(egui::ViewportCommand::Title(title));
+    }
+}
+
+fn target_id() -> Id {
+    Id::new("guiorder_scroll_target")
+}
+
+/// Open the section and scroll to it once it is rendered.
+pub(crate) fn go_to(ctx: &egui::Context, section: Section) {
+    ctx.data_mut(|d| {
+        if section.collapsible {
+            d.insert_persisted(section_id(section.name), true);
+        }
+        d.insert_temp(target_id(), String::from(section.name));
+    });
+}
+
+/// Check whether the named section should be scrolled to. The request is consumed.
+pub(crate) fn take_target(ctx: &egui::Context, name: &str) -> bool {
+    ctx.data_mut(|d| {
+        if d.get_temp::<String>(target_id()).as_deref() == Some(name.trim()) {
+            d.remove_temp::<String>(target_id());
+            true
+        } else {
+            false
+        }
+    })
+}
+
+/// Scroll to the current position if the section starting here should be scrolled to.
+pub(crate) fn anchor(ui: &mut Ui, section: Section) {
+    if take_target(ui.ctx(), section.name) {
+        ui.scroll_to_cursor(Some(egui::Align::TOP));
+    }
+}
+
+impl GuiAnalysis {
+    /// Get the first section of the form, in the order of appearance, containing an invalid option.
+    pub(crate) fn first_invalid_section(&self, ctx: &egui::Context) -> Option<Section> {
+        self.invalid_sections(ctx).into_iter().next()
+    }
+
+    /// Get all sections of the form containing an invalid option, in the order of appearance.
+    /// The context is used to check the content of the input files.
+    pub(crate) fn invalid_sections(&self, ctx: &egui::Context) -> Vec<Section> {
+        let files_valid = !self.structure.is_empty()
+            && !self.trajectory.iter().any(|file| file.is_empty())
+            && self.unexpected_trajectory_files().is_empty()
+            && !self.output.output_yaml.is_empty()
+            && self.output_collisions().is_empty();
+
+        [
+            (FILES_SECTION, files_valid),
+            (ANALYSIS_TYPE_SECTION, self.check_analysis_params_sanity()),
+            (
+                Section::collapsible("Frame selection"),
+                self.check_frame_selection_sanity(),
+            ),
+            (
+                Section::collapsible("Membrane normal"),
+                self.check_membrane_normal_sanity(),
+            ),
+            (
+                Section::collapsible("Leaflet assignment"),
+                self.check_leaflets_sanity() && self.check_leaflet_files_sanity(ctx),
+            ),
+            (
+                Section::collapsible("Order parameter maps"),
+                self.check_ordermaps_sanity(),
+            ),
+            (
+                Section::collapsible("Region selection"),
+                self.check_geometry_sanity(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(section, valid)| (!valid).then_some(section))
+        .collect()
+    }
+
+    /// Link to the first section containing an invalid option, if any.
+    pub(crate) fn invalid_section_link(&self, ui: &mut Ui) {
+        let Some(section) = self.first_invalid_section(ui.ctx()) else {
+            return;
+        };
+
+        ui.separator();
+        if ui
+            .link(accessibility::invalid_message(&format!(
+                "Check '{}'",
+                section.name
+            )))
+            .on_hover_ui(|ui| {
+                ui.label("The analysis cannot be run. Click to show the first section with missing or invalid options.");
+            })
+            .clicked()
+        {
+            go_to(ui.ctx(), section);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_invalid_in_form_order() {
+        let ctx = egui::Context::default();
+        let mut analysis = GuiAnalysis::default();
+        assert_eq!(analysis.first_invalid_section(&ctx), Some(FILES_SECTION));
+
+        analysis.structure = String::from("system.tpr");
+        analysis.trajectory = vec![String::from("md.xtc")];
+        analysis.output.output_yaml = String::from("order.yaml");
+        assert_ne!(analysis.first_invalid_section(&ctx), Some(FILES_SECTION));
+
+        let mut app = crate::GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+        assert_eq!(app.analysis.first_invalid_section(&ctx), None);
+    }
+
+    #[test]
+    fn title_with_issues() {
+        let title = format!("guiorder v{}", GUIORDER_VERSION);
+        assert_eq!(window_title(0), title);
+        assert_eq!(window_title(1), format!("{} — 1 issue", title));
+        assert_eq!(window_title(2), format!("{} — 2 issues", title));
+
+        let analysis = GuiAnalysis {
+            analysis_type: crate::analysis_types::AnalysisType::CGOrder,
+            ..Default::default()
+        };
+        assert_eq!(
+            analysis.invalid_sections(&egui::Context::default()),
+            vec![FILES_SECTION, ANALYSIS_TYPE_SECTION]
+        );
+    }
+
+    #[test]
+    fn go_to_section() {
+        let ctx = egui::Context::default();
+        let section = Section::collapsible("Frame selection");
+        go_to(&ctx, section);
+
+        assert_eq!(
+            ctx.data_mut(|d| d.get_persisted::<bool>(section_id("Frame selection"))),
+            Some(true)
+        );
+        assert!(!take_target(&ctx, "Membrane normal"));
+        assert!(take_target(&ctx, "Frame selection"));
+        assert!(!take_target(&ctx, "Frame selection"));
+    }
+}
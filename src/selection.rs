@@ -0,0 +1,598 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Input, validation, and preview of groan selection queries.
+
+use eframe::egui::{self, Id, RichText, Ui};
+
+use crate::{
+    accessibility,
+    structure::{self, SelectionCount},
+    GuiAnalysis,
+};
+
+/// Number of seconds the query must stay unchanged before it is validated.
+const SELECTION_CHECK_DELAY: f64 = 0.5;
+
+/// Examples of groan selection queries, grouped by topic. Each example consists of the query and its description.
+pub(crate) const SELECTION_EXAMPLES: [(&str, &[(&str, &str)]); 5] = [
+    (
+        "Atoms and residues",
+        &[
+            ("resname POPC", "all atoms of residues named POPC"),
+            ("name P C2 C3", "atoms named P, C2, or C3"),
+            ("resid 1 to 10", "atoms of residues 1 to 10"),
+            ("serial 1-100", "atoms with numbers 1 to 100"),
+        ],
+    ),
+    (
+        "Elements",
+        &[
+            ("element name carbon", "carbon atoms"),
+            ("element symbol H", "hydrogen atoms"),
+        ],
+    ),
+    (
+        "Groups",
+        &[
+            ("@membrane", "lipids (built-in group)"),
+            ("@protein", "protein atoms (built-in group)"),
+            ("@water", "water molecules (built-in group)"),
+            (
+                "@'My Group'",
+                "group from the NDX file, quoted if it contains spaces",
+            ),
+        ],
+    ),
+    (
+        "Patterns",
+        &[
+            ("name C2.* C3.*", "atom names starting with C2 or C3"),
+            (
+                "name r'^C[0-9]+$'",
+                "atom names matching a regular expression",
+            ),
+        ],
+    ),
+    (
+        "Operators",
+        &[
+            (
+                "resname POPC and name P",
+                "both conditions must hold (also '&&')",
+            ),
+            (
+                "resname POPC or resname POPE",
+                "either condition (also '||')",
+            ),
+            ("@membrane and not name H.*", "negation (also '!')"),
+            (
+                "(resname POPC or resname POPE) and name C2.*",
+                "parentheses group the conditions",
+            ),
+        ],
+    ),
+];
+
+/// Id of the selection field that was edited most recently.
+fn last_field_id() -> Id {
+    Id::new("guiorder_last_selection_field")
+}
+
+/// Id of the query waiting to be inserted into a selection field.
+fn pending_insert_id() -> Id {
+    Id::new("guiorder_pending_selection_insert")
+}
+
+/// Check whether a selection field has been edited, so an example can be inserted into it.
+pub(crate) fn can_insert(ctx: &egui::Context) -> bool {
+    ctx.data_mut(|d| d.get_temp::<Id>(last_field_id()))
+        .is_some()
+}
+
+/// Insert the query into the selection field that was edited most recently.
+pub(crate) fn insert_into_last_field(ctx: &egui::Context, query: &str) {
+    let Some(field) = ctx.data_mut(|d| d.get_temp::<Id>(last_field_id())) else {
+        return;
+    };
+
+    ctx.data_mut(|d| d.insert_temp(pending_insert_id(), (field, query.to_owned())));
+    ctx.memory_mut(|m| m.request_focus(field));
+}
+
+/// Get the query waiting to be inserted into the selection field, if any.
+fn take_pending_insert(ctx: &egui::Context, field: Id) -> Option<String> {
+    ctx.data_mut(|d| {
+        let (target, query) = d.get_temp::<(Id, String)>(pending_insert_id())?;
+        if target != field {
+            return None;
+        }
+
+        d.remove::<(Id, String)>(pending_insert_id());
+        Some(query)
+    })
+}
+
+/// Operator joining the text appended to a selection query with the existing query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum QueryOperator {
+    #[default]
+    And,
+    Or,
+}
+
+impl QueryOperator {
+    fn keyword(self) -> &'static str {
+        match self {
+            QueryOperator::And => "and",
+            QueryOperator::Or => "or",
+        }
+    }
+}
+
+fn operator_id() -> Id {
+    Id::new("guiorder_query_operator")
+}
+
+/// Get the operator currently used to join the inserted text with the existing query.
+pub(crate) fn query_operator(ctx: &egui::Context) -> QueryOperator {
+    ctx.data_mut(|d| d.get_temp::<QueryOperator>(operator_id()))
+        .unwrap_or_default()
+}
+
+/// Selector for the operator used to join the inserted text with the existing query.
+pub(crate) fn operator_selector(ui: &mut Ui) {
+    let mut operator = query_operator(ui.ctx());
+    let previous = operator;
+
+    ui.horizontal(|ui| {
+        ui.label("Join with:");
+        for variant in [QueryOperator::And, QueryOperator::Or] {
+            ui.selectable_value(&mut operator, variant, variant.keyword());
+        }
+    });
+
+    if operator != previous {
+        ui.ctx()
+            .data_mut(|d| d.insert_temp(operator_id(), operator));
+    }
+}
+
+/// Append the text to the selection query, joined with the operator.
+/// Parts containing `or` are put into parentheses when joined with `and`, so their meaning is kept.
+/// No operator is inserted if the query is blank, if it ends with an operator, or if the text starts with one.
+pub(crate) fn append_to_query(target: &mut String, text: &str, operator: QueryOperator) {
+    if is_blank(target) {
+        *target = text.trim().to_owned();
+        return;
+    }
+
+    let open_end = matches!(
+        tokenize(target)
+            .ok()
+            .and_then(|tokens| tokens.last().cloned()),
+        Some(Token::And | Token::Or | Token::Not | Token::Open)
+    );
+    let open_start = matches!(
+        tokenize(text)
+            .ok()
+            .and_then(|tokens| tokens.first().cloned()),
+        Some(Token::And | Token::Or | Token::Close)
+    );
+    if open_end || open_start {
+        *target = format!("{} {}", target.trim_end(), text.trim());
+        return;
+    }
+
+    let wrap = |part: &str| {
+        if operator == QueryOperator::And && has_top_level_or(part) {
+            format!("({})", part.trim())
+        } else {
+            part.trim().to_owned()
+        }
+    };
+    *target = format!(
+        "{} {} {}",
+        wrap(target.as_str()),
+        operator.keyword(),
+        wrap(text)
+    );
+}
+
+/// Check whether the query contains an `or` operator outside of parentheses.
+fn has_top_level_or(query: &str) -> bool {
+    let Ok(tokens) = tokenize(query) else {
+        return false;
+    };
+
+    let mut depth = 0usize;
+    tokens.iter().any(|token| {
+        match token {
+            Token::Open => depth += 1,
+            Token::Close => depth = depth.saturating_sub(1),
+            Token::Or if depth == 0 => return true,
+            _ => (),
+        }
+        false
+    })
+}
+
+/// Single token of a selection query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Word,
+}
+
+/// Split the query into tokens.
+fn tokenize(query: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    let mut in_word = false;
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '(' => Some(Token::Open),
+            ')' => Some(Token::Close),
+            '!' => Some(Token::Not),
+            '&' | '|' => {
+                if chars.next_if_eq(&c).is_none() {
+                    return Err(format!("single '{}' (use '{}{}')", c, c, c));
+                }
+                Some(if c == '&' { Token::And } else { Token::Or })
+            }
+            '"' | '\'' => {
+                if !chars.by_ref().any(|x| x == c) {
+                    return Err(String::from("unclosed quotes"));
+                }
+                if !in_word {
+                    tokens.push(Token::Word);
+                    in_word = true;
+                }
+                continue;
+            }
+            c if c.is_whitespace() => {
+                in_word = false;
+                continue;
+            }
+            _ => None,
+        };
+
+        match token {
+            Some(token) => {
+                tokens.push(token);
+                in_word = false;
+            }
+            None if !in_word => {
+                // collect the whole word to recognize the word operators
+                let mut word = String::from(c);
+                while let Some(x) =
+                    chars.next_if(|x| !x.is_whitespace() && !"()!&|\"'".contains(*x))
+                {
+                    word.push(x);
+                }
+
+                tokens.push(match word.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => {
+                        in_word = true;
+                        Token::Word
+                    }
+                });
+            }
+            None => (),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Check whether the text is empty or contains only whitespace.
+/// Such selections (and group names) are treated as missing.
+pub(crate) fn is_blank(text: &str) -> bool {
+    text.trim().is_empty()
+}
+
+/// Check that the selection query is syntactically valid.
+/// This only checks the structure of the query (parentheses, quotes, and operators),
+/// not whether the keywords are recognized by groan.
+pub(crate) fn validate_selection(query: &str) -> Result<(), String> {
+    let tokens = tokenize(query)?;
+    if tokens.is_empty() {
+        return Ok(());
+    }
+
+    let mut depth = 0usize;
+    // true if the previous token ended an operand
+    let mut after_operand = false;
+
+    for token in &tokens {
+        match token {
+            Token::Open | Token::Not if after_operand => {
+                return Err(String::from("missing operator before a subquery"))
+            }
+            Token::Open => depth += 1,
+            Token::Not => (),
+            Token::Close => {
+                if !after_operand {
+                    return Err(String::from("empty parentheses or dangling operator"));
+                }
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| String::from("unmatched ')'"))?;
+            }
+            Token::And | Token::Or if !after_operand => {
+                return Err(String::from("operator is missing its left operand"))
+            }
+            Token::And | Token::Or => (),
+            Token::Word => (),
+        }
+
+        after_operand = matches!(token, Token::Word | Token::Close);
+    }
+
+    if !after_operand {
+        return Err(String::from("query ends with an operator"));
+    }
+
+    if depth != 0 {
+        return Err(String::from("unmatched '('"));
+    }
+
+    Ok(())
+}
+
+impl GuiAnalysis {
+    /// Print label and an associated text field for a groan selection query.
+    /// Shows a red marker if the query is not valid and the number of matching atoms if a structure is loaded.
+    pub(crate) fn specify_selection(
+        target: &mut String,
+        ui: &mut Ui,
+        label: &str,
+        hint: &str,
+        required: bool,
+    ) {
+        Self::selection_row(target, ui, label, hint, required, false);
+    }
+
+    /// Same as `specify_selection` but also warns if the selection does not match exactly one atom per residue.
+    pub(crate) fn specify_head_selection(
+        target: &mut String,
+        ui: &mut Ui,
+        label: &str,
+        hint: &str,
+        required: bool,
+    ) {
+        Self::selection_row(target, ui, label, hint, required, true);
+    }
+
+    fn selection_row(
+        target: &mut String,
+        ui: &mut Ui,
+        label: &str,
+        hint: &str,
+        required: bool,
+        one_per_residue: bool,
+    ) {
+        ui.horizontal(|ui| {
+            Self::label_with_hint(ui, label, hint);
+            let field = Self::text_field(target, ui, required);
+            if field.has_focus() {
+                ui.ctx()
+                    .data_mut(|d| d.insert_temp(last_field_id(), field.id));
+            }
+            if let Some(query) = take_pending_insert(ui.ctx(), field.id) {
+                append_to_query(target, &query, query_operator(ui.ctx()));
+            }
+
+            Self::saved_selections_menu(ui, target);
+            Self::insert_group_menu(ui, target);
+
+            if target.trim().is_empty() {
+                return;
+            }
+
+            let error = match Self::debounced_validation(ui.ctx(), field.id, target) {
+                None => return,
+                Some(Err(e)) => Some(e),
+                Some(Ok(_)) => match structure::count_selection(ui.ctx(), target) {
+                    None => None,
+                    Some(Err(e)) => Some(e),
+                    Some(Ok(count)) => {
+                        Self::selection_count_label(ui, count, one_per_residue);
+                        None
+                    }
+                },
+            };
+
+            if let Some(e) = error {
+                accessibility::invalid_marker(ui, "invalid selection").on_hover_ui(|ui| {
+                    ui.label(format!("Invalid selection: {}", e));
+                });
+            }
+        });
+    }
+
+    /// Show the number of atoms matched by the selection.
+    /// The label is orange if no atoms are selected or if one atom per residue is expected but not matched.
+    fn selection_count_label(ui: &mut Ui, count: SelectionCount, one_per_residue: bool) {
+        let gray = egui::Color32::from_rgba_premultiplied(150, 150, 150, 100);
+        let orange = egui::Color32::from_rgb(200, 150, 0);
+
+        let (text, color, hint) = if count.atoms == 0 {
+            (
+                String::from("(0 atoms)"),
+                orange,
+                Some("The selection does not match any atoms in the structure."),
+            )
+        } else if one_per_residue && count.atoms != count.residues {
+            (
+                format!("({} atoms in {} residues)", count.atoms, count.residues),
+                orange,
+                Some("Expected exactly one atom per molecule."),
+            )
+        } else {
+            (format!("({} atoms)", count.atoms), gray, None)
+        };
+
+        let response = ui.label(
+            RichText::new(text)
+                .font(egui::FontId::monospace(12.0))
+                .color(color),
+        );
+
+        if let Some(hint) = hint {
+            response.on_hover_ui(|ui| {
+                ui.label(hint);
+            });
+        }
+    }
+
+    /// Validate the query once it has stayed unchanged for `SELECTION_CHECK_DELAY` seconds.
+    /// Returns `None` if the query has not been validated yet.
+    /// Only the last query of each field is remembered.
+    fn debounced_validation(
+        ctx: &egui::Context,
+        field: Id,
+        query: &str,
+    ) -> Option<Result<(), String>> {
+        let id = Id::new(("guiorder_selection_seen", field));
+        let now = ctx.input(|i| i.time);
+
+        let first_seen = ctx.data_mut(|d| {
+            let seen = d.get_temp_mut_or_insert_with(id, || (query.to_owned(), now));
+            if seen.0 != query {
+                *seen = (query.to_owned(), now);
+            }
+            seen.1
+        });
+        if now - first_seen < SELECTION_CHECK_DELAY {
+            ctx.request_repaint_after(std::time::Duration::from_secs_f64(
+                SELECTION_CHECK_DELAY - (now - first_seen),
+            ));
+            return None;
+        }
+
+        Some(validate_selection(query).map_err(|e| format!("{}.", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_selections() {
+        for query in [
+            "",
+            "@membrane",
+            "resname POPC and name P",
+            "resname POPC && name C2.* C3.*",
+            "(resname POPC or resname POPE) and !(name H.*)",
+            "element name carbon and not serial 1 to 20",
+            "name r'^C[0-9]+' && resid 1-10",
+            "resname \"POPC\" 'POPE'",
+        ] {
+            assert!(validate_selection(query).is_ok(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn valid_examples() {
+        for (_, examples) in SELECTION_EXAMPLES {
+            for (query, _) in examples {
+                assert!(validate_selection(query).is_ok(), "{}", query);
+            }
+        }
+    }
+
+    #[test]
+    fn insert_example() {
+        let ctx = egui::Context::default();
+        assert!(!can_insert(&ctx));
+
+        let field = Id::new("field");
+        let other = Id::new("other");
+        ctx.data_mut(|d| d.insert_temp(last_field_id(), field));
+        assert!(can_insert(&ctx));
+
+        insert_into_last_field(&ctx, "resname POPC");
+        assert_eq!(take_pending_insert(&ctx, other), None);
+        assert_eq!(
+            take_pending_insert(&ctx, field),
+            Some(String::from("resname POPC"))
+        );
+        assert_eq!(take_pending_insert(&ctx, field), None);
+    }
+
+    #[test]
+    fn append_with_operator() {
+        let append = |query: &str, text: &str, operator: QueryOperator| {
+            let mut query = String::from(query);
+            append_to_query(&mut query, text, operator);
+            query
+        };
+
+        assert_eq!(append("  ", "@Upper", QueryOperator::And), "@Upper");
+        assert_eq!(
+            append("name P", "@Upper", QueryOperator::And),
+            "name P and @Upper"
+        );
+        assert_eq!(
+            append("name P ", "@Upper", QueryOperator::Or),
+            "name P or @Upper"
+        );
+        assert_eq!(
+            append("name P", "resname POPC or resname POPE", QueryOperator::And),
+            "name P and (resname POPC or resname POPE)"
+        );
+        assert_eq!(
+            append("(name P || name C2)", "@Upper", QueryOperator::And),
+            "(name P || name C2) and @Upper"
+        );
+        assert_eq!(
+            append("name P", "resname POPC or resname POPE", QueryOperator::Or),
+            "name P or resname POPC or resname POPE"
+        );
+        assert_eq!(
+            append("name P and", "@Upper", QueryOperator::Or),
+            "name P and @Upper"
+        );
+        assert_eq!(
+            append("name P", "and resname POPC", QueryOperator::And),
+            "name P and resname POPC"
+        );
+    }
+
+    #[test]
+    fn invalid_selections() {
+        for query in [
+            "(resname POPC",
+            "resname POPC)",
+            "resname POPC and",
+            "or name P",
+            "resname POPC & name P",
+            "name \"P",
+            "()",
+            "resname POPC and or name P",
+            "resname POPC (name P)",
+            "not",
+        ] {
+            assert!(validate_selection(query).is_err(), "{}", query);
+        }
+    }
+
+    #[test]
+    fn blank_text() {
+        assert!(is_blank(""));
+        assert!(is_blank("   "));
+        assert!(is_blank(" \t\n"));
+        assert!(!is_blank(" name P "));
+    }
+}
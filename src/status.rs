@@ -0,0 +1,106 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Status bar displayed at the bottom of the application.
+
+use std::time::{Duration, Instant};
+
+use eframe::egui::{self, RichText};
+
+use crate::{accessibility, units, GuiOrderApp};
+
+/// Current state of the analysis.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) enum RunStatus {
+    /// No analysis has been run yet.
+    #[default]
+    Ready,
+    /// Analysis is running. Contains the time at which the analysis was started.
+    Running(Instant),
+    /// The last analysis finished successfully. Contains the duration of the analysis.
+    Succeeded(Duration),
+    /// The last analysis failed.
+    Failed,
+}
+
+impl RunStatus {
+    /// Get the text describing the status.
+    fn text(&self) -> String {
+        match self {
+            RunStatus::Ready => String::from("Ready"),
+            RunStatus::Running(start) => {
+                format!("Running… ({})", format_duration(start.elapsed()))
+            }
+            RunStatus::Succeeded(duration) => {
+                format!("Last run succeeded ({})", format_duration(*duration))
+            }
+            RunStatus::Failed => String::from("Last run failed"),
+        }
+    }
+
+    /// Get the color of the status text.
+    fn color(&self) -> egui::Color32 {
+        match self {
+            RunStatus::Ready | RunStatus::Running(_) => {
+                egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)
+            }
+            RunStatus::Succeeded(_) => egui::Color32::from_rgba_premultiplied(0, 150, 0, 100),
+            RunStatus::Failed => egui::Color32::from_rgba_premultiplied(150, 0, 0, 100),
+        }
+    }
+}
+
+/// Format duration as a compact human-readable string, e.g. `2m37s`.
+fn format_duration(duration: Duration) -> String {
+    let total = duration.as_secs();
+    let (hours, minutes, seconds) = (total / 3600, (total % 3600) / 60, total % 60);
+
+    if hours > 0 {
+        format!("{}h{:02}m{:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m{:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+impl GuiOrderApp {
+    /// Render the status bar at the bottom of the application.
+    pub(super) fn render_status_bar(&mut self, ctx: &egui::Context) {
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if let RunStatus::Running(_) = self.status {
+                    ui.spinner();
+                    // keep the elapsed time up to date
+                    ctx.request_repaint_after(Duration::from_secs(1));
+                }
+
+                ui.label(
+                    RichText::new(self.status.text())
+                        .font(egui::FontId::monospace(12.0))
+                        .color(self.status.color()),
+                );
+                self.analysis.invalid_section_link(ui);
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    units::unit_selector(ui);
+                    self.log_button(ui);
+                    accessibility::text_cues_toggle(ui);
+                });
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_durations() {
+        assert_eq!(format_duration(Duration::from_millis(800)), "0s");
+        assert_eq!(format_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(format_duration(Duration::from_secs(157)), "2m37s");
+        assert_eq!(format_duration(Duration::from_secs(3600 + 65)), "1h01m05s");
+    }
+}
@@ -0,0 +1,438 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Background loading of the structure file used for previewing selections.
+//!
+//! The structure is parsed once and shared by all features using it (selection counts,
+//! residue inventory, simulation box). It is parsed again whenever the path or the
+//! modification time of the structure or the NDX file changes, or when a reload is requested.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use eframe::egui::{self, Id};
+use groan_rs::{errors::GroupError, prelude::*};
+
+use crate::{paths, window::strip_ansi_codes};
+
+/// Name of the temporary group used to evaluate selections.
+const PREVIEW_GROUP: &str = "guiorder-preview";
+
+/// Number of seconds after which the modification times of the loaded files are checked again.
+const MODIFIED_CHECK_INTERVAL: f64 = 2.0;
+
+/// Maximal number of evaluated selections kept in the cache.
+const MAX_CACHED_COUNTS: usize = 64;
+
+/// Number of atoms and residues matched by a selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct SelectionCount {
+    pub atoms: usize,
+    pub residues: usize,
+    /// Number of distinct combinations of residue and atom names.
+    pub atom_types: usize,
+    /// Number of distinct residue names.
+    pub residue_types: usize,
+}
+
+/// Residues of a single name present in the structure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ResidueType {
+    pub name: String,
+    /// Number of residues with this name.
+    pub count: usize,
+    /// Names of the atoms of the residues, in the order of the first appearance.
+    pub atoms: Vec<String>,
+}
+
+/// State of the structure loading.
+enum LoadState {
+    Loading,
+    Loaded(Arc<Mutex<System>>, Arc<Vec<ResidueType>>, Option<SimBox>),
+    Failed,
+}
+
+/// Results of the selections evaluated in the loaded structure.
+/// `None` marks a selection that is still being evaluated.
+type SelectionCounts = HashMap<String, Option<Result<SelectionCount, String>>>;
+
+/// Structure loaded (or being loaded) in a background thread.
+#[derive(Clone)]
+struct StructureCache {
+    structure: String,
+    ndx: String,
+    /// Modification times of the structure and the NDX file when the loading started.
+    modified: (Option<SystemTime>, Option<SystemTime>),
+    /// Time at which the modification times were last checked.
+    checked_at: f64,
+    state: Arc<Mutex<LoadState>>,
+    counts: Arc<Mutex<SelectionCounts>>,
+}
+
+/// Get the time of the last modification of the file, if available.
+fn modification_time(path: &str) -> Option<SystemTime> {
+    if path.is_empty() {
+        return None;
+    }
+
+    std::fs::metadata(paths::expand_path(path))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+impl StructureCache {
+    fn id() -> Id {
+        Id::new("guiorder_loaded_structure")
+    }
+
+    /// Check whether the cache contains the specified files.
+    /// The modification times are only checked every `MODIFIED_CHECK_INTERVAL` seconds.
+    fn is_current(&mut self, structure: &str, ndx: &str, now: f64) -> bool {
+        if self.structure != structure || self.ndx != ndx {
+            return false;
+        }
+
+        if now - self.checked_at < MODIFIED_CHECK_INTERVAL {
+            return true;
+        }
+
+        self.checked_at = now;
+        self.modified == (modification_time(structure), modification_time(ndx))
+    }
+
+    /// Load the structure and the NDX file. Errors are silently ignored, the preview is just not shown.
+    fn load(structure: &str, ndx: &str) -> Option<System> {
+        let mut system = System::from_file(paths::expand_path(structure)).ok()?;
+        if !ndx.is_empty() {
+            system.read_ndx(paths::expand_path(ndx)).ok()?;
+        }
+
+        Some(system)
+    }
+
+    /// Get the loaded system, if the loading has finished successfully.
+    fn system(&self) -> Option<Arc<Mutex<System>>> {
+        match &*self.state.lock().unwrap() {
+            LoadState::Loaded(system, _, _) => Some(Arc::clone(system)),
+            _ => None,
+        }
+    }
+}
+
+/// Make sure that the provided structure file is loaded (or is being loaded).
+/// Loading is started in a background thread whenever the structure or the NDX file changes
+/// or one of the files is modified.
+pub(crate) fn request_structure(ctx: &egui::Context, structure: &str, ndx: &str) {
+    let now = ctx.input(|i| i.time);
+    if let Some(mut current) = cache(ctx) {
+        let is_current = current.is_current(structure, ndx, now);
+        ctx.data_mut(|d| d.insert_temp(StructureCache::id(), current));
+        if is_current {
+            return;
+        }
+    }
+
+    if structure.is_empty() {
+        reload_structure(ctx);
+        return;
+    }
+
+    let state = Arc::new(Mutex::new(LoadState::Loading));
+    ctx.data_mut(|d| {
+        d.insert_temp(
+            StructureCache::id(),
+            StructureCache {
+                structure: structure.to_owned(),
+                ndx: ndx.to_owned(),
+                modified: (modification_time(structure), modification_time(ndx)),
+                checked_at: now,
+                state: Arc::clone(&state),
+                counts: Arc::default(),
+            },
+        )
+    });
+
+    let (structure, ndx) = (structure.to_owned(), ndx.to_owned());
+    let ctx = ctx.clone();
+    std::thread::spawn(move || {
+        let loaded = match StructureCache::load(&structure, &ndx) {
+            Some(system) => {
+                let residues = Arc::new(residue_types(&system));
+                let simbox = system.get_box().cloned();
+                LoadState::Loaded(Arc::new(Mutex::new(system)), residues, simbox)
+            }
+            None => LoadState::Failed,
+        };
+
+        *state.lock().unwrap() = loaded;
+        ctx.request_repaint();
+    });
+}
+
+/// Discard the loaded structure. It is loaded again the next time it is requested.
+pub(crate) fn reload_structure(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.remove::<StructureCache>(StructureCache::id()));
+}
+
+/// Get the cached structure, if any.
+fn cache(ctx: &egui::Context) -> Option<StructureCache> {
+    ctx.data_mut(|d| d.get_temp::<StructureCache>(StructureCache::id()))
+}
+
+/// Get the number of atoms matching the selection in the loaded structure.
+/// The selection is evaluated in a background thread.
+/// Returns `None` if no structure is loaded or the selection is still being evaluated.
+/// Returns an error message if the selection is not valid.
+pub(crate) fn count_selection(
+    ctx: &egui::Context,
+    query: &str,
+) -> Option<Result<SelectionCount, String>> {
+    let loaded = cache(ctx)?;
+    let system = loaded.system()?;
+
+    {
+        let mut counts = loaded.counts.lock().unwrap();
+        if let Some(count) = counts.get(query) {
+            return count.clone();
+        }
+
+        if counts.len() >= MAX_CACHED_COUNTS {
+            counts.retain(|_, count| count.is_none());
+        }
+        counts.insert(query.to_owned(), None);
+    }
+
+    let (query, counts, ctx) = (query.to_owned(), Arc::clone(&loaded.counts), ctx.clone());
+    std::thread::spawn(move || {
+        let count = evaluate(&mut system.lock().unwrap(), &query);
+        counts.lock().unwrap().insert(query, Some(count));
+        ctx.request_repaint();
+    });
+
+    None
+}
+
+/// Get the number of atoms matching the selection in the loaded structure.
+/// Unlike `count_selection`, the selection is evaluated immediately, blocking until the result is available.
+/// Only used for checks explicitly requested by the user.
+pub(crate) fn count_selection_now(
+    ctx: &egui::Context,
+    query: &str,
+) -> Option<Result<SelectionCount, String>> {
+    let loaded = cache(ctx)?;
+    let system = loaded.system()?;
+
+    if let Some(Some(count)) = loaded.counts.lock().unwrap().get(query) {
+        return Some(count.clone());
+    }
+
+    let count = evaluate(&mut system.lock().unwrap(), query);
+    loaded
+        .counts
+        .lock()
+        .unwrap()
+        .insert(query.to_owned(), Some(count.clone()));
+    Some(count)
+}
+
+/// Get the residue names present in the loaded structure with their atoms.
+/// Returns `None` if no structure is loaded.
+pub(crate) fn residue_inventory(ctx: &egui::Context) -> Option<Arc<Vec<ResidueType>>> {
+    let loaded = cache(ctx)?;
+    let state = loaded.state.lock().unwrap();
+
+    match &*state {
+        LoadState::Loaded(_, residues, _) => Some(Arc::clone(residues)),
+        _ => None,
+    }
+}
+
+/// Collect the residue names present in the system, in the order of the first appearance.
+fn residue_types(system: &System) -> Vec<ResidueType> {
+    let mut types: Vec<ResidueType> = Vec::new();
+    let mut indices: HashMap<String, usize> = HashMap::new();
+    let mut atom_names: Vec<HashSet<String>> = Vec::new();
+    let mut previous: Option<(usize, &str)> = None;
+
+    for atom in system.atoms_iter() {
+        let (number, name) = (atom.get_residue_number(), atom.get_residue_name());
+        let index = *indices.entry(name.to_owned()).or_insert_with(|| {
+            types.push(ResidueType {
+                name: name.to_owned(),
+                count: 0,
+                atoms: Vec::new(),
+            });
+            atom_names.push(HashSet::new());
+            types.len() - 1
+        });
+
+        // a new residue starts whenever the residue number or name changes
+        if previous != Some((number, name)) {
+            types[index].count += 1;
+            previous = Some((number, name));
+        }
+
+        if atom_names[index].insert(atom.get_atom_name().to_owned()) {
+            types[index].atoms.push(atom.get_atom_name().to_owned());
+        }
+    }
+
+    types
+}
+
+/// Get the center of the simulation box of the loaded structure.
+/// Returns `None` if no structure is loaded or the structure has no simulation box.
+pub(crate) fn box_center(ctx: &egui::Context) -> Option<[f32; 3]> {
+    with_box(ctx, |simbox| {
+        [
+            (simbox.v1x + simbox.v2x + simbox.v3x) / 2.0,
+            (simbox.v1y + simbox.v2y + simbox.v3y) / 2.0,
+            (simbox.v1z + simbox.v2z + simbox.v3z) / 2.0,
+        ]
+    })
+}
+
+/// Get the lengths of the simulation box of the loaded structure along the x, y, and z axes.
+/// Returns `None` if no structure is loaded or the structure has no simulation box.
+pub(crate) fn box_size(ctx: &egui::Context) -> Option<[f32; 3]> {
+    with_box(ctx, |simbox| [simbox.v1x, simbox.v2y, simbox.v3z])
+}
+
+/// Check whether the simulation box of the loaded structure is orthogonal.
+/// Returns `None` if no structure is loaded or the structure has no simulation box.
+pub(crate) fn box_orthogonal(ctx: &egui::Context) -> Option<bool> {
+    with_box(ctx, |simbox| {
+        is_orthogonal([
+            simbox.v1y, simbox.v1z, simbox.v2x, simbox.v2z, simbox.v3x, simbox.v3y,
+        ])
+    })
+}
+
+/// Check that all off-diagonal elements of the box vectors are (almost) zero.
+fn is_orthogonal(off_diagonal: [f32; 6]) -> bool {
+    off_diagonal.iter().all(|value| value.abs() < 1e-4)
+}
+
+/// Apply the function to the simulation box of the loaded structure.
+fn with_box<T>(ctx: &egui::Context, f: impl FnOnce(&SimBox) -> T) -> Option<T> {
+    let loaded = cache(ctx)?;
+    let state = loaded.state.lock().unwrap();
+
+    let LoadState::Loaded(_, _, simbox) = &*state else {
+        return None;
+    };
+
+    simbox.as_ref().map(f)
+}
+
+/// Select atoms using the query and count them.
+fn evaluate(system: &mut System, query: &str) -> Result<SelectionCount, String> {
+    match system.group_create(PREVIEW_GROUP, query) {
+        Ok(_) | Err(GroupError::AlreadyExistsWarning(_)) => (),
+        Err(e) => return Err(strip_ansi_codes(&e.to_string())),
+    }
+
+    let atoms = system
+        .group_get_n_atoms(PREVIEW_GROUP)
+        .map_err(|e| strip_ansi_codes(&e.to_string()))?;
+
+    let mut residues = HashSet::new();
+    let mut atom_types = HashSet::new();
+    let mut residue_types = HashSet::new();
+    for atom in system
+        .group_iter(PREVIEW_GROUP)
+        .map_err(|e| strip_ansi_codes(&e.to_string()))?
+    {
+        residues.insert(atom.get_residue_number());
+        atom_types.insert((atom.get_residue_name(), atom.get_atom_name()));
+        residue_types.insert(atom.get_residue_name());
+    }
+
+    Ok(SelectionCount {
+        atoms,
+        residues: residues.len(),
+        atom_types: atom_types.len(),
+        residue_types: residue_types.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn residue_inventory_of_structure() {
+        let system = System::from_file("tests/pcpepg.tpr").unwrap();
+        let residues = residue_types(&system);
+
+        let popc = residues.iter().find(|r| r.name == "POPC").unwrap();
+        assert!(popc.count > 0);
+        assert!(popc.atoms.contains(&String::from("P")));
+        assert_eq!(
+            residues.iter().map(|r| r.count).sum::<usize>(),
+            system
+                .atoms_iter()
+                .map(|atom| (atom.get_residue_number(), atom.get_residue_name()))
+                .collect::<Vec<_>>()
+                .windows(2)
+                .filter(|pair| pair[0] != pair[1])
+                .count()
+                + 1
+        );
+    }
+
+    #[test]
+    fn cache_outdated_after_modification() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("system.gro");
+        std::fs::write(&path, "").unwrap();
+        let path = path.display().to_string();
+
+        let mut cache = StructureCache {
+            structure: path.clone(),
+            ndx: String::new(),
+            modified: (modification_time(&path), None),
+            checked_at: 0.0,
+            state: Arc::new(Mutex::new(LoadState::Loading)),
+            counts: Arc::default(),
+        };
+        assert!(cache.is_current(&path, "", 10.0));
+        assert!(!cache.is_current(&path, "index.ndx", 10.0));
+        assert!(!cache.is_current("other.gro", "", 10.0));
+
+        cache.modified.0 = Some(SystemTime::UNIX_EPOCH);
+        // not checked again before the interval passes
+        assert!(cache.is_current(&path, "", 11.0));
+        assert!(!cache.is_current(&path, "", 20.0));
+    }
+
+    #[test]
+    fn count_selection_in_background() {
+        let ctx = egui::Context::default();
+        request_structure(&ctx, "tests/pcpepg.tpr", "");
+
+        let mut count = None;
+        for _ in 0..500 {
+            count = count_selection(&ctx, "name P");
+            if count.is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+
+        assert!(count.unwrap().unwrap().atoms > 0);
+    }
+
+    #[test]
+    fn reload_discards_structure() {
+        let ctx = egui::Context::default();
+        request_structure(&ctx, "tests/pcpepg.tpr", "");
+        assert!(cache(&ctx).is_some());
+
+        reload_structure(&ctx);
+        assert!(cache(&ctx).is_none());
+    }
+}
@@ -0,0 +1,150 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Suggestions of output paths based on the input files.
+
+use std::path::Path;
+
+use eframe::egui::{self, Id, Ui};
+
+use crate::GuiAnalysis;
+
+/// Name of the output YAML file suggested next to the structure file.
+const SUGGESTED_YAML: &str = "order.yaml";
+
+/// Get the output YAML file suggested for the structure file: `order.yaml` in the same directory.
+fn suggested_output_yaml(structure: &str) -> Option<String> {
+    if structure.is_empty() {
+        return None;
+    }
+
+    match Path::new(structure).parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => {
+            Some(format!("{}/{}", parent.display(), SUGGESTED_YAML))
+        }
+        _ => Some(SUGGESTED_YAML.to_owned()),
+    }
+}
+
+/// Replace the extension of the file name.
+fn with_extension(path: &str, extension: &str) -> String {
+    Path::new(path)
+        .with_extension(extension)
+        .display()
+        .to_string()
+}
+
+/// Fill the target with the new suggestion, unless it contains a value provided by the user.
+/// The target can be overwritten if it is empty or if it holds the previous suggestion.
+/// Returns the suggestion that is currently in the target.
+fn apply_suggestion(
+    target: &mut String,
+    previous: Option<&str>,
+    suggestion: Option<String>,
+) -> Option<String> {
+    let replaceable = target.is_empty() || previous == Some(target.as_str());
+    match suggestion {
+        Some(suggestion) if replaceable => {
+            *target = suggestion.clone();
+            Some(suggestion)
+        }
+        _ => None,
+    }
+}
+
+impl GuiAnalysis {
+    /// Suggest the output YAML file in the directory of the structure file.
+    pub(crate) fn suggest_output_yaml(&mut self, ctx: &egui::Context) {
+        let id = Id::new("guiorder_suggested_output_yaml");
+        let previous = ctx.data_mut(|d| d.get_temp::<String>(id));
+
+        let suggestion = suggested_output_yaml(&self.structure);
+        if suggestion == previous {
+            return;
+        }
+
+        match apply_suggestion(
+            &mut self.output.output_yaml,
+            previous.as_deref(),
+            suggestion,
+        ) {
+            Some(current) => ctx.data_mut(|d| d.insert_temp(id, current)),
+            None => ctx.data_mut(|d| d.remove::<String>(id)),
+        }
+    }
+
+    /// Button filling the empty CSV and table output paths based on the output YAML file.
+    pub(crate) fn suggest_tables_button(&mut self, ui: &mut Ui) {
+        let yaml = &self.output.output_yaml;
+        if yaml.is_empty()
+            || (!self.output.output_csv.is_empty() && !self.output.output_tab.is_empty())
+        {
+            return;
+        }
+
+        if ui
+            .button("💡 Fill from the output YAML")
+            .on_hover_ui(|ui| {
+                ui.label("Write the CSV and table files next to the output YAML file.");
+            })
+            .clicked()
+        {
+            if self.output.output_csv.is_empty() {
+                self.output.output_csv = with_extension(yaml, "csv");
+            }
+            if self.output.output_tab.is_empty() {
+                self.output.output_tab = with_extension(yaml, "tab");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggest_yaml_next_to_structure() {
+        assert_eq!(
+            suggested_output_yaml("/data/sims/md.tpr").as_deref(),
+            Some("/data/sims/order.yaml")
+        );
+        assert_eq!(
+            suggested_output_yaml("md.tpr").as_deref(),
+            Some("order.yaml")
+        );
+        assert_eq!(suggested_output_yaml(""), None);
+    }
+
+    #[test]
+    fn keep_user_paths() {
+        let mut target = String::new();
+        let current = apply_suggestion(&mut target, None, Some(String::from("a/order.yaml")));
+        assert_eq!(target, "a/order.yaml");
+
+        // the suggestion follows the structure until the user edits it
+        let current = apply_suggestion(
+            &mut target,
+            current.as_deref(),
+            Some(String::from("b/order.yaml")),
+        );
+        assert_eq!(target, "b/order.yaml");
+
+        target = String::from("results/my.yaml");
+        assert_eq!(
+            apply_suggestion(
+                &mut target,
+                current.as_deref(),
+                Some(String::from("c/order.yaml"))
+            ),
+            None
+        );
+        assert_eq!(target, "results/my.yaml");
+    }
+
+    #[test]
+    fn table_extensions() {
+        assert_eq!(with_extension("out/order.yaml", "csv"), "out/order.csv");
+        assert_eq!(with_extension("order", "tab"), "order.tab");
+    }
+}
@@ -0,0 +1,105 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Human-readable summary of the configured analysis.
+
+use eframe::egui::{self, RichText, Ui};
+
+use crate::{
+    units::{LengthUnit, TimeUnit},
+    GuiAnalysis,
+};
+
+impl GuiAnalysis {
+    /// Get the rows of the summary of the analysis as pairs of names and descriptions.
+    /// Lengths and times are written in the provided units.
+    fn summary_rows(&self, length: LengthUnit, time: TimeUnit) -> Vec<(&'static str, String)> {
+        let mut rows = self.analysis_type_summary();
+        rows.extend([
+            ("Leaflets", self.leaflets_summary()),
+            ("Membrane normal", self.membrane_normal_summary(length)),
+            ("Region", self.geometry_summary(length)),
+            ("Ordermaps", self.ordermaps_summary(length)),
+            ("Error estimation", self.estimate_error_summary()),
+            ("Frames", self.frame_selection_summary(time)),
+            ("Threads", self.other_params.n_threads.to_string()),
+        ]);
+        rows
+    }
+
+    /// Collapsible read-only summary of the whole analysis.
+    pub(crate) fn specify_summary(&self, ui: &mut Ui) {
+        let length = LengthUnit::get(ui.ctx());
+        let time = TimeUnit::get(ui.ctx());
+        Self::collapsing_with_warning(ui, "Summary", false, true, |ui| {
+            egui::Grid::new("guiorder_summary")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (name, description) in self.summary_rows(length, time) {
+                        ui.label(
+                            RichText::new(format!("{}:", name)).font(egui::FontId::monospace(12.0)),
+                        );
+
+                        if description.is_empty() {
+                            ui.label(
+                                RichText::new("(not specified)")
+                                    .font(egui::FontId::monospace(12.0))
+                                    .color(egui::Color32::from_rgba_premultiplied(150, 0, 0, 100)),
+                            );
+                        } else {
+                            ui.label(
+                                RichText::new(description).font(egui::FontId::monospace(12.0)),
+                            );
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        units::{LengthUnit, TimeUnit},
+        GuiOrderApp,
+    };
+
+    #[test]
+    fn summarize_imported() {
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+
+        let rows = app
+            .analysis
+            .summary_rows(LengthUnit::Nanometer, TimeUnit::Picosecond);
+        assert_eq!(rows[0], ("Analysis type", String::from("atomistic")));
+        assert!(rows.iter().all(|(_, description)| !description.is_empty()));
+        assert!(rows.contains(&("Threads", String::from("4"))));
+    }
+
+    #[test]
+    fn summarize_default() {
+        let rows =
+            crate::GuiAnalysis::default().summary_rows(LengthUnit::Nanometer, TimeUnit::Picosecond);
+        assert!(rows.contains(&("Heavy atoms", String::new())));
+        assert!(rows.contains(&("Leaflets", String::from("not assigned"))));
+        assert!(rows.contains(&("Ordermaps", String::from("off"))));
+    }
+
+    #[test]
+    fn summarize_in_selected_units() {
+        let mut analysis = crate::GuiAnalysis::default();
+        analysis.frame_selection_params.begin = 1500.0;
+        analysis.frame_selection_params.end = 250000.0;
+
+        let rows = analysis.summary_rows(LengthUnit::Nanometer, TimeUnit::Nanosecond);
+        let frames = rows.iter().find(|(name, _)| *name == "Frames").unwrap();
+        assert!(frames.1.starts_with("1.5 to 250 ns, step"));
+
+        let rows = analysis.summary_rows(LengthUnit::Nanometer, TimeUnit::Picosecond);
+        let frames = rows.iter().find(|(name, _)| *name == "Frames").unwrap();
+        assert!(frames.1.starts_with("1 500 to 250 000 ps, step"));
+    }
+}
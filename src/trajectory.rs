@@ -0,0 +1,509 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Background scanning of the trajectory to determine its time span and number of frames.
+//!
+//! Scans report the number of frames read so far and are aborted when the trajectory changes
+//! or the application is closed. Results are kept for the input files and their modification times,
+//! so modified files are scanned again.
+
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::SystemTime,
+};
+
+use eframe::egui::{self, Id};
+use groan_rs::prelude::*;
+
+use crate::paths;
+
+/// Number of frames after which the progress of the scan is reported.
+const PROGRESS_INTERVAL: usize = 500;
+
+/// Number of seconds after which the trajectory files and their modification times are checked again.
+const TRAJECTORY_CHECK_INTERVAL: f64 = 2.0;
+
+/// Time span and number of frames of a trajectory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct TrajectorySpan {
+    /// Time of the first frame in ps.
+    pub start: f32,
+    /// Time of the last frame in ps.
+    pub end: f32,
+    pub frames: usize,
+}
+
+impl TrajectorySpan {
+    /// Estimate the number of frames that are analyzed when reading every `step`-th frame
+    /// with time between `begin` and `end` (in ps). Assumes equidistant frames.
+    pub(crate) fn analyzed_frames(&self, begin: f32, end: f32, step: usize) -> usize {
+        let (first, last) = (begin.max(self.start), end.min(self.end));
+        if self.frames == 0 || first > last {
+            return 0;
+        }
+
+        let in_range = if self.frames == 1 || self.end <= self.start {
+            self.frames
+        } else {
+            let dt = (self.end - self.start) as f64 / (self.frames - 1) as f64;
+            (((last - first) as f64 / dt).floor() as usize + 1).min(self.frames)
+        };
+
+        in_range.div_ceil(step.max(1))
+    }
+
+    /// Combine the spans of trajectory files that are read one after another.
+    fn merge(spans: impl IntoIterator<Item = TrajectorySpan>) -> Option<Self> {
+        spans.into_iter().reduce(|acc, span| TrajectorySpan {
+            start: acc.start.min(span.start),
+            end: acc.end.max(span.end),
+            frames: acc.frames + span.frames,
+        })
+    }
+}
+
+/// State of the trajectory scan.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ScanState {
+    /// The scan is running. Contains the number of frames read so far.
+    Scanning(usize),
+    Done(TrajectorySpan),
+    Failed,
+}
+
+/// Input files identifying a scan, together with their modification times.
+type ScanKey = (String, Vec<(String, Option<SystemTime>)>);
+
+/// Scan performed in the background.
+#[derive(Clone)]
+struct Scan {
+    state: Arc<Mutex<ScanState>>,
+    /// Set to abort the scan.
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Scan {
+    fn is_running(&self) -> bool {
+        matches!(*self.state.lock().unwrap(), ScanState::Scanning(_))
+    }
+}
+
+/// Input files of the form resolved into the key of a scan.
+#[derive(Clone)]
+struct ResolvedInput {
+    structure: String,
+    trajectory: Vec<String>,
+    /// Time at which the files were resolved.
+    checked_at: f64,
+    /// `None` if the input files are not valid.
+    key: Option<ScanKey>,
+}
+
+/// Results of all trajectory scans performed in this session.
+#[derive(Clone, Default)]
+struct Scans {
+    scans: HashMap<ScanKey, Scan>,
+    resolved: Option<ResolvedInput>,
+}
+
+impl Scans {
+    fn id() -> Id {
+        Id::new("guiorder_trajectory_scans")
+    }
+
+    /// Get the key of the scan for the input files.
+    /// The files are only resolved again if the input changes or every `TRAJECTORY_CHECK_INTERVAL` seconds.
+    fn scan_key(&mut self, structure: &str, trajectory: &[String], now: f64) -> Option<ScanKey> {
+        if let Some(resolved) = &self.resolved {
+            if resolved.structure == structure
+                && resolved.trajectory == trajectory
+                && now - resolved.checked_at < TRAJECTORY_CHECK_INTERVAL
+            {
+                return resolved.key.clone();
+            }
+        }
+
+        let key = resolve_key(structure, trajectory);
+        if let Some(key) = &key {
+            self.evict_outdated(key);
+        }
+
+        self.resolved = Some(ResolvedInput {
+            structure: structure.to_owned(),
+            trajectory: trajectory.to_vec(),
+            checked_at: now,
+            key: key.clone(),
+        });
+        key
+    }
+
+    /// Forget the finished scans of the same files as `current` that have since been modified.
+    fn evict_outdated(&mut self, current: &ScanKey) {
+        let same_files = |key: &ScanKey| {
+            key.0 == current.0
+                && key.1.len() == current.1.len()
+                && key.1.iter().zip(&current.1).all(|(a, b)| a.0 == b.0)
+        };
+
+        self.scans
+            .retain(|key, scan| key == current || !same_files(key) || scan.is_running());
+    }
+
+    /// Abort and forget all running scans, except for the one with the specified key.
+    fn cancel_running(&mut self, keep: Option<&ScanKey>) {
+        self.scans.retain(|key, scan| {
+            if Some(key) == keep || !scan.is_running() {
+                return true;
+            }
+
+            scan.cancelled.store(true, Ordering::Relaxed);
+            false
+        });
+    }
+}
+
+/// Abort all running trajectory scans, e.g., when the application is closed.
+pub(crate) fn cancel_scans(ctx: &egui::Context) {
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<Scans>(Scans::id())
+            .cancel_running(None)
+    });
+}
+
+/// Get the time of the last modification of the file, if available.
+fn modification_time(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Get the key of the scan for the structure and the trajectory files.
+/// Returns `None` if the input files are not valid.
+fn resolve_key(structure: &str, trajectory: &[String]) -> Option<ScanKey> {
+    if structure.is_empty() || !Path::new(&paths::expand_path(structure)).is_file() {
+        return None;
+    }
+
+    let files = expand_trajectories(trajectory).filter(|files| !files.is_empty())?;
+    Some((
+        structure.to_owned(),
+        files
+            .into_iter()
+            .map(|file| {
+                let modified = modification_time(&file);
+                (file, modified)
+            })
+            .collect(),
+    ))
+}
+
+/// Expand the paths to the trajectory files, including glob patterns.
+fn expand_trajectories(trajectory: &[String]) -> Option<Vec<String>> {
+    let mut expanded = Vec::new();
+    for file in trajectory {
+        let file = paths::expand_path(file);
+        if paths::is_glob_pattern(&file) {
+            expanded.extend(paths::expand_glob(&file).ok()?);
+        } else {
+            expanded.push(file);
+        }
+    }
+
+    expanded
+        .iter()
+        .all(|file| Path::new(file).is_file())
+        .then_some(expanded)
+}
+
+/// Record the time of a frame and report the progress every `PROGRESS_INTERVAL` frames.
+/// Returns `false` if the scan should be aborted.
+fn record_frame(
+    times: &mut Vec<f32>,
+    time: f32,
+    read_before: usize,
+    progress: &mut impl FnMut(usize) -> bool,
+) -> bool {
+    times.push(time);
+    times.len() % PROGRESS_INTERVAL != 0 || progress(read_before + times.len())
+}
+
+/// Read all frames of a single trajectory file. `read_before` frames were read from the previous files.
+/// `progress` is called with the total number of frames read so far and aborts the scan by returning `false`.
+fn scan_file(
+    system: &mut System,
+    file: &str,
+    read_before: usize,
+    progress: &mut impl FnMut(usize) -> bool,
+) -> Result<TrajectorySpan, Box<dyn std::error::Error + Send + Sync>> {
+    let extension = Path::new(file)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase());
+
+    let mut times = Vec::new();
+    match extension.as_deref() {
+        Some("xtc") => {
+            for frame in system.xtc_iter(file)? {
+                let time = frame?.get_simulation_time();
+                if !record_frame(&mut times, time, read_before, progress) {
+                    return Err(Box::from("scan aborted"));
+                }
+            }
+        }
+        Some("trr") => {
+            for frame in system.trr_iter(file)? {
+                let time = frame?.get_simulation_time();
+                if !record_frame(&mut times, time, read_before, progress) {
+                    return Err(Box::from("scan aborted"));
+                }
+            }
+        }
+        _ => return Err(Box::from("unsupported trajectory format")),
+    }
+
+    Ok(TrajectorySpan {
+        start: times.first().copied().ok_or("empty trajectory")?,
+        end: times.last().copied().ok_or("empty trajectory")?,
+        frames: times.len(),
+    })
+}
+
+/// Scan all the trajectory files. Returns `None` if the files can not be read or the scan is aborted.
+fn scan(
+    structure: &str,
+    trajectory: &[String],
+    mut progress: impl FnMut(usize) -> bool,
+) -> Option<TrajectorySpan> {
+    let mut system = System::from_file(paths::expand_path(structure)).ok()?;
+    let mut spans = Vec::with_capacity(trajectory.len());
+    let mut read = 0;
+    for file in trajectory {
+        let span = scan_file(&mut system, file, read, &mut progress).ok()?;
+        read += span.frames;
+        spans.push(span);
+    }
+
+    TrajectorySpan::merge(spans)
+}
+
+/// Get the span of the trajectory. Scanning is started in a background thread
+/// the first time this is called for the given structure and trajectory files,
+/// and scans of other files that are still running are aborted.
+/// Returns `None` if the input files are not valid.
+pub(crate) fn trajectory_span(
+    ctx: &egui::Context,
+    structure: &str,
+    trajectory: &[String],
+) -> Option<ScanState> {
+    let now = ctx.input(|i| i.time);
+    let (key, existing) = ctx.data_mut(|d| {
+        let scans = d.get_temp_mut_or_default::<Scans>(Scans::id());
+        let key = scans.scan_key(structure, trajectory, now)?;
+        scans.cancel_running(Some(&key));
+        let existing = scans.scans.get(&key).cloned();
+        Some((key, existing))
+    })?;
+    if let Some(scan) = existing {
+        return Some(*scan.state.lock().unwrap());
+    }
+
+    let scan_handle = Scan {
+        state: Arc::new(Mutex::new(ScanState::Scanning(0))),
+        cancelled: Arc::new(AtomicBool::new(false)),
+    };
+    let files = key
+        .1
+        .iter()
+        .map(|(file, _)| file.clone())
+        .collect::<Vec<_>>();
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<Scans>(Scans::id())
+            .scans
+            .insert(key, scan_handle.clone())
+    });
+
+    let structure = structure.to_owned();
+    let ctx = ctx.clone();
+    std::thread::spawn(move || {
+        let Scan { state, cancelled } = scan_handle;
+        let scanned = scan(&structure, &files, |read| {
+            *state.lock().unwrap() = ScanState::Scanning(read);
+            ctx.request_repaint();
+            !cancelled.load(Ordering::Relaxed)
+        });
+
+        if cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+
+        *state.lock().unwrap() = match scanned {
+            Some(span) => ScanState::Done(span),
+            None => ScanState::Failed,
+        };
+        ctx.request_repaint();
+    });
+
+    Some(ScanState::Scanning(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_spans() {
+        let merged = TrajectorySpan::merge([
+            TrajectorySpan {
+                start: 0.0,
+                end: 1000.0,
+                frames: 11,
+            },
+            TrajectorySpan {
+                start: 1100.0,
+                end: 2000.0,
+                frames: 10,
+            },
+        ]);
+
+        assert_eq!(
+            merged,
+            Some(TrajectorySpan {
+                start: 0.0,
+                end: 2000.0,
+                frames: 21
+            })
+        );
+        assert_eq!(TrajectorySpan::merge(Vec::new()), None);
+    }
+
+    #[test]
+    fn estimate_analyzed_frames() {
+        let span = TrajectorySpan {
+            start: 0.0,
+            end: 500_000.0,
+            frames: 5001,
+        };
+
+        assert_eq!(span.analyzed_frames(0.0, f32::INFINITY, 1), 5001);
+        assert_eq!(span.analyzed_frames(0.0, f32::INFINITY, 2), 2501);
+        assert_eq!(span.analyzed_frames(250_000.0, f32::INFINITY, 1), 2501);
+        assert_eq!(span.analyzed_frames(100_000.0, 199_950.0, 1), 1000);
+        assert_eq!(span.analyzed_frames(600_000.0, f32::INFINITY, 1), 0);
+    }
+
+    #[test]
+    fn scan_test_trajectory() {
+        let span = scan(
+            "tests/pcpepg.tpr",
+            &[String::from("tests/pcpepg.xtc")],
+            |_| true,
+        )
+        .unwrap();
+        assert!(span.frames > 0);
+        assert!(span.end >= span.start);
+    }
+
+    #[test]
+    fn report_progress_and_abort() {
+        let mut reported = Vec::new();
+        let mut times = Vec::new();
+        for frame in 0..PROGRESS_INTERVAL * 2 {
+            assert!(record_frame(&mut times, frame as f32, 10, &mut |read| {
+                reported.push(read);
+                true
+            }));
+        }
+        assert_eq!(
+            reported,
+            vec![PROGRESS_INTERVAL + 10, 2 * PROGRESS_INTERVAL + 10]
+        );
+
+        let mut times = vec![0.0; PROGRESS_INTERVAL - 1];
+        assert!(!record_frame(&mut times, 1.0, 0, &mut |_| false));
+    }
+
+    #[test]
+    fn cancel_other_scans() {
+        let running = Scan {
+            state: Arc::new(Mutex::new(ScanState::Scanning(100))),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+        let done = Scan {
+            state: Arc::new(Mutex::new(ScanState::Failed)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+        let running_key = (String::from("a.tpr"), Vec::new());
+        let done_key = (String::from("b.tpr"), Vec::new());
+
+        let mut scans = Scans::default();
+        scans.scans.insert(running_key.clone(), running.clone());
+        scans.scans.insert(done_key.clone(), done);
+
+        scans.cancel_running(Some(&running_key));
+        assert_eq!(scans.scans.len(), 2);
+        assert!(!running.cancelled.load(Ordering::Relaxed));
+
+        scans.cancel_running(Some(&done_key));
+        assert!(running.cancelled.load(Ordering::Relaxed));
+        assert!(!scans.scans.contains_key(&running_key));
+        assert!(scans.scans.contains_key(&done_key));
+    }
+
+    #[test]
+    fn evict_outdated_scans() {
+        let done = || Scan {
+            state: Arc::new(Mutex::new(ScanState::Failed)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+        let key = |structure: &str, modified: SystemTime| {
+            (
+                String::from(structure),
+                vec![(String::from("md.xtc"), Some(modified))],
+            )
+        };
+        let old = key("a.tpr", SystemTime::UNIX_EPOCH);
+        let other = key("b.tpr", SystemTime::UNIX_EPOCH);
+        let current = key("a.tpr", SystemTime::now());
+
+        let mut scans = Scans::default();
+        scans.scans.insert(old.clone(), done());
+        scans.scans.insert(other.clone(), done());
+        scans.scans.insert(current.clone(), done());
+
+        scans.evict_outdated(&current);
+        assert!(!scans.scans.contains_key(&old));
+        assert!(scans.scans.contains_key(&other));
+        assert!(scans.scans.contains_key(&current));
+    }
+
+    #[test]
+    fn resolve_files_once_per_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let trajectory = vec![format!("{}/md.xtc", dir.path().display())];
+
+        let mut scans = Scans::default();
+        assert_eq!(scans.scan_key("tests/pcpepg.tpr", &trajectory, 0.0), None);
+
+        std::fs::File::create(dir.path().join("md.xtc")).unwrap();
+        assert_eq!(scans.scan_key("tests/pcpepg.tpr", &trajectory, 1.0), None);
+        assert!(scans
+            .scan_key("tests/pcpepg.tpr", &trajectory, TRAJECTORY_CHECK_INTERVAL)
+            .is_some());
+    }
+
+    #[test]
+    fn invalid_trajectories() {
+        assert_eq!(
+            expand_trajectories(&[String::from("tests/nonexistent.xtc")]),
+            None
+        );
+        assert_eq!(
+            expand_trajectories(&[String::from("tests/pcpepg.xtc")]),
+            Some(vec![String::from("tests/pcpepg.xtc")])
+        );
+    }
+}
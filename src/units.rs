@@ -0,0 +1,377 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Units in which lengths and times are displayed.
+
+use std::{fmt::Display, ops::RangeInclusive};
+
+use eframe::egui::{self, DragValue, Id, Response, Ui};
+use serde::{Deserialize, Serialize};
+
+use crate::focus;
+
+/// Unit in which lengths are displayed and entered.
+/// All lengths are always stored in nanometers, the unit only affects the presentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) enum LengthUnit {
+    #[default]
+    Nanometer,
+    Angstrom,
+}
+
+impl Display for LengthUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LengthUnit::Nanometer => write!(f, "nm"),
+            LengthUnit::Angstrom => write!(f, "Å"),
+        }
+    }
+}
+
+impl LengthUnit {
+    fn id() -> Id {
+        Id::new("guiorder_length_unit")
+    }
+
+    /// Get the currently selected unit.
+    pub(crate) fn get(ctx: &egui::Context) -> Self {
+        ctx.data_mut(|d| d.get_persisted::<LengthUnit>(Self::id()))
+            .unwrap_or_default()
+    }
+
+    /// Select the unit for all length fields.
+    pub(crate) fn set(ctx: &egui::Context, unit: LengthUnit) {
+        ctx.data_mut(|d| d.insert_persisted(Self::id(), unit));
+    }
+
+    /// Number of units in one nanometer.
+    fn per_nm(self) -> f64 {
+        match self {
+            LengthUnit::Nanometer => 1.0,
+            LengthUnit::Angstrom => 10.0,
+        }
+    }
+
+    /// Convert a length in nanometers into this unit.
+    pub(crate) fn from_nm(self, value: f32) -> f64 {
+        value as f64 * self.per_nm()
+    }
+
+    /// Convert a length in this unit into nanometers.
+    pub(crate) fn to_nm(self, value: f64) -> f32 {
+        (value / self.per_nm()) as f32
+    }
+
+    /// Suffix displayed after the values.
+    pub(crate) fn suffix(self) -> String {
+        format!(" {}", self)
+    }
+}
+
+/// Unit in which times are displayed and entered.
+/// All times are always stored in picoseconds, the unit only affects the presentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) enum TimeUnit {
+    #[default]
+    Picosecond,
+    Nanosecond,
+}
+
+impl Display for TimeUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeUnit::Picosecond => write!(f, "ps"),
+            TimeUnit::Nanosecond => write!(f, "ns"),
+        }
+    }
+}
+
+impl TimeUnit {
+    fn id() -> Id {
+        Id::new("guiorder_time_unit")
+    }
+
+    /// Get the currently selected unit.
+    pub(crate) fn get(ctx: &egui::Context) -> Self {
+        ctx.data_mut(|d| d.get_persisted::<TimeUnit>(Self::id()))
+            .unwrap_or_default()
+    }
+
+    /// Select the unit for all time fields.
+    pub(crate) fn set(ctx: &egui::Context, unit: TimeUnit) {
+        ctx.data_mut(|d| d.insert_persisted(Self::id(), unit));
+    }
+
+    /// Number of picoseconds in one unit.
+    pub(crate) fn ps_per_unit(self) -> f64 {
+        match self {
+            TimeUnit::Picosecond => 1.0,
+            TimeUnit::Nanosecond => 1000.0,
+        }
+    }
+
+    /// Convert a time in picoseconds into this unit.
+    pub(crate) fn from_ps(self, value: f32) -> f64 {
+        value as f64 / self.ps_per_unit()
+    }
+
+    /// Convert a time in this unit into picoseconds.
+    pub(crate) fn to_ps(self, value: f64) -> f32 {
+        (value * self.ps_per_unit()) as f32
+    }
+
+    /// Suffix displayed after the values.
+    pub(crate) fn suffix(self) -> String {
+        format!(" {}", self)
+    }
+}
+
+/// Split the typed text into the number and its trailing unit of length or time, if any.
+fn split_unit(text: &str) -> (&str, Option<&str>) {
+    let text = text.trim();
+    for unit in ["nm", "Å", "ps", "ns"] {
+        if let Some(number) = text.strip_suffix(unit) {
+            return (number, Some(unit));
+        }
+    }
+
+    (text, None)
+}
+
+/// Parse a number typed into a drag value.
+/// Accepts scientific notation (e.g. `5e5`), digits separated by spaces or underscores,
+/// and infinity. Returns `None` for anything else, including numbers with a unit,
+/// in which case the drag value keeps its previous value.
+pub(crate) fn parse_number(text: &str) -> Option<f64> {
+    match split_unit(text) {
+        (number, None) => parse_plain_number(number),
+        (_, Some(_)) => None,
+    }
+}
+
+/// Parse a length typed into a drag value displayed in `unit`. See `parse_number` for the accepted formats.
+/// The length may be followed by a unit of length, in which case it is converted into `unit`.
+/// Returns `None` if the length is followed by a unit of time.
+pub(crate) fn parse_length(text: &str, unit: LengthUnit) -> Option<f64> {
+    let (number, typed) = split_unit(text);
+    let value = parse_plain_number(number)?;
+    match typed {
+        None => Some(value),
+        Some(typed) => {
+            let typed = [LengthUnit::Nanometer, LengthUnit::Angstrom]
+                .into_iter()
+                .find(|variant| variant.to_string() == typed)?;
+            Some(value / typed.per_nm() * unit.per_nm())
+        }
+    }
+}
+
+/// Parse a time typed into a drag value displayed in `unit`. See `parse_number` for the accepted formats.
+/// The time may be followed by a unit of time, in which case it is converted into `unit`.
+/// Returns `None` if the time is followed by a unit of length.
+pub(crate) fn parse_time(text: &str, unit: TimeUnit) -> Option<f64> {
+    let (number, typed) = split_unit(text);
+    let value = parse_plain_number(number)?;
+    match typed {
+        None => Some(value),
+        Some(typed) => {
+            let typed = [TimeUnit::Picosecond, TimeUnit::Nanosecond]
+                .into_iter()
+                .find(|variant| variant.to_string() == typed)?;
+            Some(value * typed.ps_per_unit() / unit.ps_per_unit())
+        }
+    }
+}
+
+/// Parse a number without a unit.
+fn parse_plain_number(text: &str) -> Option<f64> {
+    let cleaned = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_')
+        .collect::<String>()
+        .replace('−', "-");
+
+    match cleaned.as_str() {
+        "" => None,
+        "∞" => Some(f64::INFINITY),
+        number => number.parse::<f64>().ok().filter(|x| !x.is_nan()),
+    }
+}
+
+/// Parse a whole number typed into a drag value. See `parse_number` for the accepted formats.
+/// Returns `None` if the number is not whole.
+pub(crate) fn parse_integer(text: &str) -> Option<f64> {
+    parse_number(text).filter(|x| x.is_finite() && x.fract() == 0.0)
+}
+
+/// Selector for the unit in which times are displayed.
+pub(crate) fn time_unit_selector(ui: &mut Ui) {
+    let mut unit = TimeUnit::get(ui.ctx());
+    let previous = unit;
+
+    for variant in [TimeUnit::Picosecond, TimeUnit::Nanosecond] {
+        ui.selectable_value(&mut unit, variant, format!("{}", variant))
+            .on_hover_ui(|ui| {
+                ui.label(format!("Display the times in {}.", variant));
+            });
+    }
+
+    if unit != previous {
+        TimeUnit::set(ui.ctx(), unit);
+    }
+}
+
+/// Create a drag value for a length stored in nanometers and displayed in the currently selected unit.
+/// `speed` and `range` are provided in nanometers.
+pub(crate) fn length_drag_value(
+    ui: &mut Ui,
+    value: &mut f32,
+    speed: f64,
+    range: RangeInclusive<f32>,
+) -> Response {
+    let unit = LengthUnit::get(ui.ctx());
+    let range = unit.from_nm(*range.start())..=unit.from_nm(*range.end());
+
+    focus::chained(
+        ui.add(
+            DragValue::from_get_set(|new| {
+                if let Some(new) = new {
+                    *value = unit.to_nm(new);
+                }
+                unit.from_nm(*value)
+            })
+            .speed(speed * unit.per_nm())
+            .range(range)
+            .suffix(unit.suffix())
+            .custom_parser(move |text| parse_length(text, unit)),
+        ),
+    )
+}
+
+/// Check that the radius is a positive finite length.
+pub(crate) fn valid_radius(radius: f32) -> bool {
+    radius > 0.0 && radius.is_finite()
+}
+
+/// Selector for the unit in which lengths are displayed.
+pub(crate) fn unit_selector(ui: &mut Ui) {
+    let mut unit = LengthUnit::get(ui.ctx());
+    let previous = unit;
+
+    for variant in [LengthUnit::Nanometer, LengthUnit::Angstrom] {
+        ui.selectable_value(&mut unit, variant, format!("{}", variant))
+            .on_hover_ui(|ui| {
+                ui.label(format!("Display all lengths in {}.", variant));
+            });
+    }
+
+    if unit != previous {
+        LengthUnit::set(ui.ctx(), unit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_angstrom() {
+        assert_eq!(LengthUnit::Angstrom.from_nm(2.5), 25.0);
+        assert_eq!(LengthUnit::Angstrom.to_nm(25.0), 2.5);
+        assert_eq!(LengthUnit::Nanometer.from_nm(2.5), 2.5);
+        assert_eq!(LengthUnit::Angstrom.from_nm(f32::INFINITY), f64::INFINITY);
+    }
+
+    #[test]
+    fn convert_nanosecond() {
+        assert_eq!(TimeUnit::Nanosecond.from_ps(450500.0), 450.5);
+        assert_eq!(TimeUnit::Nanosecond.to_ps(450.5), 450500.0);
+        assert_eq!(TimeUnit::Picosecond.from_ps(200.0), 200.0);
+        assert_eq!(TimeUnit::Nanosecond.from_ps(f32::INFINITY), f64::INFINITY);
+    }
+
+    #[test]
+    fn time_round_trip_without_drift() {
+        for value in [
+            0.0f32,
+            0.1,
+            2.0,
+            450500.0,
+            450800.0,
+            123456.7,
+            2.5e7,
+            987654321.0,
+            1e12,
+        ] {
+            for unit in [TimeUnit::Picosecond, TimeUnit::Nanosecond] {
+                assert_eq!(unit.to_ps(unit.from_ps(value)), value);
+            }
+        }
+    }
+
+    #[test]
+    fn parse_typed_numbers() {
+        assert_eq!(parse_number("5e5"), Some(500000.0));
+        assert_eq!(parse_number("450 500"), Some(450500.0));
+        assert_eq!(parse_number("1_000"), Some(1000.0));
+        assert_eq!(parse_number("1_000 ps"), None);
+        assert_eq!(parse_number("−2.5"), Some(-2.5));
+        assert_eq!(parse_number("inf"), Some(f64::INFINITY));
+        assert_eq!(parse_number("∞"), Some(f64::INFINITY));
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("abc"), None);
+        assert_eq!(parse_number("1e"), None);
+        assert_eq!(parse_number("NaN"), None);
+
+        assert_eq!(parse_integer("1e3"), Some(1000.0));
+        assert_eq!(parse_integer("2.5"), None);
+        assert_eq!(parse_integer("inf"), None);
+        assert_eq!(parse_integer("4 nm"), None);
+    }
+
+    #[test]
+    fn parse_typed_lengths() {
+        assert_eq!(
+            parse_length(" 1.5E-3 nm", LengthUnit::Nanometer),
+            Some(0.0015)
+        );
+        assert_eq!(parse_length("2.5", LengthUnit::Angstrom), Some(2.5));
+        assert_eq!(parse_length("10 Å", LengthUnit::Angstrom), Some(10.0));
+        assert_eq!(
+            parse_length("∞ nm", LengthUnit::Nanometer),
+            Some(f64::INFINITY)
+        );
+
+        // typed in the other unit of length
+        assert_eq!(parse_length("10 Å", LengthUnit::Nanometer), Some(1.0));
+        assert_eq!(parse_length("1.5nm", LengthUnit::Angstrom), Some(15.0));
+
+        // typed in a unit of time
+        assert_eq!(parse_length("5 ps", LengthUnit::Nanometer), None);
+        assert_eq!(parse_length("5 ns", LengthUnit::Angstrom), None);
+        assert_eq!(parse_length("nm", LengthUnit::Nanometer), None);
+    }
+
+    #[test]
+    fn parse_typed_times() {
+        assert_eq!(parse_time("1_000 ps", TimeUnit::Picosecond), Some(1000.0));
+        assert_eq!(parse_time("450.5", TimeUnit::Nanosecond), Some(450.5));
+
+        // typed in the other unit of time
+        assert_eq!(parse_time("5 ns", TimeUnit::Picosecond), Some(5000.0));
+        assert_eq!(parse_time("2500ps", TimeUnit::Nanosecond), Some(2.5));
+
+        // typed in a unit of length
+        assert_eq!(parse_time("5 nm", TimeUnit::Picosecond), None);
+        assert_eq!(parse_time("10 Å", TimeUnit::Nanosecond), None);
+    }
+
+    #[test]
+    fn round_trip_without_drift() {
+        for value in [0.1f32, 0.3, 1.2, 2.5, 3.7, 7.13, -4.05, 123.456, 1e-4] {
+            for unit in [LengthUnit::Nanometer, LengthUnit::Angstrom] {
+                assert_eq!(unit.to_nm(unit.from_nm(value)), value);
+            }
+        }
+    }
+}
@@ -0,0 +1,396 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! User-defined presets saved on disk.
+
+use std::path::{Path, PathBuf};
+
+use eframe::egui::{self, RichText, Ui};
+
+use crate::{leaflets::LeafletClassificationParams, paths, GuiAnalysis, GuiOrderApp, OutputFiles};
+
+/// State of the user interface for managing the user-defined presets.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UserPresets {
+    /// Name under which the current options will be saved.
+    name: String,
+    /// Is the window for managing the presets open?
+    manager_open: bool,
+    /// Preset being renamed and its new name.
+    renaming: Option<(String, String)>,
+}
+
+/// Get the directory in which the user-defined presets are stored.
+fn presets_dir() -> Option<PathBuf> {
+    paths::config_dir("guiorder").map(|dir| dir.join("presets"))
+}
+
+/// Check that the name can be used as a name of a preset file.
+fn valid_name(name: &str) -> bool {
+    let name = name.trim();
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || " -_.()".contains(c))
+}
+
+/// Get the path to the file of the preset.
+fn preset_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.yaml", name.trim()))
+}
+
+/// Get the names of all presets in the directory, in natural order.
+fn list_presets(dir: &Path) -> Vec<String> {
+    let mut names = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+                .filter_map(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    names.sort_by(|a, b| paths::natural_cmp(a, b));
+    names
+}
+
+/// Save the analysis options without the input and output files as a preset.
+/// Placeholder paths are written instead of the files so that the preset is a valid configuration file.
+fn save_preset(
+    dir: &Path,
+    name: &str,
+    analysis: &GuiAnalysis,
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    if !valid_name(name) {
+        return Err(Box::from(format!(
+            "'{}' is not a valid name of a preset.",
+            name
+        )));
+    }
+
+    let placeholder = GuiAnalysis {
+        structure: String::from("system.tpr"),
+        trajectory: vec![String::from("md.xtc")],
+        leaflet_classification_params: LeafletClassificationParams::with_placeholder_paths(),
+        from_file_normals: String::from("normals.yaml"),
+        output: OutputFiles {
+            output_yaml: String::from("order.yaml"),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut preset = analysis.clone();
+    preset.copy_paths(&placeholder);
+    preset
+        .estimate_error_params
+        .output_convergence_mut()
+        .clear();
+    if !preset.ordermaps_params.output_directory_mut().is_empty() {
+        *preset.ordermaps_params.output_directory_mut() = String::from("ordermaps");
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let path = preset_path(dir, name);
+    GuiOrderApp::write_config(&preset, &path)?;
+    Ok(path)
+}
+
+/// Load the preset from the directory.
+fn load_preset(
+    dir: &Path,
+    name: &str,
+) -> Result<GuiAnalysis, Box<dyn std::error::Error + Send + Sync>> {
+    GuiAnalysis::from_preset(&std::fs::read_to_string(preset_path(dir, name))?)
+}
+
+/// Rename the preset. Fails if a preset with the new name already exists.
+fn rename_preset(
+    dir: &Path,
+    old: &str,
+    new: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if !valid_name(new) {
+        return Err(Box::from(format!(
+            "'{}' is not a valid name of a preset.",
+            new
+        )));
+    }
+
+    let target = preset_path(dir, new);
+    if target.exists() {
+        return Err(Box::from(format!(
+            "Preset '{}' already exists.",
+            new.trim()
+        )));
+    }
+
+    std::fs::rename(preset_path(dir, old), target)?;
+    Ok(())
+}
+
+impl GuiOrderApp {
+    /// Load the user-defined preset, keeping the already specified input and output files.
+    fn load_user_preset(&mut self, dir: &Path, name: &str) {
+        match load_preset(dir, name) {
+            Ok(mut preset) => {
+                preset.copy_paths(&self.analysis);
+                self.analysis = preset;
+            }
+            Err(e) => self.open_error_window(e),
+        }
+    }
+
+    /// Menu items for saving and loading the user-defined presets.
+    pub(super) fn user_presets_menu(&mut self, ui: &mut Ui) {
+        let Some(dir) = presets_dir() else {
+            ui.label("User presets are not available.");
+            return;
+        };
+
+        ui.menu_button("My presets", |ui| {
+            let names = list_presets(&dir);
+            if names.is_empty() {
+                ui.label(
+                    RichText::new("No saved presets.")
+                        .font(egui::FontId::monospace(12.0))
+                        .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                );
+            }
+
+            for name in names {
+                if ui.button(&name).clicked() {
+                    ui.close_menu();
+                    self.load_user_preset(&dir, &name);
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.user_presets.name)
+                    .hint_text("preset name")
+                    .desired_width(120.0),
+            );
+
+            if ui
+                .add_enabled(
+                    valid_name(&self.user_presets.name),
+                    egui::Button::new("💾 Save"),
+                )
+                .on_hover_ui(|ui| {
+                    ui.label(
+                        "Save the current options (without input and output files) as a preset.",
+                    );
+                })
+                .on_disabled_hover_ui(|ui| {
+                    ui.label("Provide a name for the preset.");
+                })
+                .clicked()
+            {
+                ui.close_menu();
+                match save_preset(&dir, &self.user_presets.name, &self.analysis) {
+                    Ok(path) => {
+                        self.user_presets.name.clear();
+                        self.open_success_window(&format!(
+                            "Preset saved into '{}'.",
+                            path.display()
+                        ));
+                    }
+                    Err(e) => self.open_error_window(e),
+                }
+            }
+        });
+
+        if ui.button("⚙ Manage presets").clicked() {
+            ui.close_menu();
+            self.user_presets.manager_open = true;
+        }
+    }
+
+    /// Render the window for renaming and deleting the user-defined presets, if open.
+    pub(super) fn render_presets_manager(&mut self, ctx: &egui::Context) {
+        let Some(dir) = presets_dir().filter(|_| self.user_presets.manager_open) else {
+            return;
+        };
+
+        let mut open = true;
+        let mut result: Result<(), Box<dyn std::error::Error + Send + Sync>> = Ok(());
+        egui::Window::new("Manage presets")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let names = list_presets(&dir);
+                if names.is_empty() {
+                    ui.label(
+                        RichText::new("No saved presets.")
+                            .font(egui::FontId::monospace(12.0))
+                            .color(egui::Color32::from_rgba_premultiplied(150, 150, 150, 100)),
+                    );
+                }
+
+                for name in names {
+                    ui.horizontal(|ui| match &mut self.user_presets.renaming {
+                        Some((old, new)) if *old == name => {
+                            ui.text_edit_singleline(new);
+                            if ui
+                                .add_enabled(valid_name(new), egui::Button::new("✔"))
+                                .clicked()
+                            {
+                                result = rename_preset(&dir, old, new);
+                                self.user_presets.renaming = None;
+                            }
+                            if ui.button("✖").clicked() {
+                                self.user_presets.renaming = None;
+                            }
+                        }
+                        _ => {
+                            ui.label(RichText::new(&name).font(egui::FontId::monospace(12.0)));
+                            if ui
+                                .button("✏")
+                                .on_hover_ui(|ui| {
+                                    ui.label("Rename the preset.");
+                                })
+                                .clicked()
+                            {
+                                self.user_presets.renaming = Some((name.clone(), name.clone()));
+                            }
+                            if ui
+                                .button("🗑")
+                                .on_hover_ui(|ui| {
+                                    ui.label("Delete the preset.");
+                                })
+                                .clicked()
+                            {
+                                result = std::fs::remove_file(preset_path(&dir, &name))
+                                    .map_err(Box::from);
+                            }
+                        }
+                    });
+                }
+
+                if ui
+                    .button("📂 Open presets folder")
+                    .on_hover_ui(|ui| {
+                        ui.label(format!("Open '{}'.", dir.display()));
+                    })
+                    .clicked()
+                {
+                    if let Err(e) = std::fs::create_dir_all(&dir)
+                        .and_then(|_| opener::open(&dir).map_err(std::io::Error::other))
+                    {
+                        log::warn!("Could not open '{}': {}", dir.display(), e);
+                    }
+                }
+            });
+
+        self.user_presets.manager_open = open;
+        if let Err(e) = result {
+            self.open_error_window(e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        analysis_types::AnalysisType, common::MembraneNormal, leaflets::LeafletClassification,
+    };
+
+    use super::*;
+
+    #[test]
+    fn preset_names() {
+        assert!(valid_name("My Martini setup (v2)"));
+        assert!(!valid_name("  "));
+        assert!(!valid_name("../outside"));
+        assert!(!valid_name(".hidden"));
+    }
+
+    #[test]
+    fn save_load_rename_delete() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+
+        save_preset(dir.path(), "setup 10", &app.analysis).unwrap();
+        save_preset(dir.path(), "setup 2", &app.analysis).unwrap();
+        assert_eq!(list_presets(dir.path()), vec!["setup 2", "setup 10"]);
+
+        let loaded = load_preset(dir.path(), "setup 2").unwrap();
+        assert_eq!(loaded.analysis_type, AnalysisType::AAOrder);
+        assert_eq!(loaded.other_params.n_threads, 4);
+        assert!(loaded.structure.is_empty());
+        assert!(loaded.trajectory.is_empty());
+        assert!(loaded.ndx.is_empty());
+        assert_eq!(
+            loaded.ordermaps_params.output_directory(),
+            Some("ordermaps")
+        );
+        assert_eq!(loaded.estimate_error_params.output_convergence(), None);
+
+        // the preset is a valid configuration file without the original paths
+        let content = std::fs::read_to_string(preset_path(dir.path(), "setup 2")).unwrap();
+        assert!(!content.contains("tests/"));
+        let config: gorder::input::Analysis = serde_yaml::from_str(&content).unwrap();
+        assert_eq!(config.structure(), "system.tpr");
+        assert_eq!(config.output_yaml().as_deref(), Some("order.yaml"));
+        assert!(config.output_tab().is_none());
+
+        assert!(rename_preset(dir.path(), "setup 2", "setup 10").is_err());
+        rename_preset(dir.path(), "setup 2", "martini").unwrap();
+        assert_eq!(list_presets(dir.path()), vec!["martini", "setup 10"]);
+
+        std::fs::remove_file(preset_path(dir.path(), "martini")).unwrap();
+        assert_eq!(list_presets(dir.path()), vec!["setup 10"]);
+    }
+
+    #[test]
+    fn save_without_leaflet_and_normal_files() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut app = GuiOrderApp::default();
+        app.import_yaml("tests/parameters.yaml");
+        app.analysis.leaflet_classification_method = LeafletClassification::FromNdx;
+        app.analysis.leaflet_classification_params = LeafletClassificationParams::try_from(Some(
+            gorder::input::LeafletClassification::from_ndx(
+                &["tests/leaflets1.ndx", "tests/leaflets2.ndx"],
+                "name P",
+                "Upper",
+                "Lower",
+            ),
+        ))
+        .unwrap();
+        app.analysis.membrane_normal = MembraneNormal::FromFile;
+        app.analysis.from_file_normals = String::from("tests/normals.yaml");
+
+        save_preset(dir.path(), "ndx", &app.analysis).unwrap();
+        let content = std::fs::read_to_string(preset_path(dir.path(), "ndx")).unwrap();
+        assert!(!content.contains("tests/"));
+        assert!(content.contains("leaflets.ndx"));
+        assert!(content.contains("normals.yaml"));
+
+        // loading the preset keeps the files of the current analysis
+        let current = app.analysis.clone();
+        app.load_user_preset(dir.path(), "ndx");
+        assert_eq!(app.analysis.from_file_normals, "tests/normals.yaml");
+        assert_eq!(
+            app.analysis
+                .leaflet_classification_params
+                .input_paths_mut()
+                .map(|path| path.clone())
+                .collect::<Vec<_>>(),
+            current
+                .leaflet_classification_params
+                .clone()
+                .input_paths_mut()
+                .map(|path| path.clone())
+                .collect::<Vec<_>>(),
+        );
+    }
+}
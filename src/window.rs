@@ -3,12 +3,12 @@
 
 //! Handles spawning windows.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 use eframe::egui::{self, Id, RichText, Ui};
 use regex::Regex;
 
-use crate::GuiOrderApp;
+use crate::{selection, GuiOrderApp, GUIORDER_VERSION};
 
 /// A single window.
 #[derive(Debug, Clone)]
@@ -16,43 +16,155 @@ struct Window {
     title: String,
     messages: Vec<Message>,
     open: bool,
+    /// Number of times the same messages have been reported.
+    repeated: usize,
+    /// Default position of the window.
+    position: egui::Pos2,
+    /// Number of windows spawned before this one.
+    index: usize,
 }
 
 /// Single message to print to a window.
-#[allow(dead_code)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 enum Message {
     Error(String),
     Warning(String),
     Success(String),
     Info(String),
+    /// Button opening a file or a directory using the default application.
+    Open(String, PathBuf),
+    /// Heading of a group of messages.
+    Heading(String),
+    /// Shell command with a button for copying it into the clipboard.
+    Command(String),
+    /// Link opening a web page in the browser.
+    Link(String, String),
+    /// Example of a selection query with its description.
+    Example(String, String),
+    /// Selector for the operator joining the inserted examples with the existing query.
+    OperatorSelector,
 }
 
 impl Message {
     fn write(&self, ui: &mut Ui) {
-        let color = match self {
-            Self::Error(_) => egui::Color32::from_rgba_premultiplied(150, 0, 0, 100),
-            Self::Warning(_) => egui::Color32::from_rgba_premultiplied(150, 120, 0, 100),
-            Self::Success(_) => egui::Color32::from_rgba_premultiplied(0, 150, 0, 100),
-            Self::Info(_) => egui::Color32::from_rgba_premultiplied(150, 150, 150, 100),
-        };
+        let gray = egui::Color32::from_rgba_premultiplied(150, 150, 150, 100);
 
-        let text = match self {
-            Self::Error(x) | Self::Warning(x) | Self::Info(x) | Self::Success(x) => x,
-        };
+        match self {
+            Self::Error(text) => Self::write_text(
+                ui,
+                "error: ",
+                text,
+                egui::Color32::from_rgba_premultiplied(150, 0, 0, 100),
+            ),
+            Self::Warning(text) => Self::write_text(
+                ui,
+                "warning: ",
+                text,
+                egui::Color32::from_rgba_premultiplied(150, 120, 0, 100),
+            ),
+            Self::Success(text) => Self::write_text(
+                ui,
+                "",
+                text,
+                egui::Color32::from_rgba_premultiplied(0, 150, 0, 100),
+            ),
+            Self::Info(text) => Self::write_text(ui, "", text, gray),
+            Self::Heading(heading) => {
+                ui.add_space(5.0);
+                ui.label(
+                    RichText::new(heading)
+                        .font(egui::FontId::monospace(13.0))
+                        .strong(),
+                );
+            }
+            Self::Command(command) => {
+                ui.add(
+                    egui::TextEdit::multiline(&mut command.as_str())
+                        .font(egui::FontId::monospace(12.0))
+                        .desired_width(f32::INFINITY),
+                );
+                if ui
+                    .button("📋 Copy")
+                    .on_hover_ui(|ui| {
+                        ui.label("Copy the command into the clipboard.");
+                    })
+                    .clicked()
+                {
+                    ui.ctx().copy_text(command.clone());
+                }
+            }
+            Self::Open(label, path) => {
+                if ui
+                    .button(RichText::new(label).font(egui::FontId::monospace(12.0)))
+                    .on_hover_ui(|ui| {
+                        ui.label(format!("Open '{}'.", path.display()));
+                    })
+                    .clicked()
+                {
+                    if let Err(e) = opener::open(path) {
+                        log::warn!("Could not open '{}': {}", path.display(), e);
+                    }
+                }
+            }
+            Self::Link(label, url) => {
+                ui.hyperlink_to(
+                    RichText::new(label).font(egui::FontId::monospace(12.0)),
+                    url,
+                )
+                .on_hover_text(url);
+            }
+            Self::Example(query, description) => {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(query)
+                            .font(egui::FontId::monospace(12.0))
+                            .strong(),
+                    );
+                    ui.label(
+                        RichText::new(description)
+                            .font(egui::FontId::monospace(12.0))
+                            .color(gray),
+                    );
 
-        let label = match self {
-            Self::Error(_) => "error: ",
-            Self::Warning(_) => "warning: ",
-            Self::Info(_) | Self::Success(_) => "",
-        };
+                    if ui
+                        .small_button("📋")
+                        .on_hover_ui(|ui| {
+                            ui.label("Copy the query into the clipboard.");
+                        })
+                        .clicked()
+                    {
+                        ui.ctx().copy_text(query.clone());
+                    }
+
+                    if ui
+                        .add_enabled(
+                            selection::can_insert(ui.ctx()),
+                            egui::Button::new("↳").small(),
+                        )
+                        .on_hover_ui(|ui| {
+                            ui.label("Insert the query into the last edited selection field.");
+                        })
+                        .on_disabled_hover_ui(|ui| {
+                            ui.label("Click into a selection field first.");
+                        })
+                        .clicked()
+                    {
+                        selection::insert_into_last_field(ui.ctx(), query);
+                    }
+                });
+            }
+            Self::OperatorSelector => selection::operator_selector(ui),
+        }
+    }
 
+    /// Write a text message, wrapping long lines.
+    fn write_text(ui: &mut Ui, label: &str, text: &str, color: egui::Color32) {
         ui.label(
             RichText::new(Message::insert_newlines(
                 &format!(
                     "{}{}",
                     label,
-                    Message::remove_error_labels(&Message::strip_ansi_codes(text))
+                    Message::remove_error_labels(&strip_ansi_codes(text))
                 ),
                 60,
             ))
@@ -61,10 +173,21 @@ impl Message {
         );
     }
 
-    /// Remove ANSI codes from a string.
-    fn strip_ansi_codes(input: &str) -> String {
-        let re = Regex::new(r"\x1B\[[0-9;]*[mK]").unwrap();
-        re.replace_all(input, "").into_owned()
+    /// Get the original text of the message, without any formatting applied.
+    fn raw_text(&self) -> String {
+        match self {
+            Self::Error(x)
+            | Self::Warning(x)
+            | Self::Success(x)
+            | Self::Info(x)
+            | Self::Heading(x)
+            | Self::Command(x) => x.clone(),
+            Self::Open(label, path) => format!("{}: {}", label, path.display()),
+            Self::Link(label, url) => format!("{}: {}", label, url),
+            Self::Example(query, description) => format!("{}  # {}", query, description),
+            // the selector has no text to copy
+            Self::OperatorSelector => String::new(),
+        }
     }
 
     /// Remove all `error:` labels from the string.
@@ -93,21 +216,94 @@ impl Message {
     }
 }
 
+/// Remove ANSI codes from a string.
+pub(crate) fn strip_ansi_codes(input: &str) -> String {
+    let re = Regex::new(r"\x1B\[[0-9;]*[mK]").unwrap();
+    re.replace_all(input, "").into_owned()
+}
+
+/// Percent-encode the text so that it can be used in a URL query.
+fn url_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Get the URL of a new GitHub issue prefilled with the error and the versions of guiorder and gorder.
+fn issue_url(error: &str) -> String {
+    let body = format!(
+        "guiorder version: {}\ngorder version: {}\n\nError:\n```\n{}\n```\n\nSteps to reproduce:\n",
+        GUIORDER_VERSION,
+        gorder::GORDER_VERSION,
+        strip_ansi_codes(error)
+    );
+
+    format!(
+        "{}/issues/new?title={}&body={}",
+        env!("CARGO_PKG_REPOSITORY"),
+        url_encode("Error reported by guiorder"),
+        url_encode(&body)
+    )
+}
+
+/// Messages reported by widgets without access to the application.
+/// They are shown in new windows during the next rendering of the windows.
+#[derive(Debug, Clone, Default)]
+struct ReportedMessages(Vec<(String, Message)>);
+
+impl ReportedMessages {
+    fn id() -> Id {
+        Id::new("guiorder_reported_messages")
+    }
+
+    fn push(ctx: &egui::Context, title: &str, message: Message) {
+        ctx.data_mut(|d| {
+            d.get_temp_mut_or_default::<ReportedMessages>(Self::id())
+                .0
+                .push((title.to_owned(), message))
+        });
+    }
+}
+
+/// Report an error to be shown in a new window.
+pub(crate) fn report_error(ctx: &egui::Context, message: String) {
+    ReportedMessages::push(ctx, "Error!", Message::Error(message));
+}
+
+/// Report a warning to be shown in a new window.
+pub(crate) fn report_warning(ctx: &egui::Context, message: String) {
+    ReportedMessages::push(ctx, "Warning!", Message::Warning(message));
+}
+
 /// All spawned error windows.
 #[derive(Debug, Clone, Default)]
 pub(crate) struct Windows {
     windows: HashMap<Id, Window>,
     total_spawned: usize,
+    /// Was any widget focused or any popup open at the end of the last frame?
+    busy: bool,
 }
 
 impl Window {
     /// Render the window.
     fn render(&mut self, id: Id, ctx: &egui::Context) {
-        egui::Window::new(&self.title)
+        let title = if self.repeated > 1 {
+            format!("{} (×{})", self.title, self.repeated)
+        } else {
+            self.title.clone()
+        };
+
+        egui::Window::new(title)
             .id(id)
             .open(&mut self.open)
             .collapsible(false)
             .resizable(false)
+            .default_pos(self.position)
             .default_width(600.0)
             .default_height(500.0)
             .show(ctx, |ui| {
@@ -116,13 +312,136 @@ impl Window {
                         message.write(ui);
                     }
                 });
+
+                ui.add_space(5.0);
+                if ui
+                    .small_button("📋 Copy")
+                    .on_hover_ui(|ui| {
+                        ui.label("Copy the text of this window into the clipboard.");
+                    })
+                    .clicked()
+                {
+                    ui.ctx().copy_text(Window::raw_text(&self.messages));
+                }
+
+                let errors = self
+                    .messages
+                    .iter()
+                    .filter_map(|message| match message {
+                        Message::Error(error) => Some(error.as_str()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+
+                if !errors.is_empty()
+                    && ui
+                        .small_button("🐛 Report issue")
+                        .on_hover_ui(|ui| {
+                            ui.label("Open a new GitHub issue describing this error.");
+                        })
+                        .clicked()
+                {
+                    let url = issue_url(&errors.join("\n"));
+                    if let Err(e) = opener::open_browser(&url) {
+                        log::warn!("Could not open '{}': {}", url, e);
+                    }
+                }
             });
     }
+
+    /// Get the original text of all messages.
+    fn raw_text(messages: &[Message]) -> String {
+        messages
+            .iter()
+            .map(Message::raw_text)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Position of the first spawned window.
+const CASCADE_ORIGIN: egui::Pos2 = egui::pos2(20.0, 60.0);
+/// Offset between the positions of two windows spawned one after another.
+const CASCADE_STEP: f32 = 25.0;
+/// Number of windows after which the cascade starts again from the origin.
+const CASCADE_LENGTH: usize = 8;
+
+/// Get the default position of the `n`-th spawned window.
+fn cascade_position(n: usize) -> egui::Pos2 {
+    CASCADE_ORIGIN + egui::Vec2::splat((n % CASCADE_LENGTH) as f32 * CASCADE_STEP)
 }
 
 impl Windows {
+    /// Open a new window with the messages.
+    fn spawn(&mut self, title: &str, messages: Vec<Message>) {
+        self.windows.insert(
+            Id::new(self.total_spawned),
+            Window {
+                title: title.to_owned(),
+                messages,
+                open: true,
+                repeated: 1,
+                position: cascade_position(self.total_spawned),
+                index: self.total_spawned,
+            },
+        );
+
+        self.total_spawned += 1;
+    }
+
+    /// Get the id of the open window displayed on top of the others.
+    /// Windows that have not been displayed yet are on top, the most recently spawned first.
+    fn topmost(&self, layers: &[Id]) -> Option<Id> {
+        self.windows
+            .iter()
+            .filter(|(_, window)| window.open)
+            .max_by_key(|(id, window)| {
+                let layer = layers
+                    .iter()
+                    .position(|layer| layer == *id)
+                    .unwrap_or(usize::MAX);
+                (layer, window.index)
+            })
+            .map(|(id, _)| *id)
+    }
+
+    /// Open a new window for each message reported since the last frame.
+    fn spawn_reported(&mut self, ctx: &egui::Context) {
+        let reported = ctx.data_mut(|d| d.remove_temp::<ReportedMessages>(ReportedMessages::id()));
+        for (title, message) in reported.unwrap_or_default().0 {
+            self.spawn(&title, vec![message]);
+        }
+    }
+
+    /// Close the topmost window when Escape is pressed.
+    /// Escape is left alone if it is used to stop editing a text field or to close a popup.
+    fn handle_escape(&mut self, ctx: &egui::Context) {
+        // egui drops the focus on Escape before the frame starts, so the state from the last frame is used
+        if self.busy || !ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            return;
+        }
+
+        let layers = ctx.memory(|m| {
+            m.layer_ids()
+                .filter(|layer| layer.order == egui::Order::Middle)
+                .map(|layer| layer.id)
+                .collect::<Vec<_>>()
+        });
+
+        if let Some(id) = self.topmost(&layers) {
+            ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape));
+            if let Some(window) = self.windows.get_mut(&id) {
+                window.open = false;
+            }
+        }
+    }
+
     /// Render all windows.
     pub(super) fn render(&mut self, ctx: &egui::Context) {
+        self.spawn_reported(ctx);
+        self.handle_escape(ctx);
+
         let mut windows_to_close = vec![];
         for (id, window) in self.windows.iter_mut() {
             if !window.open {
@@ -137,34 +456,259 @@ impl Windows {
         for id in windows_to_close {
             self.windows.remove(&id);
         }
+
+        self.busy = ctx.memory(|m| m.focused().is_some() || m.any_popup_open());
     }
 }
 
 impl GuiOrderApp {
     /// Open a new error window.
+    /// If an open error window already reports the same error, its counter is increased instead.
     pub(super) fn open_error_window(&mut self, error: Box<dyn std::error::Error + Send + Sync>) {
-        self.windows.windows.insert(
-            Id::new(self.windows.total_spawned),
-            Window {
-                title: String::from("Error!"),
-                messages: vec![Message::Error(error.to_string())],
-                open: true,
-            },
-        );
+        let messages = vec![Message::Error(error.to_string())];
+        if let Some(window) = self
+            .windows
+            .windows
+            .values_mut()
+            .find(|window| window.open && window.messages == messages)
+        {
+            window.repeated += 1;
+            return;
+        }
+
+        self.windows.spawn("Error!", messages);
+    }
+
+    /// Open a new warning window.
+    pub(super) fn open_warning_window(&mut self, message: &str) {
+        self.windows
+            .spawn("Warning!", vec![Message::Warning(message.to_string())]);
+    }
+
+    /// Open a new window with an informative message.
+    pub(super) fn open_info_window(&mut self, message: &str) {
+        self.windows
+            .spawn("Info", vec![Message::Info(message.to_string())]);
+    }
+
+    /// Open a new window listing the warnings reported by the analysis.
+    pub(super) fn open_analysis_warnings_window(&mut self, warnings: Vec<String>) {
+        let mut messages = vec![Message::Info(format!(
+            "The analysis reported {} warning(s):",
+            warnings.len()
+        ))];
+        messages.extend(warnings.into_iter().map(Message::Warning));
 
-        self.windows.total_spawned += 1;
+        self.windows.spawn("Warning!", messages);
+    }
+
+    /// Open a new window listing the problems found when validating the analysis options.
+    pub(super) fn open_validation_window(&mut self, errors: Vec<String>, warnings: Vec<String>) {
+        let mut messages = vec![Message::Info(format!(
+            "Validation found {} error(s) and {} warning(s):",
+            errors.len(),
+            warnings.len()
+        ))];
+        messages.extend(errors.into_iter().map(Message::Error));
+        messages.extend(warnings.into_iter().map(Message::Warning));
+
+        self.windows.spawn("Validation", messages);
     }
 
     pub(super) fn open_success_window(&mut self, message: &str) {
-        self.windows.windows.insert(
-            Id::new(self.windows.total_spawned),
-            Window {
-                title: String::from("Success!"),
-                messages: vec![Message::Success(message.to_string())],
-                open: true,
-            },
+        self.windows
+            .spawn("Success!", vec![Message::Success(message.to_string())]);
+    }
+
+    /// Open a new success window with buttons for opening the provided paths.
+    /// Links in `groups` are listed under the headings of the groups. Empty groups are not shown.
+    pub(super) fn open_success_window_with_links(
+        &mut self,
+        message: &str,
+        links: Vec<(String, PathBuf)>,
+        groups: Vec<(String, Vec<(String, PathBuf)>)>,
+    ) {
+        let mut messages = vec![Message::Success(message.to_string())];
+        messages.extend(
+            links
+                .into_iter()
+                .map(|(label, path)| Message::Open(label, path)),
         );
 
-        self.windows.total_spawned += 1;
+        for (heading, links) in groups {
+            if links.is_empty() {
+                continue;
+            }
+
+            messages.push(Message::Heading(heading));
+            messages.extend(
+                links
+                    .into_iter()
+                    .map(|(label, path)| Message::Open(label, path)),
+            );
+        }
+
+        self.windows.spawn("Success!", messages);
+    }
+
+    /// Open the window with information about the application, unless it is already open.
+    pub(super) fn open_about_window(&mut self) {
+        if self
+            .windows
+            .windows
+            .values()
+            .any(|window| window.open && window.title == "About")
+        {
+            return;
+        }
+
+        let repository = env!("CARGO_PKG_REPOSITORY");
+        self.windows.spawn(
+            "About",
+            vec![
+                Message::Heading(format!("guiorder v{}", GUIORDER_VERSION)),
+                Message::Info(format!(
+                    "Graphical User Interface for gorder v{}.",
+                    gorder::GORDER_VERSION
+                )),
+                Message::Info(format!(
+                    "Released under the {} License.",
+                    env!("CARGO_PKG_LICENSE")
+                )),
+                Message::Link(String::from("Repository"), repository.to_owned()),
+                Message::Link(
+                    String::from("gorder documentation"),
+                    String::from("https://docs.rs/gorder"),
+                ),
+                Message::Link(
+                    String::from("Check for updates"),
+                    format!("{}/releases", repository),
+                ),
+                Message::Link(
+                    String::from("Report issue"),
+                    format!("{}/issues/new", repository),
+                ),
+            ],
+        );
+    }
+
+    /// Open the window summarizing the syntax of the groan selection language, unless it is already open.
+    pub(super) fn open_selection_help_window(&mut self) {
+        if self
+            .windows
+            .windows
+            .values()
+            .any(|window| window.open && window.title == "Selection help")
+        {
+            return;
+        }
+
+        let mut messages = vec![
+            Message::Info(String::from(
+                "Atoms are selected using the groan selection language. Conditions can be combined using operators and parentheses.",
+            )),
+            Message::OperatorSelector,
+        ];
+        for (heading, examples) in selection::SELECTION_EXAMPLES {
+            messages.push(Message::Heading(heading.to_owned()));
+            messages.extend(examples.iter().map(|(query, description)| {
+                Message::Example(query.to_string(), description.to_string())
+            }));
+        }
+        messages.push(Message::Link(
+            String::from("Full documentation of the selection language"),
+            String::from("https://docs.rs/groan_rs/latest/groan_rs/"),
+        ));
+
+        self.windows.spawn("Selection help", messages);
+    }
+
+    /// Open a new window displaying a shell command that can be copied into the clipboard.
+    pub(super) fn open_command_window(&mut self, message: &str, command: String) {
+        self.windows.spawn(
+            "Command",
+            vec![
+                Message::Info(message.to_string()),
+                Message::Command(command),
+            ],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deduplicate_errors() {
+        let mut app = GuiOrderApp::default();
+        app.open_error_window(Box::from("could not read the structure"));
+        app.open_error_window(Box::from("could not read the structure"));
+        app.open_error_window(Box::from("could not read the trajectory"));
+
+        assert_eq!(app.windows.windows.len(), 2);
+        let mut repeated = app
+            .windows
+            .windows
+            .values()
+            .map(|window| window.repeated)
+            .collect::<Vec<_>>();
+        repeated.sort();
+        assert_eq!(repeated, vec![1, 2]);
+    }
+
+    #[test]
+    fn prefilled_issue() {
+        assert_eq!(url_encode("a b&c=d/é"), "a%20b%26c%3Dd%2F%C3%A9");
+
+        let url = issue_url("\x1B[1mcould not read\x1B[0m 'system.tpr'");
+        assert!(url.starts_with("https://github.com/Ladme/guiorder/issues/new?title="));
+        assert!(url.contains("could%20not%20read%20%27system.tpr%27"));
+        assert!(!url.contains("%1B"));
+    }
+
+    #[test]
+    fn close_topmost_window() {
+        let mut windows = Windows::default();
+        windows.spawn("Error!", vec![Message::Error(String::from("first"))]);
+        windows.spawn("Error!", vec![Message::Error(String::from("second"))]);
+        windows.spawn("Error!", vec![Message::Error(String::from("third"))]);
+
+        // not displayed yet, the most recently spawned is on top
+        assert_eq!(windows.topmost(&[]), Some(Id::new(2)));
+
+        // the first window was brought to the front
+        let layers = [Id::new(1), Id::new(2), Id::new(0)];
+        assert_eq!(windows.topmost(&layers), Some(Id::new(0)));
+
+        windows.windows.get_mut(&Id::new(0)).unwrap().open = false;
+        assert_eq!(windows.topmost(&layers), Some(Id::new(2)));
+    }
+
+    #[test]
+    fn spawn_reported_messages() {
+        let ctx = egui::Context::default();
+        report_error(&ctx, String::from("could not read the folder"));
+        report_warning(&ctx, String::from("no files found"));
+
+        let mut windows = Windows::default();
+        windows.spawn_reported(&ctx);
+        assert_eq!(windows.windows.len(), 2);
+        assert_eq!(windows.windows[&Id::new(0)].title, "Error!");
+        assert_eq!(
+            windows.windows[&Id::new(1)].messages,
+            vec![Message::Warning(String::from("no files found"))]
+        );
+
+        // the messages are only shown once
+        windows.spawn_reported(&ctx);
+        assert_eq!(windows.windows.len(), 2);
+    }
+
+    #[test]
+    fn cascade_windows() {
+        assert_eq!(cascade_position(0), CASCADE_ORIGIN);
+        assert_eq!(cascade_position(2), CASCADE_ORIGIN + egui::vec2(50.0, 50.0));
+        assert_eq!(cascade_position(CASCADE_LENGTH), CASCADE_ORIGIN);
     }
 }
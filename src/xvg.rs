@@ -0,0 +1,141 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Reading of XVG files written by gorder.
+
+use std::path::Path;
+
+use crate::plots::Series;
+
+/// Content of an XVG file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Xvg {
+    pub title: String,
+    pub x_label: String,
+    pub y_label: String,
+    /// Names of the individual data columns (excluding the x-column).
+    pub legends: Vec<String>,
+    /// Rows of data. The first value of each row is the x-value.
+    pub rows: Vec<Vec<f64>>,
+}
+
+/// Get the text enclosed in quotes.
+fn quoted(line: &str) -> String {
+    line.split('"').nth(1).unwrap_or_default().to_owned()
+}
+
+impl Xvg {
+    /// Parse the content of an XVG file.
+    pub(crate) fn parse(content: &str) -> Result<Self, String> {
+        let mut xvg = Xvg::default();
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(directive) = line.strip_prefix('@') {
+                let directive = directive.trim_start();
+                if directive.starts_with("title") {
+                    xvg.title = quoted(directive);
+                } else if directive.starts_with("xaxis") {
+                    xvg.x_label = quoted(directive);
+                } else if directive.starts_with("yaxis") {
+                    xvg.y_label = quoted(directive);
+                } else if directive.starts_with('s') && directive.contains("legend") {
+                    xvg.legends.push(quoted(directive));
+                }
+                continue;
+            }
+
+            let row = line
+                .split_whitespace()
+                .map(|x| x.parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| format!("could not parse line {}", i + 1))?;
+            xvg.rows.push(row);
+        }
+
+        if xvg.rows.is_empty() {
+            return Err(String::from("the file contains no data"));
+        }
+
+        Ok(xvg)
+    }
+
+    /// Read an XVG file.
+    pub(crate) fn read(path: impl AsRef<Path>) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Self::parse(&content)
+    }
+
+    /// Get the data columns as plot series. Non-finite values are skipped.
+    pub(crate) fn series(&self) -> Vec<Series> {
+        let n_columns = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        (1..n_columns)
+            .map(|column| Series {
+                name: self
+                    .legends
+                    .get(column - 1)
+                    .cloned()
+                    .unwrap_or_else(|| format!("column {}", column)),
+                points: self
+                    .rows
+                    .iter()
+                    .filter_map(|row| Some([*row.first()?, *row.get(column)?]))
+                    .filter(|[x, y]| x.is_finite() && y.is_finite())
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_convergence_file() {
+        let xvg = Xvg::read("tests/output/convergence.xvg").unwrap();
+
+        assert_eq!(
+            xvg.title,
+            "Convergence of average order parameters for individual molecules"
+        );
+        assert_eq!(xvg.x_label, "Frame number");
+        assert_eq!(xvg.y_label, "-Sch");
+        assert_eq!(
+            xvg.legends,
+            vec![
+                String::from("POPC full"),
+                String::from("POPC upper"),
+                String::from("POPC lower")
+            ]
+        );
+        assert_eq!(xvg.rows.len(), 8);
+        assert_eq!(xvg.rows[0], vec![1.0, -0.0525, -0.0894, 0.0021]);
+
+        let series = xvg.series();
+        assert_eq!(series.len(), 3);
+        assert_eq!(series[1].name, "POPC upper");
+        assert_eq!(series[1].points[7], [15.0, -0.0847]);
+    }
+
+    #[test]
+    fn parse_invalid_xvg() {
+        assert!(Xvg::parse("# only comments\n@TYPE xy\n").is_err());
+        assert!(Xvg::parse("1 2 3\n4 five 6\n").is_err());
+    }
+
+    #[test]
+    fn series_without_legends() {
+        let xvg = Xvg::parse("1 0.5\n2 nan\n3 0.7\n").unwrap();
+        let series = xvg.series();
+
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].name, "column 1");
+        assert_eq!(series[0].points, vec![[1.0, 0.5], [3.0, 0.7]]);
+    }
+}
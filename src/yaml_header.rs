@@ -0,0 +1,129 @@
+// Released under MIT License.
+// Copyright (c) 2025 Ladislav Bartos
+
+//! Header of the exported configuration YAML files.
+
+use std::fmt::Display;
+
+use chrono::{DateTime, TimeZone};
+use eframe::egui::{self, Id, Ui};
+use serde::{Deserialize, Serialize};
+
+use crate::GUIORDER_VERSION;
+
+/// Comment written at the start of the exported configuration YAML files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub(crate) enum YamlHeader {
+    /// No header. Useful for configuration files under version control.
+    None,
+    /// A single line stating the version of guiorder.
+    #[default]
+    Comment,
+    /// Versions of guiorder and gorder and the date of the export.
+    Full,
+}
+
+impl Display for YamlHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            YamlHeader::None => write!(f, "None"),
+            YamlHeader::Comment => write!(f, "Comment"),
+            YamlHeader::Full => write!(f, "Full"),
+        }
+    }
+}
+
+impl YamlHeader {
+    fn id() -> Id {
+        Id::new("guiorder_yaml_header")
+    }
+
+    /// Get the currently selected header.
+    pub(crate) fn get(ctx: &egui::Context) -> Self {
+        ctx.data_mut(|d| d.get_persisted::<YamlHeader>(Self::id()))
+            .unwrap_or_default()
+    }
+
+    /// Select the header of all exported configuration YAML files.
+    pub(crate) fn set(ctx: &egui::Context, header: YamlHeader) {
+        ctx.data_mut(|d| d.insert_persisted(Self::id(), header));
+    }
+
+    /// Get the description of the header shown on hover.
+    fn description(self) -> &'static str {
+        match self {
+            YamlHeader::None => {
+                "Do not write any header. Exported files only change when the options change."
+            }
+            YamlHeader::Comment => "Write a comment with the version of guiorder.",
+            YamlHeader::Full => {
+                "Write the versions of guiorder and gorder and the date of the export."
+            }
+        }
+    }
+
+    /// Get the header for a file exported at `time`.
+    /// The export time is written in the time zone of `time` together with its UTC offset.
+    pub(crate) fn text<Tz: TimeZone>(self, time: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: Display,
+    {
+        let comment = format!(
+            "# Analysis options generated by 'guiorder v{}'.\n",
+            GUIORDER_VERSION
+        );
+
+        match self {
+            YamlHeader::None => String::new(),
+            YamlHeader::Comment => comment,
+            YamlHeader::Full => format!(
+                "{}# gorder version: {}\n# Exported on {}.\n",
+                comment,
+                gorder::GORDER_VERSION,
+                time.format("%Y-%m-%d %H:%M:%S %:z")
+            ),
+        }
+    }
+}
+
+/// Selector for the header of the exported configuration YAML files.
+pub(crate) fn header_selector(ui: &mut Ui) {
+    let mut header = YamlHeader::get(ui.ctx());
+    let previous = header;
+
+    ui.horizontal(|ui| {
+        ui.label("YAML header:");
+        for variant in [YamlHeader::None, YamlHeader::Comment, YamlHeader::Full] {
+            ui.selectable_value(&mut header, variant, format!("{}", variant))
+                .on_hover_ui(|ui| {
+                    ui.label(variant.description());
+                });
+        }
+    });
+
+    if header != previous {
+        YamlHeader::set(ui.ctx(), header);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_texts() {
+        assert_eq!(YamlHeader::None.text(&time), "");
+        assert_eq!(
+            YamlHeader::Comment.text(&time),
+            format!(
+                "# Analysis options generated by 'guiorder v{}'.\n",
+                GUIORDER_VERSION
+            )
+        );
+
+        let full = YamlHeader::Full.text(&time);
+        assert!(full.starts_with(&YamlHeader::Comment.text(&time)));
+        assert!(full.ends_with("# Exported on 2023-11-14 22:13:20 +00:00.\n"));
+        assert_eq!(full.lines().count(), 3);
+    }
+}